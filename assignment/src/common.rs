@@ -8,7 +8,9 @@ use std::{
 
 use eyre::eyre;
 use itertools::Itertools;
-use num::{BigRational, One, Signed};
+use leptos::*;
+use num::{pow::Pow, BigInt, BigRational, One, Signed, Zero};
+use thiserror::Error;
 
 pub use crate::linalg::ReducedRowEchelonForm;
 
@@ -17,7 +19,7 @@ pub struct Matrix<T>(pub Vec<Vec<T>>);
 
 impl<T> Matrix<T> {
     pub fn shape(&self) -> (usize, usize) {
-        (self.0.len(), self.0[0].len())
+        (self.0.len(), self.0.first().map_or(0, Vec::len))
     }
 
     pub fn map<F, U>(&self, f: F) -> Matrix<U>
@@ -30,6 +32,230 @@ impl<T> Matrix<T> {
                 .collect_vec(),
         )
     }
+
+    /// Combine elements at the same positions, `None` if the shapes differ.
+    pub fn zip_with<U, V, F>(&self, other: &Matrix<U>, f: F) -> Option<Matrix<V>>
+    where
+        F: Fn(&T, &U) -> V,
+    {
+        if self.len() != other.len()
+            || self
+                .iter()
+                .zip(other.iter())
+                .any(|(a, b)| a.len() != b.len())
+        {
+            return None;
+        }
+        Some(Matrix(
+            self.iter()
+                .zip(other.iter())
+                .map(|(a, b)| a.iter().zip(b.iter()).map(|(a, b)| f(a, b)).collect_vec())
+                .collect_vec(),
+        ))
+    }
+}
+
+#[test]
+fn test_zip_with() {
+    let a = "1 0\n0 2".parse::<Matrix<BigInt>>().unwrap();
+    let b = Matrix(vec![vec![true, false], vec![true, true]]);
+    assert_eq!(
+        a.zip_with(&b, |x, y| x.is_positive() && *y),
+        Some(Matrix(vec![vec![true, false], vec![false, true]]))
+    );
+    assert_eq!(a.zip_with(&Matrix(vec![vec![true]]), |_, y| *y), None);
+}
+
+impl<T: Clone> Matrix<T> {
+    pub fn transpose(&self) -> Self {
+        let (m, n) = self.shape();
+        Matrix(
+            (0..n)
+                .map(|j| (0..m).map(|i| self[i][j].clone()).collect_vec())
+                .collect_vec(),
+        )
+    }
+
+    /// Columns of `other` placed after those of `self`, `None` if the row counts differ.
+    pub fn hstack(&self, other: &Matrix<T>) -> Option<Matrix<T>> {
+        if self.len() != other.len() {
+            return None;
+        }
+        Some(Matrix(
+            self.iter()
+                .zip(other.iter())
+                .map(|(a, b)| a.iter().chain(b).cloned().collect_vec())
+                .collect_vec(),
+        ))
+    }
+}
+
+impl<T: Clone> Matrix<T> {
+    /// Entries at the given rows and columns in the given order, panics if an index is out of
+    /// range.
+    pub fn submatrix(&self, rows: &[usize], cols: &[usize]) -> Matrix<T> {
+        let (m, n) = self.shape();
+        if let Some(i) = rows.iter().find(|&&i| i >= m) {
+            panic!("row {i} is out of range for a matrix of {m} rows");
+        }
+        if let Some(j) = cols.iter().find(|&&j| j >= n) {
+            panic!("column {j} is out of range for a matrix of {n} columns");
+        }
+        Matrix(
+            rows.iter()
+                .map(|&i| cols.iter().map(|&j| self[i][j].clone()).collect_vec())
+                .collect_vec(),
+        )
+    }
+
+    /// The matrix with row `skip_row` and column `skip_col` deleted.
+    pub fn minor(&self, skip_row: usize, skip_col: usize) -> Matrix<T> {
+        let (m, n) = self.shape();
+        assert!(
+            skip_row < m,
+            "row {skip_row} is out of range for a matrix of {m} rows"
+        );
+        assert!(
+            skip_col < n,
+            "column {skip_col} is out of range for a matrix of {n} columns"
+        );
+        self.submatrix(
+            &(0..m).filter(|&i| i != skip_row).collect_vec(),
+            &(0..n).filter(|&j| j != skip_col).collect_vec(),
+        )
+    }
+}
+
+#[test]
+fn test_submatrix_and_minor() {
+    let a = "1 2 3\n4 5 6\n7 8 9".parse::<Matrix<BigInt>>().unwrap();
+    assert_eq!(a.minor(1, 1), "1 3\n7 9".parse().unwrap());
+    assert_eq!(a.minor(2, 0), "2 3\n5 6".parse().unwrap());
+    assert_eq!(a.submatrix(&[2, 0], &[1]), "8\n2".parse().unwrap());
+    assert_eq!(a.submatrix(&[], &[]).shape(), (0, 0));
+    let one = "1".parse::<Matrix<BigInt>>().unwrap();
+    assert_eq!(one.minor(0, 0).shape(), (0, 0));
+}
+
+#[test]
+#[should_panic(expected = "row 3 is out of range")]
+fn test_minor_out_of_range() {
+    "1 2\n3 4".parse::<Matrix<BigInt>>().unwrap().minor(3, 0);
+}
+
+impl<T> Matrix<T> {
+    /// Panics if the length of `col` is not the row count.
+    pub fn append_column(&mut self, col: Vec<T>) {
+        assert_eq!(
+            col.len(),
+            self.len(),
+            "column length does not match the row count"
+        );
+        for (row, x) in self.iter_mut().zip(col) {
+            row.push(x);
+        }
+    }
+}
+
+#[test]
+fn test_hstack_and_append_column() {
+    let a = "1 2\n3 4".parse::<Matrix<BigInt>>().unwrap();
+    let b = "5\n6".parse::<Matrix<BigInt>>().unwrap();
+    assert_eq!(a.hstack(&b), Some("1 2 5\n3 4 6".parse().unwrap()));
+    assert_eq!(a.hstack(&"5".parse().unwrap()), None);
+    let mut c = a.clone();
+    c.append_column(vec![5.into(), 6.into()]);
+    assert_eq!(Some(c), a.hstack(&b));
+    let mut d = Matrix::<BigInt>(vec![vec![], vec![]]);
+    d.append_column(vec![1.into(), 2.into()]);
+    assert_eq!(d.shape(), (2, 1));
+}
+
+impl Matrix<bool> {
+    /// Elementwise or of two matrices of the same shape.
+    pub fn or(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| *a || *b)
+            .expect("matrices of different shapes")
+    }
+
+    /// Boolean product, `None` if the column count of `self` is not the row count of `other`.
+    pub fn bool_product(&self, other: &Self) -> Option<Self> {
+        let ((m, n), (p, q)) = (self.shape(), other.shape());
+        if n != p {
+            return None;
+        }
+        Some(Matrix(
+            (0..m)
+                .map(|i| {
+                    (0..q)
+                        .map(|j| (0..n).any(|k| self[i][k] && other[k][j]))
+                        .collect_vec()
+                })
+                .collect_vec(),
+        ))
+    }
+}
+
+#[test]
+fn test_bool_product() {
+    let a = Matrix(vec![vec![false, true], vec![false, false]]);
+    let b = Matrix(vec![vec![true, false], vec![false, true]]);
+    assert_eq!(a.bool_product(&b), Some(a.clone()));
+    assert_eq!(
+        a.bool_product(&a),
+        Some(Matrix(vec![vec![false, false], vec![false, false]]))
+    );
+    assert_eq!(a.bool_product(&Matrix(vec![vec![true]])), None);
+}
+
+impl Matrix<BigRational> {
+    /// Parse comma-separated rows, skipping empty lines.
+    pub fn from_csv(s: &str) -> eyre::Result<Self> {
+        let rows = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .enumerate()
+            .map(|(i, line)| {
+                line.split(',')
+                    .map(|element| {
+                        parse_rational(element.trim()).map_err(|_| {
+                            eyre!("第 {} 行的元素 {:?} 无法解析", i + 1, element.trim())
+                        })
+                    })
+                    .try_collect::<_, Vec<_>, _>()
+            })
+            .try_collect::<_, Vec<_>, _>()?;
+        if let Some(first) = rows.first() {
+            if let Some((i, row)) = rows.iter().find_position(|row| row.len() != first.len()) {
+                return Err(eyre!(
+                    "第 {} 行有 {} 列, 应为 {} 列",
+                    i + 1,
+                    row.len(),
+                    first.len()
+                ));
+            }
+        }
+        Ok(Self(rows))
+    }
+
+    pub fn to_csv(&self) -> String {
+        self.iter().map(|row| row.iter().join(",")).join("\n")
+    }
+}
+
+#[test]
+fn test_csv_round_trip() {
+    let matrix = "1 -1/2 0.25\n3 0 7".parse::<Matrix<BigRational>>().unwrap();
+    assert_eq!(matrix.to_csv(), "1,-1/2,1/4\n3,0,7");
+    assert_eq!(Matrix::from_csv(&matrix.to_csv()).unwrap(), matrix);
+    assert_eq!(
+        Matrix::from_csv(" 1 , 2 \n\n3,4\n").unwrap(),
+        "1 2\n3 4".parse::<Matrix<BigRational>>().unwrap()
+    );
+    assert_eq!(
+        Matrix::from_csv("1,2\n3").unwrap_err().to_string(),
+        "第 2 行有 1 列, 应为 2 列"
+    );
 }
 
 impl<T> Deref for Matrix<T> {
@@ -46,37 +272,357 @@ impl<T> DerefMut for Matrix<T> {
     }
 }
 
+/// Why an element of a [`Matrix`] failed to parse.
+#[derive(Error, Debug, Clone, Copy, PartialEq)]
+pub enum ElementParseError {
+    #[error("分母为零")]
+    ZeroDenominator,
+    #[error("分数格式错误")]
+    MalformedFraction,
+    #[error("小数格式错误")]
+    MalformedDecimal,
+    #[error("不是整数")]
+    NotAnInteger,
+    #[error("不是数")]
+    NotANumber,
+}
+
+/// Element of a [`Matrix`] parsed from text.
+pub trait ParseElement: Sized {
+    fn parse_element(s: &str) -> Result<Self, ElementParseError>;
+}
+
+impl ParseElement for BigInt {
+    fn parse_element(s: &str) -> Result<Self, ElementParseError> {
+        s.parse::<BigInt>()
+            .map_err(|_| ElementParseError::NotAnInteger)
+    }
+}
+
+impl ParseElement for BigRational {
+    fn parse_element(s: &str) -> Result<Self, ElementParseError> {
+        parse_rational(s)
+    }
+}
+
+/// Parse an integer (`-3`), a fraction (`1/2`) or a decimal (`-0.5`) exactly.
+pub fn parse_rational(s: &str) -> Result<BigRational, ElementParseError> {
+    if let Some((numer, denom)) = s.split_once('/') {
+        let (Ok(numer), Ok(denom)) = (numer.parse::<BigInt>(), denom.parse::<BigInt>()) else {
+            return Err(ElementParseError::MalformedFraction);
+        };
+        if denom.is_zero() {
+            return Err(ElementParseError::ZeroDenominator);
+        }
+        return Ok(BigRational::new(numer, denom));
+    }
+    let Some((int_part, frac_part)) = s.split_once('.') else {
+        return s
+            .parse::<BigInt>()
+            .map(BigRational::from_integer)
+            .map_err(|_| ElementParseError::NotANumber);
+    };
+    let (negative, int_part) = match int_part.strip_prefix('-') {
+        Some(int_part) => (true, int_part),
+        None => (false, int_part.strip_prefix('+').unwrap_or(int_part)),
+    };
+    if (int_part.is_empty() && frac_part.is_empty())
+        || !int_part.chars().all(|c| c.is_ascii_digit())
+        || !frac_part.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(ElementParseError::MalformedDecimal);
+    }
+    let numer = format!("{int_part}{frac_part}")
+        .parse::<BigInt>()
+        .map_err(|_| ElementParseError::MalformedDecimal)?;
+    let denom = BigInt::from(10).pow(frac_part.len() as u32);
+    let value = BigRational::new(numer, denom);
+    Ok(if negative { -value } else { value })
+}
+
+#[test]
+fn test_parse_rational() {
+    assert_eq!(
+        parse_rational("0.1").unwrap(),
+        BigRational::new(1.into(), 10.into())
+    );
+    assert_eq!(
+        parse_rational("-2.5").unwrap(),
+        BigRational::new((-5).into(), 2.into())
+    );
+    assert_eq!(
+        parse_rational("1.25").unwrap(),
+        BigRational::new(5.into(), 4.into())
+    );
+    assert_eq!(
+        parse_rational("-3/6").unwrap(),
+        BigRational::new((-1).into(), 2.into())
+    );
+    assert_eq!(
+        parse_rational("7").unwrap(),
+        BigRational::from_integer(7.into())
+    );
+    assert!(parse_rational(".").is_err());
+    assert!(parse_rational("1.2.3").is_err());
+    assert!(parse_rational("1.-2").is_err());
+    assert_eq!(
+        parse_rational("1/0"),
+        Err(ElementParseError::ZeroDenominator)
+    );
+    assert_eq!(
+        parse_rational("1//2"),
+        Err(ElementParseError::MalformedFraction)
+    );
+    assert_eq!(parse_rational("abc"), Err(ElementParseError::NotANumber));
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MatrixParseError {
+    #[error("矩阵第 {line} 行有 {found} 个元素, 应为 {expected} 个")]
+    RaggedRows {
+        expected: usize,
+        found: usize,
+        line: usize,
+    },
+    #[error("矩阵第 {line} 行第 {col} 个元素无法解析: {text:?} ({reason})")]
+    InvalidEntry {
+        text: String,
+        line: usize,
+        col: usize,
+        reason: ElementParseError,
+    },
+    #[error("矩阵第 {line} 行竖线后必须恰有一个元素")]
+    MisplacedBar { line: usize },
+    #[error("输入为空")]
+    Empty,
+}
+
+impl MatrixParseError {
+    /// The error with its line moved down by `offset`, for input parsed after leading lines.
+    pub fn offset_line(self, offset: usize) -> Self {
+        match self {
+            Self::RaggedRows {
+                expected,
+                found,
+                line,
+            } => Self::RaggedRows {
+                expected,
+                found,
+                line: line + offset,
+            },
+            Self::InvalidEntry {
+                text,
+                line,
+                col,
+                reason,
+            } => Self::InvalidEntry {
+                text,
+                line: line + offset,
+                col,
+                reason,
+            },
+            Self::MisplacedBar { line } => Self::MisplacedBar {
+                line: line + offset,
+            },
+            Self::Empty => Self::Empty,
+        }
+    }
+}
+
+#[test]
+fn test_offset_line() {
+    let e = "1 2\n3".parse::<Matrix<BigInt>>().unwrap_err();
+    assert_eq!(
+        e.offset_line(1),
+        MatrixParseError::RaggedRows {
+            expected: 2,
+            found: 1,
+            line: 3
+        }
+    );
+    assert_eq!(
+        MatrixParseError::Empty.offset_line(1),
+        MatrixParseError::Empty
+    );
+}
+
 impl<T> FromStr for Matrix<T>
 where
-    T: FromStr,
+    T: ParseElement,
 {
-    type Err = eyre::Report;
+    type Err = MatrixParseError;
 
+    /// Rows are separated by newlines, or by closing brackets if there are any, e.g.
+    /// `[[1, 2], [3, 4]]`. Elements are separated by whitespace or commas. Lines in errors
+    /// count these rows, empty ones included.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.split('\n')
-            .filter_map(|s| {
-                match s
-                    .split_whitespace()
-                    .map(|s| s.parse::<T>())
-                    .try_collect::<_, Vec<T>, _>()
-                {
-                    Ok(v) if v.is_empty() => None,
-                    x => Some(x),
-                }
-            })
-            .try_collect()
-            .ok()
-            .and_then(|v: Vec<Vec<T>>| {
-                if v.iter().map(|v| v.len()).all_equal() {
-                    Some(Self(v))
-                } else {
-                    None
+        let rows = if s.contains('[') {
+            s.split(']').collect_vec()
+        } else {
+            s.split('\n').collect_vec()
+        };
+        let mut matrix: Vec<Vec<T>> = Vec::new();
+        for (i, row) in rows.into_iter().enumerate() {
+            let row = row
+                .split(|c: char| c.is_whitespace() || c == ',' || c == '[')
+                .filter(|s| !s.is_empty())
+                .enumerate()
+                .map(|(j, text)| {
+                    T::parse_element(text).map_err(|reason| MatrixParseError::InvalidEntry {
+                        text: text.to_string(),
+                        line: i + 1,
+                        col: j + 1,
+                        reason,
+                    })
+                })
+                .try_collect::<_, Vec<T>, _>()?;
+            if row.is_empty() {
+                continue;
+            }
+            if let Some(first) = matrix.first() {
+                if first.len() != row.len() {
+                    return Err(MatrixParseError::RaggedRows {
+                        expected: first.len(),
+                        found: row.len(),
+                        line: i + 1,
+                    });
                 }
-            })
-            .ok_or(eyre!("failed to parse Matrix"))
+            }
+            matrix.push(row);
+        }
+        if matrix.is_empty() {
+            Err(MatrixParseError::Empty)
+        } else {
+            Ok(Self(matrix))
+        }
     }
 }
 
+#[test]
+fn test_matrix_parse_error() {
+    assert_eq!(
+        "1 2\n\n3".parse::<Matrix<BigInt>>(),
+        Err(MatrixParseError::RaggedRows {
+            expected: 2,
+            found: 1,
+            line: 3
+        })
+    );
+    assert_eq!(
+        "1 2\n3 x".parse::<Matrix<BigInt>>(),
+        Err(MatrixParseError::InvalidEntry {
+            text: "x".to_string(),
+            line: 2,
+            col: 2,
+            reason: ElementParseError::NotAnInteger,
+        })
+    );
+    assert_eq!(
+        "1 1/0"
+            .parse::<Matrix<BigRational>>()
+            .unwrap_err()
+            .to_string(),
+        r#"矩阵第 1 行第 2 个元素无法解析: "1/0" (分母为零)"#
+    );
+    assert_eq!(
+        "abc"
+            .parse::<Matrix<BigRational>>()
+            .unwrap_err()
+            .to_string(),
+        r#"矩阵第 1 行第 1 个元素无法解析: "abc" (不是数)"#
+    );
+    assert_eq!(
+        "\n \n".parse::<Matrix<BigInt>>(),
+        Err(MatrixParseError::Empty)
+    );
+}
+
+#[test]
+fn test_shape_empty() {
+    assert_eq!(Matrix::<BigRational>(vec![]).shape(), (0, 0));
+}
+
+#[test]
+fn test_parse_matrix_bracketed() {
+    let expected = Matrix(vec![
+        vec![BigInt::from(1), BigInt::from(2)],
+        vec![BigInt::from(3), BigInt::from(4)],
+    ]);
+    assert_eq!("1 2\n3 4".parse::<Matrix<BigInt>>().unwrap(), expected);
+    assert_eq!(
+        "[[1, 2], [3, 4]]".parse::<Matrix<BigInt>>().unwrap(),
+        expected
+    );
+    assert_eq!(
+        "[1,2]\n[3,4]\n".parse::<Matrix<BigInt>>().unwrap(),
+        expected
+    );
+    assert_eq!("1, 2\n3, 4".parse::<Matrix<BigInt>>().unwrap(), expected);
+    assert!("[[1, 2], [3]]".parse::<Matrix<BigInt>>().is_err());
+}
+
+#[test]
+fn test_parse_matrix_mixed() {
+    let matrix = "1 0.5 -3/4\n-2.5 2 0.1\n"
+        .parse::<Matrix<BigRational>>()
+        .unwrap();
+    assert_eq!(
+        matrix,
+        Matrix(vec![
+            vec![
+                BigRational::from_integer(1.into()),
+                BigRational::new(1.into(), 2.into()),
+                BigRational::new((-3).into(), 4.into()),
+            ],
+            vec![
+                BigRational::new((-5).into(), 2.into()),
+                BigRational::from_integer(2.into()),
+                BigRational::new(1.into(), 10.into()),
+            ],
+        ])
+    );
+    assert!("1 2\n0.5".parse::<Matrix<BigRational>>().is_err());
+    assert!("1 0.5".parse::<Matrix<BigInt>>().is_err());
+}
+
+/// Whether the first line of `input` is `keyword`, and the input after that line if so.
+pub fn split_keyword_line<'a>(input: &'a str, keyword: &str) -> (bool, &'a str) {
+    let (first, rest) = input.trim_start().split_once('\n').unwrap_or((input, ""));
+    if first.trim() == keyword {
+        (true, rest)
+    } else {
+        (false, input)
+    }
+}
+
+/// Lines of `input` before its suffix `rest`, to offset the lines in errors from parsing
+/// `rest`. Zero for bracket notation, whose rows are not counted by lines.
+pub fn lines_before(input: &str, rest: &str) -> usize {
+    if rest.contains('[') {
+        0
+    } else {
+        input[..input.len() - rest.len()].matches('\n').count()
+    }
+}
+
+#[test]
+fn test_split_keyword_line() {
+    assert_eq!(split_keyword_line("\n rows \n1 2", "rows"), (true, "1 2"));
+    assert_eq!(split_keyword_line(" rows ", "rows"), (true, ""));
+    assert_eq!(split_keyword_line("1 2\n3 4", "rows"), (false, "1 2\n3 4"));
+    let input = "\n\nrows\n1 2\n3";
+    let (_, rest) = split_keyword_line(input, "rows");
+    let e = rest.parse::<Matrix<BigInt>>().unwrap_err();
+    assert_eq!(
+        e.offset_line(lines_before(input, rest)),
+        MatrixParseError::RaggedRows {
+            expected: 2,
+            found: 1,
+            line: 5
+        }
+    );
+}
+
 impl<T> Display for Matrix<T>
 where
     T: Display,
@@ -97,6 +643,7 @@ where
 
 pub trait ToTex {
     fn to_tex(&self) -> String;
+    #[allow(dead_code)]
     fn to_tex_with_positive_sign(&self) -> String;
     fn to_tex_with_paren(&self) -> String;
     fn to_tex_ignore_one(&self) -> String;
@@ -206,6 +753,76 @@ impl ToTex for BigRational {
     }
 }
 
+impl ToTex for BigInt {
+    fn to_tex(&self) -> String {
+        self.to_string()
+    }
+
+    fn to_tex_with_positive_sign(&self) -> String {
+        format!(r"{}{self}", if self.is_negative() { "" } else { "+" })
+    }
+
+    fn to_tex_with_paren(&self) -> String {
+        if self.is_negative() {
+            format!(r"\left({self}\right)")
+        } else {
+            self.to_string()
+        }
+    }
+
+    fn to_tex_ignore_one(&self) -> String {
+        if self.is_one() {
+            "".to_string()
+        } else if self.abs().is_one() {
+            "-".to_string()
+        } else {
+            self.to_string()
+        }
+    }
+
+    fn to_tex_with_sign_ignore_one(&self) -> String {
+        if self.is_one() {
+            "+".to_string()
+        } else if self.abs().is_one() {
+            "-".to_string()
+        } else {
+            self.to_tex_with_positive_sign()
+        }
+    }
+
+    fn sign_to_tex(&self) -> String {
+        if self.is_positive() {
+            "".to_string()
+        } else {
+            "-".to_string()
+        }
+    }
+
+    fn sign_to_tex_with_positive_sign(&self) -> String {
+        if self.is_positive() {
+            "+".to_string()
+        } else {
+            "-".to_string()
+        }
+    }
+}
+
+#[test]
+fn test_bigint_to_tex() {
+    let n = BigInt::from(-5);
+    assert_eq!(n.to_tex(), "-5");
+    assert_eq!(n.to_tex_with_paren(), r"\left(-5\right)");
+    assert_eq!(BigInt::from(5).to_tex_with_paren(), "5");
+    assert_eq!(BigInt::from(3).to_tex_with_positive_sign(), "+3");
+    assert_eq!(BigInt::from(-1).to_tex_ignore_one(), "-");
+    assert_eq!(BigInt::from(1).to_tex_with_sign_ignore_one(), "+");
+    let matrix = "1 -2\n0 3".parse::<Matrix<BigInt>>().unwrap();
+    assert_eq!(
+        matrix.to_tex_with_paren(),
+        r"1 & \left(-2\right) \\[1ex] 0 & 3"
+    );
+}
+
 impl<T> ToTex for Matrix<T>
 where
     T: ToTex,
@@ -238,3 +855,136 @@ where
         self.map(T::sign_to_tex_with_positive_sign).to_string()
     }
 }
+
+/// Places after the decimal point when results are shown as decimals, unless changed.
+pub const DEFAULT_DECIMAL_DIGITS: usize = 3;
+
+pub trait ToDecimalString {
+    /// Rounded half away from zero to `digits` places after the decimal point.
+    fn to_decimal_string(&self, digits: usize) -> String;
+}
+
+impl ToDecimalString for BigRational {
+    fn to_decimal_string(&self, digits: usize) -> String {
+        let numer = self.numer().abs() * BigInt::from(10).pow(digits as u32);
+        let denom = self.denom();
+        // long division with the next digit rounded in
+        let rounded: BigInt = (numer * 2 + denom) / (denom * 2);
+        let s = format!("{rounded:0width$}", width = digits + 1);
+        let (int_part, frac_part) = s.split_at(s.len() - digits);
+        let sign = if self.is_negative() && !rounded.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        if digits == 0 {
+            format!("{sign}{int_part}")
+        } else {
+            format!("{sign}{int_part}.{frac_part}")
+        }
+    }
+}
+
+impl<T> ToDecimalString for Matrix<T>
+where
+    T: ToDecimalString,
+{
+    fn to_decimal_string(&self, digits: usize) -> String {
+        self.map(|x| x.to_decimal_string(digits)).to_string()
+    }
+}
+
+#[test]
+fn test_to_decimal_string() {
+    let decimal = |n: i32, d: i32, digits: usize| {
+        BigRational::new(n.into(), d.into()).to_decimal_string(digits)
+    };
+    assert_eq!(decimal(1, 3, 3), "0.333");
+    assert_eq!(decimal(2, 3, 3), "0.667");
+    assert_eq!(decimal(-17, 23, 3), "-0.739");
+    assert_eq!(decimal(5, 2, 0), "3");
+    assert_eq!(decimal(-1, 3000, 3), "0.000");
+    assert_eq!(decimal(-1, 2000, 3), "-0.001");
+    assert_eq!(decimal(7, 1, 2), "7.00");
+}
+
+/// Checkbox to show results as decimals and the number of places after the decimal point.
+#[component]
+pub fn DecimalToggle(decimal: RwSignal<bool>, digits: RwSignal<usize>) -> impl IntoView {
+    view! {
+        <label class="ml-4 font-normal">
+            <input
+                type="checkbox"
+                class="mr-1"
+                on:change=move |ev| decimal.set(event_target_checked(&ev))
+            />
+            "显示为小数"
+        </label>
+        <Show when=move || decimal.get()>
+            <label class="ml-4 font-normal">
+                "保留 "
+                <input
+                    type="number"
+                    class="w-12 border"
+                    min="0"
+                    max="30"
+                    prop:value=move || digits.get().to_string()
+                    on:change=move |ev| {
+                        if let Ok(value) = event_target_value(&ev).parse::<usize>() {
+                            digits.set(value.min(30));
+                        }
+                    }
+                />
+                " 位小数"
+            </label>
+        </Show>
+    }
+}
+
+/// Checkbox to reveal steps one at a time, with buttons and a slider to move between them.
+/// `shown` is the number of steps revealed, `None` if all are shown.
+#[component]
+pub fn StepPlayback(count: usize, shown: RwSignal<Option<usize>>) -> impl IntoView {
+    let step = move |delta: isize| {
+        shown.update(|shown| {
+            if let Some(shown) = shown {
+                *shown = shown.saturating_add_signed(delta).clamp(1, count);
+            }
+        })
+    };
+    view! {
+        <label class="ml-4 font-normal">
+            <input
+                type="checkbox"
+                class="mr-1"
+                on:change=move |ev| shown.set(event_target_checked(&ev).then_some(1))
+            />
+            "逐步显示"
+        </label>
+        <Show when=move || shown.get().is_some()>
+            <button
+                class="ml-4 px-2 font-normal border rounded"
+                prop:disabled=move || shown.get() <= Some(1)
+                on:click=move |_| step(-1)
+            > "上一步" </button>
+            <input
+                type="range"
+                class="ml-2 align-middle"
+                min="1"
+                max=count
+                prop:value=move || shown.get().unwrap_or(count).to_string()
+                on:input=move |ev| {
+                    if let Ok(value) = event_target_value(&ev).parse::<usize>() {
+                        shown.set(Some(value.clamp(1, count)));
+                    }
+                }
+            />
+            <button
+                class="ml-2 px-2 font-normal border rounded"
+                prop:disabled=move || shown.get() >= Some(count)
+                on:click=move |_| step(1)
+            > "下一步" </button>
+            <span class="ml-2 font-normal"> { move || format!("{} / {count}", shown.get().unwrap_or(count)) } </span>
+        </Show>
+    }
+}
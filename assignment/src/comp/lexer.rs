@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{collections::HashSet, fmt::Display};
 
 use indoc::indoc;
 use itertools::Itertools;
@@ -16,11 +16,11 @@ pub struct PositionedChar {
 #[allow(dead_code)]
 #[derive(Error, Debug, Clone)]
 pub enum PreprocessError {
-    #[error("invalid character {c:?}")]
+    #[error("非法字符 {c:?}")]
     InvalidChar { c: char, row: usize, col: usize },
-    #[error("unexpected EOF inside block comment")]
+    #[error("块注释未结束")]
     EofWhileBlockComment { row: usize, col: usize },
-    #[error("nested block comment is not implemented")]
+    #[error("不支持嵌套的块注释")]
     NestedBlockComment { row: usize, col: usize },
 }
 
@@ -392,9 +392,9 @@ pub struct LiteralInt {
 #[allow(dead_code)]
 #[derive(Error, Debug, Clone)]
 pub enum LexError {
-    #[error("unexpected {c:?}")]
+    #[error("意外的字符 {c:?}")]
     UnexpectedChar { c: char, row: usize, col: usize },
-    #[error("unexpected EOF")]
+    #[error("意外的输入结束")]
     UnexpectedEof { row: usize, col: usize },
 }
 
@@ -1793,12 +1793,12 @@ pub fn mark_erroneous_source(
         row => row,
     };
     let col = match col {
-        usize::MAX => input.split('\n').last().unwrap().len() + 1,
+        usize::MAX => input.split('\n').next_back().unwrap().len() + 1,
         col => col,
     };
     let line_num_len = row.to_string().len();
     let line_num_space = " ".repeat(line_num_len);
-    let message = format!("error: {message}");
+    let message = format!("错误: {message}");
     let input_info = format!("{line_num_space}--> input:{row}:{col}");
     let line_num = format!("{row} | ");
     let line = input.splitn(row + 1, '\n').nth(row - 1).unwrap();
@@ -1808,6 +1808,252 @@ pub fn mark_erroneous_source(
     format!("{message}\n{input_info}\n{line_num}{line}\n{line_num_marker}{space}{marker}")
 }
 
+/// Char indices where each line of `chars` starts.
+fn line_starts(chars: &[char]) -> Vec<usize> {
+    [0].into_iter()
+        .chain(chars.iter().positions(|c| *c == '\n').map(|i| i + 1))
+        .collect_vec()
+}
+
+/// Char index of the 1-based `row:col`, if it lies within `len` chars.
+fn char_index(line_starts: &[usize], len: usize, row: usize, col: usize) -> Option<usize> {
+    line_starts
+        .get(row.wrapping_sub(1))
+        .map(|start| start + col - 1)
+        .filter(|i| *i < len)
+}
+
+/// Source with each token in a span highlighted while its row in the table is hovered.
+fn token_positions_view(source: &str, tokens: &[Token]) -> View {
+    let (hovered, set_hovered) = create_signal(None);
+    let chars = source.chars().collect_vec();
+    let line_starts = line_starts(&chars);
+    let mut segments = vec![];
+    let mut end = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        let Some(start) = char_index(&line_starts, chars.len(), token.row, token.col) else {
+            continue;
+        };
+        if start < end {
+            continue;
+        }
+        let len = token.raw.chars().count();
+        segments.push((chars[end..start].iter().collect::<String>(), None));
+        segments.push((chars[start..start + len].iter().collect(), Some(i)));
+        end = start + len;
+    }
+    segments.push((chars[end..].iter().collect(), None));
+    view! {
+        <pre class="mb-4"> {
+            segments
+                .into_iter()
+                .map(|(text, i)| match i {
+                    Some(i) => view! {
+                        <span class:bg-yellow-200=move || hovered() == Some(i)> { text } </span>
+                    }
+                    .into_view(),
+                    None => text.into_view(),
+                })
+                .collect_vec()
+        } </pre>
+        <table class="font-mono"> {
+            tokens
+                .iter()
+                .enumerate()
+                .map(|(i, token)| view! {
+                    <tr
+                        class="hover:bg-yellow-200"
+                        on:mouseenter=move |_| set_hovered(Some(i))
+                        on:mouseleave=move |_| set_hovered(None)
+                    >
+                        <td class="pr-8 whitespace-pre"> { token.raw.clone() } </td>
+                        <td class="text-right"> { format!("{}:{}", token.row, token.col) } </td>
+                    </tr>
+                })
+                .collect_vec()
+        } </table>
+    }
+    .into_view()
+}
+
+/// Each source line split into runs of chars kept or removed by [`preprocess`], and the
+/// preprocessed chars positioned on that line.
+fn preprocess_diff(
+    source: &str,
+    preprocessed: &[PositionedChar],
+) -> Vec<(Vec<(String, bool)>, String)> {
+    let kept: HashSet<_> = preprocessed.iter().collect();
+    source
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            let runs = line
+                .chars()
+                .enumerate()
+                .map(|(j, c)| {
+                    let kept = kept.contains(&PositionedChar {
+                        c,
+                        row: i + 1,
+                        col: j + 1,
+                    });
+                    (c, kept)
+                })
+                .chunk_by(|(_, kept)| *kept)
+                .into_iter()
+                .map(|(kept, chars)| (chars.map(|(c, _)| c).collect::<String>(), kept))
+                .collect_vec();
+            let preprocessed = preprocessed
+                .iter()
+                .filter(|pc| pc.row == i + 1)
+                .map(|pc| pc.c)
+                .collect();
+            (runs, preprocessed)
+        })
+        .collect_vec()
+}
+
+#[test]
+fn test_preprocess_diff() {
+    let source = "a /* b */ c\n// d\ne";
+    let diff = preprocess_diff(source, &preprocess(source.to_string()).unwrap());
+    assert_eq!(
+        diff[0],
+        (
+            vec![
+                ("a ".to_string(), true),
+                ("/* b */ ".to_string(), false),
+                ("c".to_string(), true)
+            ],
+            // the newline becomes a space
+            "a c ".to_string()
+        )
+    );
+    assert_eq!(diff[1], (vec![("// d".to_string(), false)], "".to_string()));
+    assert_eq!(diff[2], (vec![("e".to_string(), true)], "e".to_string()));
+}
+
+/// Source lines beside their preprocessed chars, with removed chars struck through.
+fn preprocess_diff_view(source: &str, preprocessed: &[PositionedChar]) -> View {
+    view! {
+        <table class="font-mono"> {
+            preprocess_diff(source, preprocessed)
+                .into_iter()
+                .map(|(runs, preprocessed)| view! {
+                    <tr>
+                        <td class="pr-8 whitespace-pre align-top"> {
+                            runs.into_iter().map(|(text, kept)| if kept {
+                                text.into_view()
+                            } else {
+                                view! { <span class="line-through text-gray-400"> { text } </span> }.into_view()
+                            }).collect_vec()
+                        } </td>
+                        <td class="whitespace-pre align-top border-l pl-8"> { preprocessed } </td>
+                    </tr>
+                })
+                .collect_vec()
+        } </table>
+    }
+    .into_view()
+}
+
+/// Unexpected chars skipped before highlighting falls back to plain text.
+const MAX_HIGHLIGHT_ERRORS: usize = 32;
+
+const COMMENT_COLOR: &str = "rgb(156, 163, 175)";
+
+/// Colored segments of `source` by its tokens, skipping unexpected chars.
+pub fn highlight(source: &str) -> Vec<(String, Option<String>)> {
+    let plain = vec![(source.to_string(), None)];
+    let chars = source.chars().collect_vec();
+    let line_starts = line_starts(&chars);
+    let index = |row, col| char_index(&line_starts, chars.len(), row, col);
+    let mut skipped = chars.clone();
+    let mut errors = vec![];
+    let tokens = loop {
+        if errors.len() > MAX_HIGHLIGHT_ERRORS {
+            return plain;
+        }
+        let lexed = match preprocess(skipped.iter().collect()) {
+            Ok(preprocessed) => match lex(preprocessed) {
+                Ok(tokens) => break tokens,
+                Err(LexError::UnexpectedChar { c: _, row, col }) => index(row, col),
+                Err(LexError::UnexpectedEof { .. }) => None,
+            },
+            Err(PreprocessError::InvalidChar { c: _, row, col }) => index(row, col),
+            Err(_) => None,
+        };
+        let Some(i) = lexed else {
+            return plain;
+        };
+        skipped[i] = ' ';
+        errors.push(i);
+    };
+    let mut colors = chars
+        .iter()
+        .map(|c| (!c.is_whitespace()).then_some(COMMENT_COLOR))
+        .collect_vec();
+    for token in tokens {
+        let color = match token.token {
+            TokenValue::Ident(_) => Some("rgb(37, 99, 235)"),
+            TokenValue::Kw(_) => Some("rgb(147, 51, 234)"),
+            TokenValue::Op(_) => Some("rgb(234, 88, 12)"),
+            TokenValue::LiteralInt(_) => Some("rgb(22, 163, 74)"),
+            TokenValue::Sym(_) => None,
+        };
+        let Some(start) = index(token.row, token.col) else {
+            return plain;
+        };
+        for color_slot in colors
+            .iter_mut()
+            .skip(start)
+            .take(token.raw.chars().count())
+        {
+            *color_slot = color;
+        }
+    }
+    for i in errors {
+        colors[i] = Some("rgb(239, 68, 68)");
+    }
+    // spaces inside comments
+    for i in 1..chars.len() {
+        if chars[i] != '\n' && chars[i].is_whitespace() && colors[i - 1] == Some(COMMENT_COLOR) {
+            colors[i] = Some(COMMENT_COLOR);
+        }
+    }
+    chars
+        .into_iter()
+        .zip(colors)
+        .chunk_by(|(_, color)| *color)
+        .into_iter()
+        .map(|(color, chunk)| {
+            (
+                chunk.map(|(c, _)| c).collect(),
+                color.map(|color| color.to_string()),
+            )
+        })
+        .collect_vec()
+}
+
+#[test]
+fn test_highlight() {
+    let segments = highlight("int a; // x\na = 1 @ 2;");
+    assert_eq!(
+        segments.iter().map(|(text, _)| text.as_str()).join(""),
+        "int a; // x\na = 1 @ 2;"
+    );
+    let color = |text: &str| {
+        segments
+            .iter()
+            .find(|(t, _)| t == text)
+            .map(|(_, color)| color.clone())
+            .unwrap()
+    };
+    assert_eq!(color("int"), Some("rgb(147, 51, 234)".to_string()));
+    assert_eq!(color("@"), Some("rgb(239, 68, 68)".to_string()));
+    assert_eq!(color("// x"), Some("rgb(156, 163, 175)".to_string()));
+    assert_eq!(highlight("/* open"), [("/* open".to_string(), None)]);
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct LexerSolver;
 
@@ -1824,6 +2070,14 @@ impl Solver for LexerSolver {
         "输入类 C 语言源程序.".into_view()
     }
 
+    fn highlights_input(&self) -> bool {
+        true
+    }
+
+    fn highlight_input(&self, input: &str) -> Vec<(String, Option<String>)> {
+        highlight(input)
+    }
+
     fn default_input(&self) -> String {
         indoc! {"
             main()
@@ -1843,13 +2097,14 @@ impl Solver for LexerSolver {
                 return view! {
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "预处理" </p>
-                        <pre class="text-red-500"> { e.to_string_with_source(input) } </pre>
+                        { self.render_error(&e.to_string_with_source(input)) }
                     </div>
                 }
                 .into_view()
             }
         };
         let preprocessed_string: String = preprocessed.iter().map(|pc| pc.c).collect();
+        let diff = preprocess_diff_view(&input, &preprocessed);
         let tokens = match lex(preprocessed) {
             Ok(tokens) => tokens,
             Err(e) => {
@@ -1860,21 +2115,49 @@ impl Solver for LexerSolver {
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "词法分析" </p>
-                        <pre class="text-red-500"> { e.to_string_with_source(input) } </pre>
+                        { self.render_error(&e.to_string_with_source(input)) }
                     </div>
                 }
                 .into_view()
             }
         };
         let tokens_string = tokens.iter().map(|token| token.to_string()).join("\n");
+        let (positions, set_positions) = create_signal(false);
+        let (diffed, set_diffed) = create_signal(false);
         view! {
             <div class="mb-10">
-                <p class="font-bold mb-2"> "预处理" </p>
-                <pre> { preprocessed_string } </pre>
+                <p class="font-bold mb-2">
+                    "预处理"
+                    <label class="ml-4 font-normal">
+                        <input
+                            type="checkbox"
+                            class="mr-1"
+                            on:change=move |ev| set_diffed(event_target_checked(&ev))
+                        />
+                        "对照原文"
+                    </label>
+                </p>
+                <Show
+                    when=diffed
+                    fallback=move || view! { <pre> { preprocessed_string.clone() } </pre> }
+                > { diff.clone() } </Show>
             </div>
             <div class="mb-10">
-                <p class="font-bold mb-2"> "词法分析" </p>
-                <pre> { tokens_string } </pre>
+                <p class="font-bold mb-2">
+                    "词法分析"
+                    <label class="ml-4 font-normal">
+                        <input
+                            type="checkbox"
+                            class="mr-1"
+                            on:change=move |ev| set_positions(event_target_checked(&ev))
+                        />
+                        "显示位置"
+                    </label>
+                </p>
+                <Show
+                    when=positions
+                    fallback=move || view! { <pre> { tokens_string.clone() } </pre> }
+                > { token_positions_view(&input, &tokens) } </Show>
             </div>
         }
         .into_view()
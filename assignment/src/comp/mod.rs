@@ -2,3 +2,5 @@ mod lexer;
 pub use lexer::*;
 mod parser;
 pub use parser::*;
+mod slr;
+pub use slr::*;
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, fmt::Display, sync::OnceLock};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt::Display,
+    sync::OnceLock,
+};
 
 use indoc::indoc;
 use itertools::Itertools;
@@ -8,10 +12,12 @@ use shiyanyi::*;
 use stylers::style_str;
 use thiserror::Error;
 
-use super::{lex, mark_erroneous_source, preprocess, Op, Sym, Token, TokenValue};
+use crate::common::StepPlayback;
+
+use super::{highlight, lex, mark_erroneous_source, preprocess, Op, Sym, Token, TokenValue};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum Term {
+pub(super) enum Term {
     Terminal(Terminal),
     Nonterminal(Nonterminal),
 }
@@ -26,7 +32,7 @@ impl Display for Term {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-enum Terminal {
+pub(super) enum Terminal {
     Ident,
     Sym(Sym),
     Op(Op),
@@ -67,8 +73,8 @@ impl TryFrom<Token> for Terminal {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Nonterminal {
-    name: String,
+pub(super) struct Nonterminal {
+    pub(super) name: String,
 }
 
 impl Display for Nonterminal {
@@ -78,12 +84,12 @@ impl Display for Nonterminal {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct LL1Rule {
-    lhs: Nonterminal,
-    rhs: Vec<Term>,
+pub(super) struct Production {
+    pub(super) lhs: Nonterminal,
+    pub(super) rhs: Vec<Term>,
 }
 
-impl Display for LL1Rule {
+impl Display for Production {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{} ::= ", self.lhs)?;
         if self.rhs.is_empty() {
@@ -97,13 +103,13 @@ impl Display for LL1Rule {
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct LL1ParseTable {
     start: Nonterminal,
-    rules: Vec<LL1Rule>,
+    rules: Vec<Production>,
     table: HashMap<(Nonterminal, Terminal), usize>,
 }
 
 impl Default for LL1ParseTable {
     fn default() -> Self {
-        static RULES: OnceLock<Vec<LL1Rule>> = OnceLock::new();
+        static RULES: OnceLock<Vec<Production>> = OnceLock::new();
         static TABLE: OnceLock<HashMap<(Nonterminal, Terminal), usize>> = OnceLock::new();
         // Rules:
         // (0)  E  ::= T E'
@@ -119,7 +125,7 @@ impl Default for LL1ParseTable {
             .get_or_init(|| {
                 vec![
                     // E  ::= T E'
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "E".to_string(),
                         },
@@ -133,7 +139,7 @@ impl Default for LL1ParseTable {
                         ],
                     },
                     // E' ::= + T E'
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "E^\\prime".to_string(),
                         },
@@ -148,14 +154,14 @@ impl Default for LL1ParseTable {
                         ],
                     },
                     // E' ::= \epsilon
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "E^\\prime".to_string(),
                         },
                         rhs: vec![],
                     },
                     // T  ::= F T'
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "T".to_string(),
                         },
@@ -169,7 +175,7 @@ impl Default for LL1ParseTable {
                         ],
                     },
                     // T' ::= * F T'
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "T^\\prime".to_string(),
                         },
@@ -184,14 +190,14 @@ impl Default for LL1ParseTable {
                         ],
                     },
                     // T' ::= \epsilon
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "T^\\prime".to_string(),
                         },
                         rhs: vec![],
                     },
                     // F ::= ( E )
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "F".to_string(),
                         },
@@ -204,14 +210,14 @@ impl Default for LL1ParseTable {
                         ],
                     },
                     // F ::= Ident
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "F".to_string(),
                         },
                         rhs: vec![Term::Terminal(Terminal::Ident)],
                     },
                     // F ::= LiteralInt
-                    LL1Rule {
+                    Production {
                         lhs: Nonterminal {
                             name: "F".to_string(),
                         },
@@ -403,6 +409,328 @@ impl Default for LL1ParseTable {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FirstFollowSets {
+    /// FIRST sets without `\epsilon`
+    pub(super) first: HashMap<Nonterminal, HashSet<Terminal>>,
+    /// Nonterminals whose FIRST sets contain `\epsilon`
+    pub(super) nullable: HashSet<Nonterminal>,
+    pub(super) follow: HashMap<Nonterminal, HashSet<Terminal>>,
+}
+
+impl FirstFollowSets {
+    /// Computes the sets to a fixed point, where `#` follows `start`.
+    pub(super) fn new(start: &Nonterminal, rules: &[Production]) -> Self {
+        let mut sets = Self {
+            first: HashMap::new(),
+            nullable: HashSet::new(),
+            follow: HashMap::from([(start.clone(), HashSet::from([Terminal::Eos]))]),
+        };
+        loop {
+            let mut changed = false;
+            for rule in rules {
+                let (rhs_first, rhs_nullable) = sets.first_of(&rule.rhs);
+                let lhs_first = sets.first.entry(rule.lhs.clone()).or_default();
+                let len = lhs_first.len();
+                lhs_first.extend(rhs_first);
+                changed |= lhs_first.len() != len;
+                if rhs_nullable {
+                    changed |= sets.nullable.insert(rule.lhs.clone());
+                }
+                for (i, term) in rule.rhs.iter().enumerate() {
+                    let Term::Nonterminal(nonterminal) = term else {
+                        continue;
+                    };
+                    let (mut trailer, trailer_nullable) = sets.first_of(&rule.rhs[i + 1..]);
+                    if trailer_nullable {
+                        trailer.extend(sets.follow.get(&rule.lhs).cloned().unwrap_or_default());
+                    }
+                    let nonterminal_follow = sets.follow.entry(nonterminal.clone()).or_default();
+                    let len = nonterminal_follow.len();
+                    nonterminal_follow.extend(trailer);
+                    changed |= nonterminal_follow.len() != len;
+                }
+            }
+            if !changed {
+                break sets;
+            }
+        }
+    }
+
+    /// FIRST set of a string of terms and whether it derives `\epsilon`.
+    pub(super) fn first_of(&self, terms: &[Term]) -> (HashSet<Terminal>, bool) {
+        let mut first = HashSet::new();
+        for term in terms {
+            match term {
+                Term::Terminal(terminal) => {
+                    first.insert(terminal.clone());
+                    return (first, false);
+                }
+                Term::Nonterminal(nonterminal) => {
+                    first.extend(self.first.get(nonterminal).cloned().unwrap_or_default());
+                    if !self.nullable.contains(nonterminal) {
+                        return (first, false);
+                    }
+                }
+            }
+        }
+        (first, true)
+    }
+}
+
+impl LL1ParseTable {
+    /// Constructs the table from FIRST and FOLLOW sets, or returns a conflicting cell if the
+    /// grammar is not LL(1).
+    fn from_rules(
+        start: Nonterminal,
+        rules: Vec<Production>,
+    ) -> Result<Self, (Nonterminal, Terminal)> {
+        let sets = FirstFollowSets::new(&start, &rules);
+        let mut table = HashMap::new();
+        for (index, rule) in rules.iter().enumerate() {
+            let (mut terminals, nullable) = sets.first_of(&rule.rhs);
+            if nullable {
+                terminals.extend(sets.follow.get(&rule.lhs).cloned().unwrap_or_default());
+            }
+            for terminal in terminals {
+                if table
+                    .insert((rule.lhs.clone(), terminal.clone()), index)
+                    .is_some()
+                {
+                    return Err((rule.lhs.clone(), terminal));
+                }
+            }
+        }
+        Ok(Self {
+            start,
+            rules,
+            table,
+        })
+    }
+
+    fn first_follow_sets(&self) -> FirstFollowSets {
+        FirstFollowSets::new(&self.start, &self.rules)
+    }
+
+    /// Terminals in the rules in order of first appearance, followed by `#`.
+    fn terminals(&self) -> Vec<Terminal> {
+        self.rules
+            .iter()
+            .flat_map(|rule| rule.rhs.iter())
+            .filter_map(|term| match term {
+                Term::Terminal(terminal) => Some(terminal.clone()),
+                Term::Nonterminal(_) => None,
+            })
+            .chain([Terminal::Eos])
+            .unique()
+            .collect_vec()
+    }
+
+    /// Grid of nonterminals by terminals, with 1-based rule indices.
+    fn table_view(&self, class_name: &'static str) -> View {
+        let terminals = self.terminals();
+        view! {
+            class = class_name,
+            <table class="mb-2">
+                <thead>
+                    <tr>
+                        <th />
+                        {
+                            terminals.iter().map(|terminal| view! {
+                                class = class_name,
+                                <th><KaTeX expr={ terminal.to_string() } /></th>
+                            }).collect_vec()
+                        }
+                    </tr>
+                </thead>
+                <tbody> {
+                    self.nonterminals().into_iter().map(|nonterminal| view! {
+                        class = class_name,
+                        <tr>
+                            <td><KaTeX expr={ nonterminal.to_string() } /></td>
+                            {
+                                terminals.iter().map(|terminal| {
+                                    match self.table.get(&(nonterminal.clone(), terminal.clone())) {
+                                        Some(index) => view! {
+                                            class = class_name,
+                                            <td><KaTeX expr={ (index + 1).to_string() } /></td>
+                                        }.into_view(),
+                                        None => view! { class = class_name, <td /> }.into_view(),
+                                    }
+                                }).collect_vec()
+                            }
+                        </tr>
+                    }).collect_vec()
+                } </tbody>
+            </table>
+        }
+        .into_view()
+    }
+
+    /// `\mathrm{FIRST}(A) = \{...\}` and `\mathrm{FOLLOW}(A) = \{...\}` of each nonterminal.
+    fn first_follow_tex(&self) -> (String, String) {
+        let sets = self.first_follow_sets();
+        let terminals = self.terminals();
+        let set_tex = |set: Option<&HashSet<Terminal>>, epsilon: bool| {
+            terminals
+                .iter()
+                .filter(|terminal| set.is_some_and(|set| set.contains(terminal)))
+                .map(|terminal| terminal.to_string())
+                .chain(epsilon.then(|| "\\epsilon".to_string()))
+                .join(",\\ ")
+        };
+        let lines = |name: &str,
+                     sets: &HashMap<Nonterminal, HashSet<Terminal>>,
+                     nullable: &HashSet<Nonterminal>| {
+            let lines = self
+                .nonterminals()
+                .into_iter()
+                .map(|nonterminal| {
+                    format!(
+                        "\\mathrm{{{name}}}({nonterminal}) &= \\{{ {} \\}} \\\\",
+                        set_tex(sets.get(&nonterminal), nullable.contains(&nonterminal))
+                    )
+                })
+                .join("\n");
+            format!("\\begin{{align*}}\n{lines}\n\\end{{align*}}")
+        };
+        (
+            lines("FIRST", &sets.first, &sets.nullable),
+            lines("FOLLOW", &sets.follow, &HashSet::new()),
+        )
+    }
+
+    /// Left-hand sides of the rules in order of first appearance.
+    fn nonterminals(&self) -> Vec<Nonterminal> {
+        self.rules
+            .iter()
+            .map(|rule| rule.lhs.clone())
+            .unique()
+            .collect_vec()
+    }
+
+    /// The same table with another start symbol, if it is the left-hand side of some rule.
+    fn with_start(self, start: &str) -> Option<Self> {
+        let start = self
+            .nonterminals()
+            .into_iter()
+            .find(|nonterminal| nonterminal.name == start)?;
+        Some(Self { start, ..self })
+    }
+
+    /// Operators grouped by precedence from low to high as implied by the structure of the rules,
+    /// where operators of levels farther from the start symbol bind tighter.
+    fn operator_precedences(&self) -> Vec<(Vec<Terminal>, Associativity)> {
+        // distance of each nonterminal from the start symbol
+        let mut depths = HashMap::from([(self.start.clone(), 0)]);
+        let mut queue = VecDeque::from([self.start.clone()]);
+        while let Some(nonterminal) = queue.pop_front() {
+            let depth = depths[&nonterminal];
+            for rule in self.rules.iter().filter(|rule| rule.lhs == nonterminal) {
+                for term in &rule.rhs {
+                    if let Term::Nonterminal(next) = term {
+                        if !depths.contains_key(next) {
+                            depths.insert(next.clone(), depth + 1);
+                            queue.push_back(next.clone());
+                        }
+                    }
+                }
+            }
+        }
+        let lhs = |rule: &Production| Term::Nonterminal(rule.lhs.clone());
+        self.rules
+            .iter()
+            .filter_map(|rule| {
+                let (op, associativity) = match &rule.rhs[..] {
+                    // A' ::= op B A' from eliminating the left recursion of A ::= A op B
+                    [op @ Term::Terminal(Terminal::Op(_)), Term::Nonterminal(_), a]
+                        if *a == lhs(rule) =>
+                    {
+                        (op, Associativity::Left)
+                    }
+                    [a, op @ Term::Terminal(Terminal::Op(_)), Term::Nonterminal(_)]
+                        if *a == lhs(rule) =>
+                    {
+                        (op, Associativity::Left)
+                    }
+                    [Term::Nonterminal(_), op @ Term::Terminal(Terminal::Op(_)), a]
+                        if *a == lhs(rule) =>
+                    {
+                        (op, Associativity::Right)
+                    }
+                    _ => return None,
+                };
+                let Term::Terminal(op) = op else {
+                    unreachable!()
+                };
+                Some((*depths.get(&rule.lhs)?, op.clone(), associativity))
+            })
+            .sorted_by_key(|(depth, _, _)| *depth)
+            .chunk_by(|(depth, _, associativity)| (*depth, *associativity))
+            .into_iter()
+            .map(|((_, associativity), ops)| {
+                (
+                    ops.map(|(_, op, _)| op).unique().collect_vec(),
+                    associativity,
+                )
+            })
+            .collect_vec()
+    }
+
+    fn operator_precedences_view(&self, class_name: &'static str) -> View {
+        view! {
+            class = class_name,
+            <table class="mb-2">
+                <thead>
+                    <tr>
+                        <th> "优先级" </th>
+                        <th> "运算符" </th>
+                        <th> "结合性" </th>
+                    </tr>
+                </thead>
+                <tbody> {
+                    self.operator_precedences().into_iter().enumerate().map(|(i, (ops, associativity))| view! {
+                        class = class_name,
+                        <tr>
+                            <td> { (i + 1).to_string() } </td>
+                            <td><KaTeX expr={ ops.iter().join(r",\ ") } /></td>
+                            <td> { associativity.to_string() } </td>
+                        </tr>
+                    }).collect_vec()
+                } </tbody>
+            </table>
+        }
+        .into_view()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Associativity {
+    Left,
+    Right,
+}
+
+impl Display for Associativity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Associativity::Left => write!(f, "左结合"),
+            Associativity::Right => write!(f, "右结合"),
+        }
+    }
+}
+
+#[test]
+fn test_operator_precedences() {
+    let table = LL1ParseTable::default();
+    assert_eq!(
+        table.operator_precedences(),
+        [
+            (vec![Terminal::Op(Op::Add)], Associativity::Left),
+            (vec![Terminal::Op(Op::Mul)], Associativity::Left)
+        ]
+    );
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum ParseTraceRowRule {
     Rule(usize),
@@ -468,9 +796,12 @@ impl ParseTrace {
                 padding: 0.3rem 1rem;
             }
         };
+        let count = self.0.len();
+        let shown = create_rw_signal(None);
         view! {
             class = class_name,
             <Style> {style_val} </Style>
+            <p class="mb-2"> <StepPlayback count shown /> </p>
             <table>
                 <thead>
                     <tr>
@@ -481,21 +812,22 @@ impl ParseTrace {
                     </tr>
                 </thead>
                 <tbody> {
-                    self.0.into_iter().zip(1..).map(|(t, i)| view! {
+                    move || self.0.iter().take(shown().unwrap_or(count)).zip(1..).map(|(t, i)| view! {
                         class = class_name,
                         <tr>
                             <td><KaTeX expr={ i.to_string() } /></td>
                             <td><KaTeX expr={
                                 [Term::Terminal(Terminal::Eos)]
-                                    .into_iter()
-                                    .chain(t.stack.into_iter())
+                                    .iter()
+                                    .chain(t.stack.iter())
                                     .map(|t| t.to_string())
                                     .join("\\ ")
                             } /></td>
                             <td><KaTeX expr={
                                 t.input
-                                    .into_iter()
+                                    .iter()
                                     .rev()
+                                    .cloned()
                                     .map(format_token)
                                     .chain(["\\#".to_string()].into_iter())
                                     .join("\\ ")
@@ -524,18 +856,18 @@ impl ParseTrace {
 
 #[derive(Error, Debug, Clone)]
 pub enum ParseError {
-    #[error("invalid token {token}")]
+    #[error("非法记号 {token}")]
     InvalidToken { token: Token },
-    #[error("unexpected {token}")]
+    #[error("意外的记号 {token}")]
     UnexpectedToken { token: Token },
-    #[error("expect end of stream, found {token}")]
+    #[error("应为输入结束, 实为 {token}")]
     ExtraToken { token: Token },
-    #[error("unexpected end of stream")]
+    #[error("意外的输入结束")]
     UnexpectedEos,
 }
 
 impl ParseError {
-    fn to_string_with_source(&self, input: String) -> String {
+    pub(super) fn to_string_with_source(&self, input: String) -> String {
         match self {
             ParseError::InvalidToken { token } => mark_erroneous_source(
                 input,
@@ -698,11 +1030,35 @@ fn test_parse() {
     .to_string();
     let preprocessed = preprocess(source).unwrap();
     let tokens = lex(preprocessed).unwrap();
-    let (trace, result) = parse(LL1ParseTable::default(), tokens);
+    let (_trace, result) = parse(LL1ParseTable::default(), tokens);
     result.unwrap();
 }
 
-fn format_token(token: Token) -> String {
+#[test]
+fn test_from_rules() {
+    let table = LL1ParseTable::default();
+    assert_eq!(
+        LL1ParseTable::from_rules(table.start.clone(), table.rules.clone()),
+        Ok(table.clone())
+    );
+    let (first, follow) = table.first_follow_tex();
+    assert!(first.contains(r"\mathrm{FIRST}(E^\prime) &= \{ \texttt{+},\ \epsilon \}"));
+    assert!(follow.contains(r"\mathrm{FOLLOW}(T) &= \{ \texttt{+},\ \texttt{)},\ \# \}"));
+}
+
+#[test]
+fn test_parse_with_start() {
+    let tokens = |source: &str| lex(preprocess(source.to_string()).unwrap()).unwrap();
+    let table = LL1ParseTable::default().with_start("T").unwrap();
+    parse(table.clone(), tokens("a * (b + c)")).1.unwrap();
+    assert!(matches!(
+        parse(table, tokens("a + b")).1,
+        Err(ParseError::ExtraToken { .. })
+    ));
+    assert!(LL1ParseTable::default().with_start("X").is_none());
+}
+
+pub(super) fn format_token(token: Token) -> String {
     match token.token {
         TokenValue::Ident(ident) => {
             format!("\\textrm{{Ident}}\\left(\\texttt{{{}}}\\right)", ident.name)
@@ -718,6 +1074,30 @@ fn format_token(token: Token) -> String {
     }
 }
 
+/// Style of the LL(1) parse table grid.
+pub(super) fn table_style() -> (&'static str, &'static str) {
+    style_str! {
+        thead > tr {
+            border-top: 1px solid #333;
+            border-bottom: 1px solid #333;
+        }
+
+        tbody > tr {
+            border-bottom: 1px solid #333;
+        }
+
+        th:last-child, td:last-child {
+            border-right: 1px solid #333;
+        }
+
+        th, td {
+            text-align: center;
+            padding: 0.3rem 1rem;
+            border-left: 1px solid #333;
+        }
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct ParserSolver;
 
@@ -730,27 +1110,16 @@ impl Solver for ParserSolver {
         "语法分析器的构造".to_string()
     }
 
-    fn description(&self) -> View {
-        let (class_name, style_val) = style_str! {
-            thead > tr {
-                border-top: 1px solid #333;
-                border-bottom: 1px solid #333;
-            }
-
-            tbody > tr {
-                border-bottom: 1px solid #333;
-            }
+    fn highlights_input(&self) -> bool {
+        true
+    }
 
-            th:last-child, td:last-child {
-                border-right: 1px solid #333;
-            }
+    fn highlight_input(&self, input: &str) -> Vec<(String, Option<String>)> {
+        highlight(input)
+    }
 
-            th, td {
-                text-align: center;
-                padding: 0.3rem 1rem;
-                border-left: 1px solid #333;
-            }
-        };
+    fn description(&self) -> View {
+        let (class_name, style_val) = table_style();
         view! {
             class = class_name,
             <Style> {style_val} </Style>
@@ -777,72 +1146,9 @@ impl Solver for ParserSolver {
                 \end{align}
             "} } /></p>
             <p class="mb-2"> "手工构造得到以下 LL(1) 分析表. (数字索引自上述文法)" </p>
-            <table class="mb-2">
-                <thead>
-                    <tr>
-                        <th />
-                        <th><KaTeX expr=r"\texttt{+}" /></th>
-                        <th><KaTeX expr=r"\texttt{*}" /></th>
-                        <th><KaTeX expr=r"\texttt{(}" /></th>
-                        <th><KaTeX expr=r"\texttt{)}" /></th>
-                        <th><KaTeX expr=r"\textrm{Ident}" /></th>
-                        <th><KaTeX expr=r"\textrm{LiteralInt}" /></th>
-                        <th><KaTeX expr=r"\#" /></th>
-                    </tr>
-                </thead>
-                <tbody>
-                    <tr>
-                        <td><KaTeX expr=r"E" /></td>
-                        <td />
-                        <td />
-                        <td><KaTeX expr=r"1" /></td>
-                        <td />
-                        <td><KaTeX expr=r"1" /></td>
-                        <td><KaTeX expr=r"1" /></td>
-                        <td />
-                    </tr>
-                    <tr>
-                        <td><KaTeX expr=r"E^{\prime}" /></td>
-                        <td><KaTeX expr=r"2" /></td>
-                        <td />
-                        <td />
-                        <td><KaTeX expr=r"3" /></td>
-                        <td />
-                        <td />
-                        <td><KaTeX expr=r"3" /></td>
-                    </tr>
-                    <tr>
-                        <td><KaTeX expr=r"T" /></td>
-                        <td />
-                        <td />
-                        <td><KaTeX expr=r"4" /></td>
-                        <td />
-                        <td><KaTeX expr=r"4" /></td>
-                        <td><KaTeX expr=r"4" /></td>
-                        <td />
-                    </tr>
-                    <tr>
-                        <td><KaTeX expr=r"T^{\prime}" /></td>
-                        <td><KaTeX expr=r"6" /></td>
-                        <td><KaTeX expr=r"5" /></td>
-                        <td />
-                        <td><KaTeX expr=r"6" /></td>
-                        <td />
-                        <td />
-                        <td><KaTeX expr=r"6" /></td>
-                    </tr>
-                    <tr>
-                        <td><KaTeX expr=r"F" /></td>
-                        <td />
-                        <td />
-                        <td><KaTeX expr=r"7" /></td>
-                        <td />
-                        <td><KaTeX expr=r"8" /></td>
-                        <td><KaTeX expr=r"9" /></td>
-                        <td />
-                    </tr>
-                </tbody>
-            </table>
+            { LL1ParseTable::default().table_view(class_name) }
+            <p class="mb-2"> "由文法的层次结构可知运算符的优先级 (从低到高) 与结合性." </p>
+            { LL1ParseTable::default().operator_precedences_view(class_name) }
             <p class="mb-2"> "在下方输入符号串使用上述 LL(1) 分析表进行识别." </p>
         }.into_view()
     }
@@ -858,7 +1164,7 @@ impl Solver for ParserSolver {
                 return view! {
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "预处理" </p>
-                        <pre class="text-red-500"> { e.to_string_with_source(input) } </pre>
+                        { self.render_error(&e.to_string_with_source(input)) }
                     </div>
                 }
                 .into_view()
@@ -870,37 +1176,87 @@ impl Solver for ParserSolver {
                 return view! {
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "词法分析" </p>
-                        <pre class="text-red-500"> { e.to_string_with_source(input) } </pre>
+                        { self.render_error(&e.to_string_with_source(input)) }
                     </div>
                 }
                 .into_view()
             }
         };
-        let table = LL1ParseTable::default();
-        let (trace, result) = parse(table.clone(), tokens);
-        match result {
-            Ok(_) => view! {
-                <div class="mb-10">
-                    <p class="font-bold mb-2"> "语法分析" </p>
-                    { trace.into_view_with_table(table) }
-                </div>
+        let (class_name, style_val) = table_style();
+        let default_table = LL1ParseTable::default();
+        let (first_tex, follow_tex) = default_table.first_follow_tex();
+        let constructed_table = match LL1ParseTable::from_rules(
+            default_table.start.clone(),
+            default_table.rules.clone(),
+        ) {
+            Ok(table) => table.table_view(class_name),
+            Err((nonterminal, terminal)) => view! {
+                <p class="text-red-500"> "冲突: " <KaTeX expr={
+                    format!(r"M[{nonterminal}, {terminal}]")
+                } /></p>
             }
             .into_view(),
-            Err(e @ ParseError::InvalidToken { .. }) => view! {
-                <div class="mb-10">
-                    <p class="font-bold mb-2"> "语法分析" </p>
-                    <pre class="text-red-500"> { e.to_string_with_source(input) } </pre>
-                </div>
-            }
-            .into_view(),
-            Err(e) => view! {
-                <div class="mb-10">
-                    <p class="font-bold mb-2"> "语法分析" </p>
-                    <pre class="text-red-500 mb-2"> { e.to_string_with_source(input) } </pre>
+        };
+        let (start, set_start) = create_signal(default_table.start.name.clone());
+        let options = default_table
+            .nonterminals()
+            .into_iter()
+            .map(|nonterminal| {
+                let selected = nonterminal == default_table.start;
+                view! {
+                    <option value={ nonterminal.name.clone() } selected=selected> {
+                        nonterminal.name.replace("^\\prime", "'")
+                    } </option>
+                }
+            })
+            .collect_vec();
+        let solver = self.clone();
+        let parsing = move || {
+            let Some(table) = default_table.clone().with_start(&start()) else {
+                return solver.render_error(&format!("非法的开始符号 {}", start()));
+            };
+            let (trace, result) = parse(table.clone(), tokens.clone());
+            match result {
+                Ok(_) => trace.into_view_with_table(table),
+                Err(e @ ParseError::InvalidToken { .. }) => {
+                    solver.render_error(&e.to_string_with_source(input.clone()))
+                }
+                Err(e) => view! {
+                    <div class="mb-2"> { solver.render_error(&e.to_string_with_source(input.clone())) } </div>
                     { trace.into_view_with_table(table) }
-                </div>
+                }
+                .into_view(),
             }
-            .into_view(),
+        };
+        view! {
+            class = class_name,
+            <Style> {style_val} </Style>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "FIRST 集" </p>
+                <KaTeX display_mode=true fleqn=true expr=first_tex />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "FOLLOW 集" </p>
+                <KaTeX display_mode=true fleqn=true expr=follow_tex />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "LL(1) 分析表" </p>
+                { constructed_table }
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2">
+                    "语法分析"
+                    <label class="ml-4 font-normal">
+                        "开始符号"
+                        <select
+                            class="ml-1 border rounded"
+                            on:change=move |ev| set_start(event_target_value(&ev))
+                        > { options } </select>
+                    </label>
+                </p>
+                { parsing }
+            </div>
         }
+        .into_view()
     }
 }
@@ -0,0 +1,631 @@
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
+use leptos::*;
+use leptos_meta::Style;
+use shiyanyi::*;
+
+use super::{
+    highlight, lex,
+    parser::{format_token, table_style, FirstFollowSets, Nonterminal, Production, Term, Terminal},
+    preprocess, Op, ParseError, Sym, Token,
+};
+
+fn nonterminal(name: &str) -> Nonterminal {
+    Nonterminal {
+        name: name.to_string(),
+    }
+}
+
+/// Productions of the arithmetic grammar, augmented with `E' ::= E` as production 0.
+fn arithmetic_productions() -> Vec<Production> {
+    let production = |lhs: &str, rhs: Vec<Term>| Production {
+        lhs: nonterminal(lhs),
+        rhs,
+    };
+    let n = |name: &str| Term::Nonterminal(nonterminal(name));
+    let t = Term::Terminal;
+    vec![
+        production("E^\\prime", vec![n("E")]),
+        production("E", vec![n("E"), t(Terminal::Op(Op::Add)), n("T")]),
+        production("E", vec![n("T")]),
+        production("T", vec![n("T"), t(Terminal::Op(Op::Mul)), n("F")]),
+        production("T", vec![n("F")]),
+        production(
+            "F",
+            vec![
+                t(Terminal::Sym(Sym::LeftParen)),
+                n("E"),
+                t(Terminal::Sym(Sym::RightParen)),
+            ],
+        ),
+        production("F", vec![t(Terminal::Ident)]),
+        production("F", vec![t(Terminal::LiteralInt)]),
+    ]
+}
+
+/// LR(0) item, a production with the dot before `rhs[dot]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Item {
+    production: usize,
+    dot: usize,
+}
+
+impl Item {
+    fn to_tex(self, productions: &[Production]) -> String {
+        let production = &productions[self.production];
+        let rhs = production
+            .rhs
+            .iter()
+            .map(|term| term.to_string())
+            .collect_vec();
+        let before = rhs[..self.dot].iter();
+        let after = rhs[self.dot..].iter();
+        format!(
+            "{} ::= {}",
+            production.lhs,
+            before
+                .chain([&r"\bullet".to_string()])
+                .chain(after)
+                .join("\\ ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Action {
+    Shift(usize),
+    Reduce(usize),
+    Accept,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Shift(state) => write!(f, "\\mathrm{{s}}{state}"),
+            Action::Reduce(production) => write!(f, "\\mathrm{{r}}{production}"),
+            Action::Accept => write!(f, "\\mathrm{{acc}}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SlrTable {
+    productions: Vec<Production>,
+    /// Terminals in order of appearance, followed by `#`
+    terminals: Vec<Terminal>,
+    /// Nonterminals in order of appearance, except the augmented start symbol
+    nonterminals: Vec<Nonterminal>,
+    /// LR(0) item sets, each sorted
+    states: Vec<Vec<Item>>,
+    /// More than one action in a cell is a conflict
+    action: HashMap<(usize, Terminal), Vec<Action>>,
+    goto: HashMap<(usize, Nonterminal), usize>,
+}
+
+impl SlrTable {
+    /// Builds the SLR(1) table of an augmented grammar whose production 0 is `S' ::= S`.
+    fn new(productions: Vec<Production>) -> Self {
+        let terminals = productions
+            .iter()
+            .flat_map(|production| production.rhs.iter())
+            .filter_map(|term| match term {
+                Term::Terminal(terminal) => Some(terminal.clone()),
+                Term::Nonterminal(_) => None,
+            })
+            .chain([Terminal::Eos])
+            .unique()
+            .collect_vec();
+        let nonterminals = productions
+            .iter()
+            .skip(1)
+            .map(|production| production.lhs.clone())
+            .unique()
+            .collect_vec();
+        let closure = |mut items: Vec<Item>| {
+            let mut i = 0;
+            while i < items.len() {
+                let item = items[i];
+                if let Some(Term::Nonterminal(nonterminal)) =
+                    productions[item.production].rhs.get(item.dot)
+                {
+                    for (production, _) in productions
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, production)| &production.lhs == nonterminal)
+                    {
+                        let item = Item { production, dot: 0 };
+                        if !items.contains(&item) {
+                            items.push(item);
+                        }
+                    }
+                }
+                i += 1;
+            }
+            items.sort();
+            items
+        };
+        let mut states = vec![closure(vec![Item {
+            production: 0,
+            dot: 0,
+        }])];
+        let mut transitions = vec![];
+        let mut i = 0;
+        while i < states.len() {
+            let symbols = states[i]
+                .iter()
+                .filter_map(|item| productions[item.production].rhs.get(item.dot))
+                .unique()
+                .cloned()
+                .collect_vec();
+            for symbol in symbols {
+                let kernel = states[i]
+                    .iter()
+                    .filter(|item| productions[item.production].rhs.get(item.dot) == Some(&symbol))
+                    .map(|item| Item {
+                        production: item.production,
+                        dot: item.dot + 1,
+                    })
+                    .collect_vec();
+                let next = closure(kernel);
+                let j = match states.iter().position(|state| *state == next) {
+                    Some(j) => j,
+                    None => {
+                        states.push(next);
+                        states.len() - 1
+                    }
+                };
+                transitions.push((i, symbol, j));
+            }
+            i += 1;
+        }
+        let follow = FirstFollowSets::new(&productions[0].lhs, &productions).follow;
+        let mut action: HashMap<(usize, Terminal), Vec<Action>> = HashMap::new();
+        let mut goto = HashMap::new();
+        for (state, symbol, next) in transitions {
+            match symbol {
+                Term::Terminal(terminal) => action
+                    .entry((state, terminal))
+                    .or_default()
+                    .push(Action::Shift(next)),
+                Term::Nonterminal(nonterminal) => {
+                    goto.insert((state, nonterminal), next);
+                }
+            }
+        }
+        for (state, items) in states.iter().enumerate() {
+            for item in items {
+                let production = &productions[item.production];
+                if item.dot < production.rhs.len() {
+                    continue;
+                }
+                if item.production == 0 {
+                    action
+                        .entry((state, Terminal::Eos))
+                        .or_default()
+                        .push(Action::Accept);
+                    continue;
+                }
+                for terminal in terminals
+                    .iter()
+                    .filter(|terminal| follow[&production.lhs].contains(terminal))
+                {
+                    action
+                        .entry((state, terminal.clone()))
+                        .or_default()
+                        .push(Action::Reduce(item.production));
+                }
+            }
+        }
+        Self {
+            productions,
+            terminals,
+            nonterminals,
+            states,
+            action,
+            goto,
+        }
+    }
+
+    /// Cells with more than one action, in table order.
+    fn conflicts(&self) -> Vec<(usize, Terminal, Vec<Action>)> {
+        (0..self.states.len())
+            .cartesian_product(self.terminals.iter())
+            .filter_map(|(state, terminal)| {
+                self.action
+                    .get(&(state, terminal.clone()))
+                    .filter(|actions| actions.len() > 1)
+                    .map(|actions| (state, terminal.clone(), actions.clone()))
+            })
+            .collect_vec()
+    }
+
+    fn states_view(&self, class_name: &'static str) -> View {
+        view! {
+            class = class_name,
+            <table>
+                <thead>
+                    <tr>
+                        <th> "状态" </th>
+                        <th> "项目" </th>
+                    </tr>
+                </thead>
+                <tbody> {
+                    self.states.iter().enumerate().map(|(state, items)| view! {
+                        class = class_name,
+                        <tr>
+                            <td><KaTeX expr={ format!("I_{{{state}}}") } /></td>
+                            <td class="text-left"> {
+                                items.iter().map(|item| view! {
+                                    class = class_name,
+                                    <p><KaTeX expr={ item.to_tex(&self.productions) } /></p>
+                                }).collect_vec()
+                            } </td>
+                        </tr>
+                    }).collect_vec()
+                } </tbody>
+            </table>
+        }
+        .into_view()
+    }
+
+    fn table_view(&self, class_name: &'static str) -> View {
+        view! {
+            class = class_name,
+            <table>
+                <thead>
+                    <tr>
+                        <th rowspan="2"> "状态" </th>
+                        <th colspan={ self.terminals.len() }> "ACTION" </th>
+                        <th colspan={ self.nonterminals.len() }> "GOTO" </th>
+                    </tr>
+                    <tr>
+                        {
+                            self.terminals.iter().map(|terminal| view! {
+                                class = class_name,
+                                <th><KaTeX expr={ terminal.to_string() } /></th>
+                            }).collect_vec()
+                        }
+                        {
+                            self.nonterminals.iter().map(|nonterminal| view! {
+                                class = class_name,
+                                <th><KaTeX expr={ nonterminal.to_string() } /></th>
+                            }).collect_vec()
+                        }
+                    </tr>
+                </thead>
+                <tbody> {
+                    (0..self.states.len()).map(|state| view! {
+                        class = class_name,
+                        <tr>
+                            <td><KaTeX expr={ state.to_string() } /></td>
+                            {
+                                self.terminals.iter().map(|terminal| {
+                                    match self.action.get(&(state, terminal.clone())) {
+                                        Some(actions) => view! {
+                                            class = class_name,
+                                            <td class:text-red-500={ actions.len() > 1 }>
+                                                <KaTeX expr={ actions.iter().join(",\\ ") } />
+                                            </td>
+                                        }.into_view(),
+                                        None => view! { class = class_name, <td /> }.into_view(),
+                                    }
+                                }).collect_vec()
+                            }
+                            {
+                                self.nonterminals.iter().map(|nonterminal| {
+                                    match self.goto.get(&(state, nonterminal.clone())) {
+                                        Some(next) => view! {
+                                            class = class_name,
+                                            <td><KaTeX expr={ next.to_string() } /></td>
+                                        }.into_view(),
+                                        None => view! { class = class_name, <td /> }.into_view(),
+                                    }
+                                }).collect_vec()
+                            }
+                        </tr>
+                    }).collect_vec()
+                } </tbody>
+            </table>
+        }
+        .into_view()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SlrTraceRow {
+    states: Vec<usize>,
+    symbols: Vec<Term>,
+    input: Vec<Token>,
+    /// `None` if no action is defined
+    action: Option<Action>,
+}
+
+/// Shift-reduce parsing with a conflict-free table.
+fn slr_parse(table: &SlrTable, input: Vec<Token>) -> (Vec<SlrTraceRow>, Result<(), ParseError>) {
+    let mut trace = vec![];
+    let input: Result<Vec<(Token, Terminal)>, Token> = input
+        .into_iter()
+        .rev()
+        .map(|token| token.clone().try_into().map(|terminal| (token, terminal)))
+        .collect();
+    let mut input = match input {
+        Ok(input) => input,
+        Err(token) => return (trace, Err(ParseError::InvalidToken { token })),
+    };
+    let mut states = vec![0];
+    let mut symbols = vec![];
+    loop {
+        let (token, terminal) = match input.last() {
+            Some((token, terminal)) => (Some(token.clone()), terminal.clone()),
+            None => (None, Terminal::Eos),
+        };
+        let action = table
+            .action
+            .get(&(*states.last().unwrap(), terminal.clone()))
+            .and_then(|actions| actions.first())
+            .copied();
+        trace.push(SlrTraceRow {
+            states: states.clone(),
+            symbols: symbols.clone(),
+            input: input.iter().map(|v| v.0.clone()).collect(),
+            action,
+        });
+        match action {
+            Some(Action::Shift(next)) => {
+                states.push(next);
+                symbols.push(Term::Terminal(terminal));
+                input.pop();
+            }
+            Some(Action::Reduce(index)) => {
+                let production = &table.productions[index];
+                states.truncate(states.len() - production.rhs.len());
+                symbols.truncate(symbols.len() - production.rhs.len());
+                symbols.push(Term::Nonterminal(production.lhs.clone()));
+                states.push(table.goto[&(*states.last().unwrap(), production.lhs.clone())]);
+            }
+            Some(Action::Accept) => return (trace, Ok(())),
+            None => {
+                let e = match token {
+                    Some(token) => ParseError::UnexpectedToken { token },
+                    None => ParseError::UnexpectedEos,
+                };
+                return (trace, Err(e));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_slr() {
+    let table = SlrTable::new(arithmetic_productions());
+    assert_eq!(table.states.len(), 13);
+    assert!(table.conflicts().is_empty());
+    let parse = |source: &str| {
+        let tokens = lex(preprocess(source.to_string()).unwrap()).unwrap();
+        slr_parse(&table, tokens).1
+    };
+    parse("a + b * (1 + c)").unwrap();
+    assert!(matches!(parse("a +"), Err(ParseError::UnexpectedEos)));
+    assert!(matches!(
+        parse("a b"),
+        Err(ParseError::UnexpectedToken { .. })
+    ));
+    // E' ::= E, E ::= E + E | Ident
+    let e = Term::Nonterminal(nonterminal("E"));
+    let ambiguous = SlrTable::new(vec![
+        Production {
+            lhs: nonterminal("E^\\prime"),
+            rhs: vec![e.clone()],
+        },
+        Production {
+            lhs: nonterminal("E"),
+            rhs: vec![e.clone(), Term::Terminal(Terminal::Op(Op::Add)), e],
+        },
+        Production {
+            lhs: nonterminal("E"),
+            rhs: vec![Term::Terminal(Terminal::Ident)],
+        },
+    ]);
+    assert_eq!(
+        ambiguous
+            .conflicts()
+            .into_iter()
+            .map(|(_, _, actions)| actions)
+            .collect_vec(),
+        [vec![Action::Shift(3), Action::Reduce(1)]]
+    );
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SlrParserSolver;
+
+impl Solver for SlrParserSolver {
+    fn id(&self) -> String {
+        "slr".to_string()
+    }
+
+    fn title(&self) -> String {
+        "SLR(1) 语法分析器的构造".to_string()
+    }
+
+    fn highlights_input(&self) -> bool {
+        true
+    }
+
+    fn highlight_input(&self, input: &str) -> Vec<(String, Option<String>)> {
+        highlight(input)
+    }
+
+    fn description(&self) -> View {
+        let productions = arithmetic_productions()
+            .iter()
+            .enumerate()
+            .map(|(i, production)| {
+                let rhs = production.rhs.iter().map(|t| t.to_string()).join("\\ ");
+                format!(r"\quad ({i}) \quad {} & ::= {rhs} \\", production.lhs)
+            })
+            .join("\n");
+        view! {
+            <p class="mb-2"> "对语法分析器中的算术文法进行拓广, 得到以下文法." </p>
+            <p class="mb-2"><KaTeX display_mode=true fleqn=true expr={
+                format!("\\begin{{align*}}\n{productions}\n\\end{{align*}}")
+            } /></p>
+            <p class="mb-2"> "在下方输入符号串, 构造 LR(0) 项目集族与 SLR(1) 分析表进行识别." </p>
+        }
+        .into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "1 + 2 * foo + bar".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let (class_name, style_val) = table_style();
+        let preprocessed = match preprocess(input.clone()) {
+            Ok(preprocessed) => preprocessed,
+            Err(e) => {
+                return view! {
+                    <div class="mb-10">
+                        <p class="font-bold mb-2"> "预处理" </p>
+                        { self.render_error(&e.to_string_with_source(input)) }
+                    </div>
+                }
+                .into_view()
+            }
+        };
+        let tokens = match lex(preprocessed) {
+            Ok(tokens) => tokens,
+            Err(e) => {
+                return view! {
+                    <div class="mb-10">
+                        <p class="font-bold mb-2"> "词法分析" </p>
+                        { self.render_error(&e.to_string_with_source(input)) }
+                    </div>
+                }
+                .into_view()
+            }
+        };
+        let table = SlrTable::new(arithmetic_productions());
+        let conflicts = table.conflicts();
+        let parsing = if conflicts.is_empty() {
+            let (trace, result) = slr_parse(&table, tokens);
+            let error = match result {
+                Ok(()) => ().into_view(),
+                Err(e) => view! {
+                    <div class="mb-2"> { self.render_error(&e.to_string_with_source(input)) } </div>
+                }
+                .into_view(),
+            };
+            let trace = view! {
+                class = class_name,
+                <table>
+                    <thead>
+                        <tr>
+                            <th> "步骤" </th>
+                            <th> "状态栈" </th>
+                            <th> "符号栈" </th>
+                            <th> "余留输入串" </th>
+                            <th> "动作" </th>
+                        </tr>
+                    </thead>
+                    <tbody> {
+                        trace.into_iter().zip(1..).map(|(row, i)| view! {
+                            class = class_name,
+                            <tr>
+                                <td><KaTeX expr={ i.to_string() } /></td>
+                                <td class="text-left"><KaTeX expr={
+                                    row.states.iter().join("\\ ")
+                                } /></td>
+                                <td class="text-left"><KaTeX expr={
+                                    ["\\#".to_string()]
+                                        .into_iter()
+                                        .chain(row.symbols.iter().map(|t| t.to_string()))
+                                        .join("\\ ")
+                                } /></td>
+                                <td class="text-right"><KaTeX expr={
+                                    row.input
+                                        .into_iter()
+                                        .rev()
+                                        .map(format_token)
+                                        .chain(["\\#".to_string()])
+                                        .join("\\ ")
+                                } /></td>
+                                <td class="text-left"> {
+                                    match row.action {
+                                        Some(action @ Action::Reduce(index)) => view! {
+                                            class = class_name,
+                                            <KaTeX expr={
+                                                format!("{action} \\colon {}", table.productions[index])
+                                            } />
+                                        }.into_view(),
+                                        Some(action) => view! {
+                                            class = class_name,
+                                            <KaTeX expr={ action.to_string() } />
+                                        }.into_view(),
+                                        None => view! {
+                                            class = class_name,
+                                            <pre class="text-red-500"> "Error" </pre>
+                                        }.into_view(),
+                                    }
+                                } </td>
+                            </tr>
+                        }).collect_vec()
+                    } </tbody>
+                </table>
+            };
+            view! {
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "语法分析" </p>
+                    { error }
+                    { trace }
+                </div>
+            }
+            .into_view()
+        } else {
+            let conflicts = conflicts
+                .into_iter()
+                .map(|(state, terminal, actions)| {
+                    let kind = if actions
+                        .iter()
+                        .any(|action| matches!(action, Action::Shift(_)))
+                    {
+                        "移进-归约冲突: "
+                    } else {
+                        "归约-归约冲突: "
+                    };
+                    view! {
+                        <p class="text-red-500"> { kind } <KaTeX expr={
+                            format!(
+                                r"\mathrm{{ACTION}}[{state}, {terminal}] = \{{ {} \}}",
+                                actions.iter().join(",\\ ")
+                            )
+                        } /></p>
+                    }
+                })
+                .collect_vec();
+            view! {
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "语法分析" </p>
+                    <p class="mb-2"> "文法不是 SLR(1) 文法." </p>
+                    { conflicts }
+                </div>
+            }
+            .into_view()
+        };
+        view! {
+            class = class_name,
+            <Style> {style_val} </Style>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "LR(0) 项目集族" </p>
+                { table.states_view(class_name) }
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "SLR(1) 分析表" </p>
+                { table.table_view(class_name) }
+            </div>
+            { parsing }
+        }
+        .into_view()
+    }
+}
@@ -0,0 +1,119 @@
+use itertools::Itertools;
+use leptos::*;
+use num::{BigInt, One};
+use shiyanyi::*;
+
+/// Larger `n` are not computed.
+const MAX_N: u64 = 1000;
+
+fn factorial(n: u64) -> BigInt {
+    (1..=n).fold(BigInt::one(), |acc, i| acc * i)
+}
+
+/// `P(n, k) = n! / (n - k)!`, assuming `k <= n`.
+fn permutations(n: u64, k: u64) -> BigInt {
+    (n - k + 1..=n).fold(BigInt::one(), |acc, i| acc * i)
+}
+
+/// `C(n, k) = n! / (k! (n - k)!)`, assuming `k <= n`.
+fn combinations(n: u64, k: u64) -> BigInt {
+    let k = k.min(n - k);
+    // each partial product is itself a binomial coefficient, so the division is exact
+    (1..=k).fold(BigInt::one(), |acc, i| acc * (n - k + i) / i)
+}
+
+#[test]
+fn test_combinatorics() {
+    assert_eq!(factorial(0), BigInt::one());
+    assert_eq!(factorial(10), BigInt::from(3628800));
+    assert_eq!(permutations(10, 3), BigInt::from(720));
+    assert_eq!(permutations(5, 0), BigInt::one());
+    assert_eq!(combinations(52, 5), BigInt::from(2598960));
+    assert_eq!(combinations(10, 10), BigInt::one());
+    assert_eq!(
+        combinations(100, 50),
+        "100891344545564193334812497256".parse::<BigInt>().unwrap()
+    );
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CombinatoricsSolver;
+
+impl Solver for CombinatoricsSolver {
+    fn id(&self) -> String {
+        "comb".to_string()
+    }
+
+    fn title(&self) -> String {
+        "排列数, 组合数与阶乘".to_string()
+    }
+
+    fn description(&self) -> View {
+        format!("输入两个非负整数 n 和 k, 其中 k ≤ n ≤ {MAX_N}.").into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "52 5".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let (n, k) = match input
+            .split_whitespace()
+            .map(|s| s.parse::<u64>())
+            .collect_tuple()
+        {
+            Some((Ok(n), Ok(k))) => (n, k),
+            _ => return self.render_error("无法解析输入, n 和 k 应为非负整数."),
+        };
+        if k > n {
+            return self.render_error("k 不能大于 n.");
+        }
+        if n > MAX_N {
+            return self.render_error(&format!("n 不能大于 {MAX_N}."));
+        }
+        let pascal = if 0 < k && k < n {
+            view! {
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "杨辉三角递推" </p>
+                    <KaTeX expr={
+                        format!(
+                            r"C({n}, {k}) = C({}, {}) + C({}, {k}) = {} + {}",
+                            n - 1,
+                            k - 1,
+                            n - 1,
+                            combinations(n - 1, k - 1),
+                            combinations(n - 1, k)
+                        )
+                    } />
+                </div>
+            }
+            .into_view()
+        } else {
+            ().into_view()
+        };
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "阶乘" </p>
+                <KaTeX expr={ format!("{n}! = {}", factorial(n)) } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "排列数" </p>
+                <KaTeX expr={
+                    format!(r"P({n}, {k}) = \frac{{{n}!}}{{{}!}} = {}", n - k, permutations(n, k))
+                } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "组合数" </p>
+                <KaTeX expr={
+                    format!(
+                        r"C({n}, {k}) = \frac{{{n}!}}{{{k}! \, {}!}} = {}",
+                        n - k,
+                        combinations(n, k)
+                    )
+                } />
+            </div>
+            { pascal }
+        }
+        .into_view()
+    }
+}
@@ -0,0 +1,176 @@
+use itertools::Itertools;
+use leptos::*;
+use leptos_meta::Style;
+use shiyanyi::*;
+use stylers::style_str;
+
+use super::exp1::{formula_to_tex, syntax_error, Expr};
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct EquivalenceSolver;
+
+impl Solver for EquivalenceSolver {
+    fn id(&self) -> String {
+        "equiv".to_string()
+    }
+
+    fn title(&self) -> String {
+        "利用真值表判定命题公式的等价性".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入两个命题公式, 以空行分隔.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "¬(P ∧ Q)\n\n¬P ∨ ¬Q".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let formulas = input
+            .split('\n')
+            .collect_vec()
+            .split(|line| line.trim().is_empty())
+            .filter(|lines| !lines.is_empty())
+            .map(|lines| lines.join("\n"))
+            .collect_vec();
+        if formulas.len() != 2 {
+            return self.render_error("输入应恰为以空行分隔的两个公式.");
+        }
+        let exprs = match formulas
+            .iter()
+            .map(|formula| Expr::parse(formula.as_str()))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(exprs) => exprs,
+            Err(e) => return self.render_error(&syntax_error(&e)),
+        };
+        let propositions = exprs
+            .iter()
+            .flat_map(|expr| expr.propositions())
+            .unique()
+            .sorted()
+            .collect_vec();
+        let truth_tables = exprs
+            .iter()
+            .map(|expr| expr.truth_table_with_propositions(&propositions))
+            .collect_vec();
+        let rows = truth_tables[0]
+            .iter()
+            .zip_eq(truth_tables[1].iter())
+            .map(|((assignment, lhs), (_, rhs))| (assignment, *lhs, *rhs))
+            .collect_vec();
+        let differences = rows
+            .iter()
+            .filter(|(_, lhs, rhs)| lhs != rhs)
+            .map(|(assignment, _, _)| {
+                propositions
+                    .iter()
+                    .map(|p| {
+                        format!(
+                            r"{p} = {}",
+                            if assignment[p] {
+                                r"\mathbf{T}"
+                            } else {
+                                r"\mathbf{F}"
+                            }
+                        )
+                    })
+                    .join(r",\ ")
+            })
+            .collect_vec();
+        let (class_name, style_val) = style_str! {
+            thead > tr {
+                border-top: 1px solid #333;
+                border-bottom: 1px solid #333;
+            }
+
+            tbody > tr:last-child {
+                border-bottom: 1px solid #333;
+            }
+
+            th:first-child, td:first-child {
+                border-left: 1px solid #333;
+            }
+
+            th:last-child, td:last-child {
+                border-right: 1px solid #333;
+            }
+
+            th, td {
+                text-align: center;
+                padding: 0.3rem 1.5rem;
+            }
+
+            tr.different {
+                background-color: rgb(254, 226, 226);
+            }
+        };
+        view! {
+            class = class_name,
+            <Style> {style_val} </Style>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "等价性" </p>
+                <p class="text-xl font-bold"> {
+                    if differences.is_empty() { "两个公式等价." } else { "两个公式不等价." }
+                } </p>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "真值表" </p>
+                <table>
+                    <thead>
+                        <tr>
+                            {
+                                propositions.iter().map(|p| view! {
+                                    class = class_name,
+                                    <th><KaTeX expr={ p.to_string() } /></th>
+                                }).collect_vec()
+                            }
+                            {
+                                formulas.iter().map(|formula| view! {
+                                    class = class_name,
+                                    <th><KaTeX expr={ formula_to_tex(formula.as_str()) } /></th>
+                                }).collect_vec()
+                            }
+                        </tr>
+                    </thead>
+                    <tbody> {
+                        rows.iter().map(|(assignment, lhs, rhs)| view! {
+                            class = class_name,
+                            <tr class:different={ lhs != rhs }>
+                                {
+                                    propositions.iter().map(|p| view! {
+                                        class = class_name,
+                                        <td><KaTeX expr={ if assignment[p] { r"\mathbf{T}" } else { r"\mathbf{F}" } } /></td>
+                                    }).collect_vec()
+                                }
+                                <td><KaTeX expr={ if *lhs { r"\mathbf{T}" } else { r"\mathbf{F}" } } /></td>
+                                <td><KaTeX expr={ if *rhs { r"\mathbf{T}" } else { r"\mathbf{F}" } } /></td>
+                            </tr>
+                        }).collect_vec()
+                    } </tbody>
+                </table>
+            </div>
+            {
+                if differences.is_empty() {
+                    ().into_view()
+                } else {
+                    view! {
+                        class = class_name,
+                        <div class="mb-10">
+                            <p class="font-bold mb-2"> "真值不同的赋值" </p>
+                            {
+                                differences.into_iter().map(|difference| view! {
+                                    class = class_name,
+                                    <KaTeX expr={ difference } />
+                                }).collect_vec()
+                            }
+                        </div>
+                    }
+                    .into_view()
+                }
+            }
+        }
+        .into_view()
+    }
+}
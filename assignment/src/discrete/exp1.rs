@@ -1,11 +1,13 @@
 use std::{
     collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter},
     ops::Deref,
 };
 
 use itertools::Itertools;
 use leptos::*;
 use leptos_meta::Style;
+use num::{pow::Pow, BigInt};
 use pest::{
     error::Error as PestError,
     iterators::{Pair, Pairs},
@@ -16,12 +18,14 @@ use pest_derive::Parser;
 use shiyanyi::*;
 use stylers::style_str;
 
+use crate::common::split_keyword_line;
+
 #[derive(Parser)]
 #[grammar = "discrete/propositional_formula.pest"]
 struct PropositionalFormulaParser;
 
-#[derive(Debug, Clone)]
-enum Expr {
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Expr {
     Literal(bool),
     Proposition(String),
     Negation(Box<Expr>),
@@ -32,12 +36,17 @@ enum Expr {
     },
 }
 
+/// Message for a formula that fails to parse, pointing at the error in the input.
+pub(super) fn syntax_error(e: &PestError<Rule>) -> String {
+    format!("语法错误:\n{}", e.clone().with_path("<Input Section>"))
+}
+
 impl Expr {
-    fn parse(input: &str) -> Result<Self, Box<PestError<Rule>>> {
+    pub(super) fn parse(input: &str) -> Result<Self, Box<PestError<Rule>>> {
         Ok(Self::from_expr(Self::into_pairs(input)?))
     }
 
-    fn into_pairs(input: &str) -> Result<Pairs<Rule>, Box<PestError<Rule>>> {
+    fn into_pairs(input: &str) -> Result<Pairs<'_, Rule>, Box<PestError<Rule>>> {
         Ok(PropositionalFormulaParser::parse(Rule::formula, input)?
             .next()
             .unwrap()
@@ -67,6 +76,9 @@ impl Expr {
                 Rule::disjunction => Operator::Disjunction,
                 Rule::implication => Operator::Implication,
                 Rule::equivalence => Operator::Equivalence,
+                Rule::exclusive_disjunction => Operator::ExclusiveDisjunction,
+                Rule::non_conjunction => Operator::NonConjunction,
+                Rule::non_disjunction => Operator::NonDisjunction,
                 _ => unreachable!(),
             },
             rhs: Box::new(rhs),
@@ -77,14 +89,17 @@ impl Expr {
         PrattParser::new()
             .op(Op::infix(Rule::equivalence, Assoc::Left))
             .op(Op::infix(Rule::implication, Assoc::Left))
-            .op(Op::infix(Rule::disjunction, Assoc::Left))
-            .op(Op::infix(Rule::conjunction, Assoc::Left))
+            .op(Op::infix(Rule::disjunction, Assoc::Left)
+                | Op::infix(Rule::exclusive_disjunction, Assoc::Left)
+                | Op::infix(Rule::non_disjunction, Assoc::Left))
+            .op(Op::infix(Rule::conjunction, Assoc::Left)
+                | Op::infix(Rule::non_conjunction, Assoc::Left))
             .map_primary(Expr::from_term)
             .map_infix(Expr::from_binop)
             .parse(tokens)
     }
 
-    fn propositions(&self) -> HashSet<&str> {
+    pub(super) fn propositions(&self) -> HashSet<&str> {
         let mut propositions = HashSet::new();
         match self {
             Expr::Literal(_v) => {}
@@ -102,7 +117,7 @@ impl Expr {
         propositions
     }
 
-    fn substitute(&self, assignment: &Assignment) -> bool {
+    pub(super) fn substitute(&self, assignment: &Assignment) -> bool {
         match self {
             Expr::Literal(v) => *v,
             Expr::Proposition(proposition) => assignment[proposition.as_str()],
@@ -114,40 +129,548 @@ impl Expr {
                     Operator::Disjunction => lhs || rhs,
                     Operator::Implication => (!lhs) || rhs,
                     Operator::Equivalence => lhs == rhs,
+                    Operator::ExclusiveDisjunction => lhs != rhs,
+                    Operator::NonConjunction => !(lhs && rhs),
+                    Operator::NonDisjunction => !(lhs || rhs),
+                }
+            }
+        }
+    }
+
+    /// Truth value under a partial assignment, `None` if it depends on unassigned propositions.
+    fn partial_substitute(&self, assignment: &HashMap<&str, bool>) -> Option<bool> {
+        match self {
+            Expr::Literal(v) => Some(*v),
+            Expr::Proposition(proposition) => assignment.get(proposition.as_str()).copied(),
+            Expr::Negation(expr) => expr.partial_substitute(assignment).map(|v| !v),
+            Expr::BinOp { lhs, op, rhs } => {
+                let (lhs, rhs) = (
+                    lhs.partial_substitute(assignment),
+                    rhs.partial_substitute(assignment),
+                );
+                let conjunction = match (lhs, rhs) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                };
+                let disjunction = match (lhs, rhs) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                };
+                match op {
+                    Operator::Conjunction => conjunction,
+                    Operator::Disjunction => disjunction,
+                    Operator::Implication => match (lhs, rhs) {
+                        (Some(false), _) | (_, Some(true)) => Some(true),
+                        (Some(true), Some(false)) => Some(false),
+                        _ => None,
+                    },
+                    Operator::Equivalence => lhs.zip(rhs).map(|(lhs, rhs)| lhs == rhs),
+                    Operator::ExclusiveDisjunction => lhs.zip(rhs).map(|(lhs, rhs)| lhs != rhs),
+                    Operator::NonConjunction => conjunction.map(|v| !v),
+                    Operator::NonDisjunction => disjunction.map(|v| !v),
                 }
             }
         }
     }
 
-    fn truth_table(&self) -> TruthTable {
+    /// Rows of the truth table with the given `result` in truth table order, at most `limit`
+    /// of them. Branches whose result is already determined otherwise are pruned.
+    fn rows_with_result<'a>(
+        &self,
+        propositions: &[&'a str],
+        result: bool,
+        limit: usize,
+    ) -> TruthTable<'a> {
+        fn enumerate<'a>(
+            expr: &Expr,
+            propositions: &[&'a str],
+            assignment: &mut HashMap<&'a str, bool>,
+            result: bool,
+            limit: usize,
+            rows: &mut Vec<(Assignment<'a>, bool)>,
+        ) {
+            if rows.len() >= limit || expr.partial_substitute(assignment) == Some(!result) {
+                return;
+            }
+            match propositions.split_first() {
+                None => rows.push((assignment.clone().into(), result)),
+                Some((p, rest)) => {
+                    for v in [false, true] {
+                        assignment.insert(p, v);
+                        enumerate(expr, rest, assignment, result, limit, rows);
+                    }
+                    assignment.remove(p);
+                }
+            }
+        }
+        let mut rows = Vec::new();
+        enumerate(
+            self,
+            propositions,
+            &mut HashMap::new(),
+            result,
+            limit,
+            &mut rows,
+        );
+        TruthTable::new(propositions, rows)
+    }
+
+    pub(super) fn truth_table(&self) -> TruthTable<'_> {
         let propositions = self.propositions().into_iter().sorted().collect_vec();
-        let possible_inputs = itertools::repeat_n([true, false].into_iter(), propositions.len())
+        self.truth_table_with_propositions(&propositions)
+    }
+
+    /// Truth table over `propositions` in the given order, which must include all propositions
+    /// of this formula. Rows count up in binary from all false, so that row `i` has index `i`.
+    pub(super) fn truth_table_with_propositions<'a>(
+        &self,
+        propositions: &[&'a str],
+    ) -> TruthTable<'a> {
+        let possible_inputs = itertools::repeat_n([false, true].into_iter(), propositions.len())
             .multi_cartesian_product();
-        possible_inputs
+        let rows = possible_inputs
             .map(|inputs| {
                 let assignment = propositions
-                    .clone()
-                    .into_iter()
+                    .iter()
+                    .copied()
                     .zip_eq(inputs)
                     .collect::<HashMap<_, _>>()
                     .into();
                 let result = self.substitute(&assignment);
                 (assignment, result)
             })
-            .collect::<Vec<_>>()
-            .into()
+            .collect::<Vec<_>>();
+        TruthTable::new(propositions, rows)
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            Expr::Literal(_) | Expr::Proposition(_) => 1,
+            Expr::Negation(expr) => 1 + expr.size(),
+            Expr::BinOp { lhs, rhs, .. } => 1 + lhs.size() + rhs.size(),
+        }
+    }
+
+    /// Distinct non-atomic subformulas in ascending order of size, the last being the whole
+    /// formula.
+    pub(super) fn subformulas(&self) -> Vec<&Expr> {
+        fn collect<'a>(expr: &'a Expr, subformulas: &mut Vec<&'a Expr>) {
+            match expr {
+                Expr::Literal(_) | Expr::Proposition(_) => return,
+                Expr::Negation(inner) => collect(inner, subformulas),
+                Expr::BinOp { lhs, rhs, .. } => {
+                    collect(lhs, subformulas);
+                    collect(rhs, subformulas);
+                }
+            }
+            subformulas.push(expr);
+        }
+        let mut subformulas = Vec::new();
+        collect(self, &mut subformulas);
+        subformulas
+            .into_iter()
+            .unique_by(|expr| expr.to_string())
+            .sorted_by_key(|expr| expr.size())
+            .collect_vec()
+    }
+
+    pub(super) fn negation(expr: Expr) -> Self {
+        Expr::Negation(Box::new(expr))
+    }
+
+    pub(super) fn binop(lhs: Expr, op: Operator, rhs: Expr) -> Self {
+        Expr::BinOp {
+            lhs: Box::new(lhs),
+            op,
+            rhs: Box::new(rhs),
+        }
+    }
+
+    /// Removes the literals `T` and `F` from all subformulas that contain them, unless the whole
+    /// formula folds to a literal.
+    pub(super) fn fold_constants(&self) -> Self {
+        use Operator::*;
+        match self {
+            Expr::Literal(_) | Expr::Proposition(_) => self.clone(),
+            Expr::Negation(expr) => match expr.fold_constants() {
+                Expr::Literal(v) => Expr::Literal(!v),
+                expr => Self::negation(expr),
+            },
+            Expr::BinOp { lhs, op, rhs } => {
+                let (lhs, rhs) = (lhs.fold_constants(), rhs.fold_constants());
+                let (v, other, literal_on_left) = match (&lhs, &rhs) {
+                    (Expr::Literal(l), Expr::Literal(r)) => {
+                        let expr = Self::binop(Expr::Literal(*l), op.clone(), Expr::Literal(*r));
+                        return Expr::Literal(expr.partial_substitute(&HashMap::new()).unwrap());
+                    }
+                    (Expr::Literal(v), other) => (*v, other, true),
+                    (other, Expr::Literal(v)) => (*v, other, false),
+                    _ => return Self::binop(lhs, op.clone(), rhs),
+                };
+                let other = other.clone();
+                match (op, v) {
+                    (Conjunction, true) | (Disjunction, false) => other,
+                    (Conjunction, false) | (Disjunction, true) => Expr::Literal(v),
+                    // `T → Q = Q`, `F → Q = T`, `P → T = T`, `P → F = ¬P`
+                    (Implication, true) if literal_on_left => other,
+                    (Implication, false) if literal_on_left => Expr::Literal(true),
+                    (Implication, true) => Expr::Literal(true),
+                    (Implication, false) => Self::negation(other),
+                    (Equivalence, true) | (ExclusiveDisjunction, false) => other,
+                    (Equivalence, false) | (ExclusiveDisjunction, true) => Self::negation(other),
+                    (NonConjunction, false) => Expr::Literal(true),
+                    (NonDisjunction, true) => Expr::Literal(false),
+                    (NonConjunction, true) | (NonDisjunction, false) => Self::negation(other),
+                }
+            }
+        }
+    }
+
+    /// Negation normal form, with only `∧`, `∨` and `¬` directly on propositions.
+    pub(super) fn to_nnf(&self) -> Self {
+        self.nnf(false)
+    }
+
+    /// Negation normal form of `¬self` if `negated`.
+    fn nnf(&self, negated: bool) -> Self {
+        use Operator::*;
+        match self {
+            Expr::Literal(v) => Expr::Literal(*v != negated),
+            Expr::Proposition(_) if negated => Self::negation(self.clone()),
+            Expr::Proposition(_) => self.clone(),
+            Expr::Negation(expr) => expr.nnf(!negated),
+            Expr::BinOp { lhs, op, rhs } => {
+                let (and, or) = if negated {
+                    (Disjunction, Conjunction)
+                } else {
+                    (Conjunction, Disjunction)
+                };
+                match op {
+                    Conjunction => Self::binop(lhs.nnf(negated), and, rhs.nnf(negated)),
+                    Disjunction => Self::binop(lhs.nnf(negated), or, rhs.nnf(negated)),
+                    // p → q = ¬p ∨ q
+                    Implication => Self::binop(lhs.nnf(!negated), or, rhs.nnf(negated)),
+                    // p ↑ q = ¬p ∨ ¬q
+                    NonConjunction => Self::binop(lhs.nnf(!negated), or, rhs.nnf(!negated)),
+                    // p ↓ q = ¬p ∧ ¬q
+                    NonDisjunction => Self::binop(lhs.nnf(!negated), and, rhs.nnf(!negated)),
+                    // p ⇄ q = (¬p ∨ q) ∧ (p ∨ ¬q), p ⊕ q = (p ∨ q) ∧ (¬p ∨ ¬q)
+                    Equivalence | ExclusiveDisjunction => {
+                        let equivalent = matches!(op, Equivalence) != negated;
+                        Self::binop(
+                            Self::binop(lhs.nnf(equivalent), Disjunction, rhs.nnf(false)),
+                            Conjunction,
+                            Self::binop(lhs.nnf(!equivalent), Disjunction, rhs.nnf(true)),
+                        )
+                    }
+                }
+            }
+        }
+    }
+
+    /// Conjunctive normal form by distributing `∨` over `∧` in the negation normal form.
+    pub(super) fn to_cnf(&self) -> Self {
+        fn cnf(expr: Expr) -> Expr {
+            match expr {
+                Expr::BinOp {
+                    lhs,
+                    op: Operator::Conjunction,
+                    rhs,
+                } => Expr::binop(cnf(*lhs), Operator::Conjunction, cnf(*rhs)),
+                Expr::BinOp {
+                    lhs,
+                    op: Operator::Disjunction,
+                    rhs,
+                } => distribute(cnf(*lhs), cnf(*rhs)),
+                expr => expr,
+            }
+        }
+        fn distribute(lhs: Expr, rhs: Expr) -> Expr {
+            match (lhs, rhs) {
+                (
+                    Expr::BinOp {
+                        lhs: a,
+                        op: Operator::Conjunction,
+                        rhs: b,
+                    },
+                    c,
+                ) => Expr::binop(
+                    distribute(*a, c.clone()),
+                    Operator::Conjunction,
+                    distribute(*b, c),
+                ),
+                (
+                    a,
+                    Expr::BinOp {
+                        lhs: b,
+                        op: Operator::Conjunction,
+                        rhs: c,
+                    },
+                ) => Expr::binop(
+                    distribute(a.clone(), *b),
+                    Operator::Conjunction,
+                    distribute(a, *c),
+                ),
+                (a, b) => Expr::binop(a, Operator::Disjunction, b),
+            }
+        }
+        cnf(self.to_nnf())
+    }
+
+    /// TeX with parentheses only where the precedence requires.
+    pub(super) fn to_tex(&self) -> String {
+        fn precedence(op: &Operator) -> u8 {
+            match op {
+                Operator::Equivalence => 1,
+                Operator::Implication => 2,
+                Operator::Disjunction
+                | Operator::ExclusiveDisjunction
+                | Operator::NonDisjunction => 3,
+                Operator::Conjunction | Operator::NonConjunction => 4,
+            }
+        }
+        fn paren(expr: &Expr, min_precedence: u8) -> String {
+            match expr {
+                Expr::BinOp { op, .. } if precedence(op) < min_precedence => {
+                    format!(r"\left({}\right)", expr.to_tex())
+                }
+                _ => expr.to_tex(),
+            }
+        }
+        match self {
+            Expr::Literal(true) => r"\mathbf{T}".to_string(),
+            Expr::Literal(false) => r"\mathbf{F}".to_string(),
+            Expr::Proposition(proposition) => proposition.clone(),
+            Expr::Negation(expr) => format!(r"\lnot {}", paren(expr, u8::MAX)),
+            Expr::BinOp { lhs, op, rhs } => format!(
+                "{} {} {}",
+                paren(lhs, precedence(op)),
+                op.to_tex(),
+                paren(rhs, precedence(op) + 1)
+            ),
+        }
+    }
+}
+
+/// Fully parenthesized TeX.
+impl Display for Expr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Literal(true) => write!(f, r"\mathbf{{T}}"),
+            Expr::Literal(false) => write!(f, r"\mathbf{{F}}"),
+            Expr::Proposition(proposition) => write!(f, "{proposition}"),
+            Expr::Negation(expr) => write!(f, r"\lnot {expr}"),
+            Expr::BinOp { lhs, op, rhs } => write!(f, "({lhs} {} {rhs})", op.to_tex()),
+        }
     }
 }
 
-#[derive(Debug, Clone)]
-enum Operator {
+#[test]
+fn test_ascii_operators() {
+    let results = |input: &str| {
+        Expr::parse(input)
+            .unwrap()
+            .truth_table()
+            .iter()
+            .map(|(_, result)| *result)
+            .collect_vec()
+    };
+    assert_eq!(results("P & Q -> !R"), results("P ∧ Q → ¬R"));
+    assert_eq!(results("P && ~Q || R"), results("P ∧ ¬Q ∨ R"));
+    assert_eq!(results("(P <-> Q) = (P | R)"), results("(P ⇄ Q) ⇄ (P ∨ R)"));
+    assert_eq!(results("P ∧ Q -> ¬R | S"), results("P ∧ Q → ¬R ∨ S"));
+    assert_eq!(formula_to_tex("P & !Q"), formula_to_tex("P ∧ ¬Q"));
+}
+
+#[test]
+fn test_subformulas() {
+    let expr = Expr::parse("¬P ∨ (P ∧ Q) → ¬P").unwrap();
+    assert_eq!(
+        expr.subformulas()
+            .into_iter()
+            .map(|expr| expr.to_string())
+            .collect_vec(),
+        [
+            r"\lnot P",
+            r"(P \land Q)",
+            r"(\lnot P \lor (P \land Q))",
+            r"((\lnot P \lor (P \land Q)) \to \lnot P)",
+        ]
+    );
+}
+
+#[test]
+fn test_display_expr() {
+    let expr = Expr::parse("P ∧ Q → R").unwrap();
+    assert_eq!(expr.to_string(), r"((P \land Q) \to R)");
+    let expr = Expr::parse("¬(P ∨ Q) ⇄ ¬R ∧ T").unwrap();
+    assert_eq!(
+        expr.to_string(),
+        r"(\lnot (P \lor Q) \leftrightarrow (\lnot R \land \mathbf{T}))"
+    );
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Operator {
     Conjunction,
     Disjunction,
     Implication,
     Equivalence,
+    ExclusiveDisjunction,
+    NonConjunction,
+    NonDisjunction,
 }
 
-struct Assignment<'a>(HashMap<&'a str, bool>);
+impl Operator {
+    fn to_tex(&self) -> &'static str {
+        match self {
+            Operator::Conjunction => r"\land",
+            Operator::Disjunction => r"\lor",
+            Operator::Implication => r"\to",
+            Operator::Equivalence => r"\leftrightarrow",
+            Operator::ExclusiveDisjunction => r"\oplus",
+            Operator::NonConjunction => r"\uparrow",
+            Operator::NonDisjunction => r"\downarrow",
+        }
+    }
+}
+
+#[test]
+fn test_fold_constants() {
+    let folded = |input: &str| Expr::parse(input).unwrap().fold_constants().to_tex();
+    assert_eq!(folded("T → Q"), "Q");
+    assert_eq!(folded("F ∧ P"), r"\mathbf{F}");
+    assert_eq!(folded("(P ∧ T) ∨ (Q ∨ F)"), r"P \lor Q");
+    assert_eq!(folded("P → F"), r"\lnot P");
+    assert_eq!(folded("¬T ⇄ ¬F"), r"\mathbf{F}");
+}
+
+#[test]
+fn test_rewriting() {
+    for input in [
+        "((P ∧ (T → Q)) → ¬(R ⇄ Q)) ∧ ¬S",
+        "¬(P ⊕ Q) ∨ (P ↑ R)",
+        "¬(P ↓ (Q ⇄ ¬R))",
+        "(P ∧ Q) ∨ (R ∧ S) ∨ ¬(P → F)",
+    ] {
+        let expr = Expr::parse(input).unwrap();
+        let propositions = expr.propositions().into_iter().sorted().collect_vec();
+        let results = |expr: &Expr| {
+            expr.truth_table_with_propositions(&propositions)
+                .iter()
+                .map(|(_, result)| *result)
+                .collect_vec()
+        };
+        assert_eq!(results(&expr.to_nnf()), results(&expr));
+        assert_eq!(results(&expr.to_cnf()), results(&expr));
+    }
+    let expr = Expr::parse("¬(P → Q ∧ R)").unwrap();
+    assert_eq!(
+        expr.to_nnf().to_tex(),
+        r"P \land \left(\lnot Q \lor \lnot R\right)"
+    );
+    assert_eq!(
+        expr.to_cnf().to_tex(),
+        r"P \land \left(\lnot Q \lor \lnot R\right)"
+    );
+    let expr = Expr::parse("P ∨ Q ∧ R").unwrap();
+    assert_eq!(
+        expr.to_cnf().to_tex(),
+        r"\left(P \lor Q\right) \land \left(P \lor R\right)"
+    );
+}
+
+#[test]
+fn test_extended_operators() {
+    let results = |input: &str| {
+        Expr::parse(input)
+            .unwrap()
+            .truth_table()
+            .iter()
+            .map(|(_, result)| *result)
+            .collect_vec()
+    };
+    assert_eq!(results("P ⊕ Q"), [false, true, true, false]);
+    assert_eq!(results("P ↑ Q"), [true, true, true, false]);
+    assert_eq!(results("P ↓ Q"), [true, false, false, false]);
+    // ↑ binds as tight as ∧, ⊕ as loose as ∨
+    assert_eq!(results("P ⊕ Q ↑ Q"), results("P ⊕ (Q ↑ Q)"));
+}
+
+#[test]
+fn test_truth_table_order() {
+    let expr = Expr::parse("P → Q").unwrap();
+    let truth_table = expr.truth_table();
+    let rows = truth_table
+        .iter()
+        .map(|(assignment, result)| (assignment["P"], assignment["Q"], *result))
+        .collect_vec();
+    assert_eq!(
+        rows,
+        [
+            (false, false, true),
+            (false, true, true),
+            (true, false, false),
+            (true, true, true),
+        ]
+    );
+    assert_eq!(
+        truth_table
+            .iter()
+            .map(|(assignment, _)| truth_table.index(assignment))
+            .collect_vec(),
+        [0, 1, 2, 3]
+    );
+}
+
+#[test]
+fn test_rows_with_result() {
+    let expr = Expr::parse("(P ∨ Q) ∧ ¬R").unwrap();
+    let propositions = expr.propositions().into_iter().sorted().collect_vec();
+    let truth_table = expr.truth_table();
+    for result in [true, false] {
+        let expected = truth_table
+            .iter()
+            .filter(|(_, r)| *r == result)
+            .map(|(assignment, _)| truth_table.index(assignment))
+            .collect_vec();
+        let rows = expr.rows_with_result(&propositions, result, usize::MAX);
+        let actual = rows
+            .iter()
+            .map(|(assignment, _)| rows.index(assignment))
+            .collect_vec();
+        assert_eq!(actual, expected);
+    }
+    assert_eq!(expr.rows_with_result(&propositions, false, 2).len(), 2);
+    let mut minterms = expr.rows_with_result(&propositions, true, 4);
+    assert!(!minterms.truncate(3));
+    assert!(minterms.truncate(2));
+}
+
+/// Replace operator symbols in `formula` with TeX commands.
+pub(super) fn formula_to_tex(formula: &str) -> String {
+    formula
+        .replace("<->", "⇄")
+        .replace("->", "→")
+        .replace("&&", "∧")
+        .replace('&', "∧")
+        .replace("||", "∨")
+        .replace('|', "∨")
+        .replace('=', "⇄")
+        .replace(['!', '~'], "¬")
+        .replace('¬', r"\lnot ")
+        .replace('∧', r"\land ")
+        .replace('∨', r"\lor ")
+        .replace('→', r"\to ")
+        .replace('⇄', r"\leftrightarrow ")
+        .replace('⊕', r"\oplus ")
+        .replace('↑', r"\uparrow ")
+        .replace('↓', r"\downarrow ")
+}
+
+pub(super) struct Assignment<'a>(HashMap<&'a str, bool>);
 
 impl<'a> From<HashMap<&'a str, bool>> for Assignment<'a> {
     fn from(value: HashMap<&'a str, bool>) -> Self {
@@ -163,10 +686,168 @@ impl<'a> Deref for Assignment<'a> {
     }
 }
 
-struct TruthTable<'a>(Vec<(Assignment<'a>, bool)>);
+/// Parse a line like `P=T Q=F`, `None` if `line` is not an assignment. A line like `P=Q`
+/// is a formula as `=` also denotes `⇄`.
+fn parse_assignment(line: &str) -> Option<Assignment<'_>> {
+    line.split_whitespace()
+        .map(|token| {
+            let (name, value) = token.split_once('=')?;
+            let value = match value.to_ascii_lowercase().as_str() {
+                "t" | "true" | "1" => true,
+                "f" | "false" | "0" => false,
+                _ => return None,
+            };
+            Some((name, value))
+        })
+        .collect::<Option<HashMap<_, _>>>()
+        .filter(|assignment| !assignment.is_empty())
+        .map(Assignment::from)
+}
+
+/// Parse a header line like `order: P, Q, R`, `None` if `line` is not one.
+fn parse_order(line: &str) -> Option<Vec<&str>> {
+    let order = line.trim().strip_prefix("order:")?;
+    Some(
+        order
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|name| !name.is_empty())
+            .collect_vec(),
+    )
+}
+
+/// Propositions of `expr` in `order`, which must list each of them exactly once.
+fn ordered_propositions<'a>(expr: &'a Expr, order: &[&str]) -> Result<Vec<&'a str>, String> {
+    let propositions = expr.propositions();
+    if let Some(name) = order.iter().duplicates().next() {
+        return Err(format!("命题变元 {name} 在顺序中重复出现."));
+    }
+    if order.len() != propositions.len() || !order.iter().all(|p| propositions.contains(p)) {
+        return Err(format!(
+            "顺序应恰好列出公式中的命题变元: {}.",
+            propositions.iter().sorted().join(", ")
+        ));
+    }
+    Ok(order
+        .iter()
+        .map(|name| propositions.get(*name).copied().unwrap())
+        .collect_vec())
+}
+
+#[test]
+fn test_ordered_propositions() {
+    assert_eq!(parse_order("order: R, Q P"), Some(vec!["R", "Q", "P"]));
+    assert_eq!(parse_order("R ∧ Q"), None);
+    let expr = Expr::parse("R ∧ ¬P").unwrap();
+    let order = ordered_propositions(&expr, &["R", "P"]).unwrap();
+    let truth_table = expr.truth_table_with_propositions(&order);
+    // R = T, P = F is 10 over R, P but 01 over P, R
+    assert_eq!(truth_table.minterm_indices(), [2]);
+    assert_eq!(expr.truth_table().minterm_indices(), [1]);
+    assert!(ordered_propositions(&expr, &["R"]).is_err());
+    assert!(ordered_propositions(&expr, &["R", "P", "Q"]).is_err());
+    assert!(ordered_propositions(&expr, &["R", "R"]).is_err());
+}
 
-impl TruthTable<'_> {
+/// Replace propositions in `formula` with their truth values in TeX.
+fn substitute_formula(formula: &str, assignment: &Assignment) -> String {
+    let mut result = String::new();
+    let mut chars = formula.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_ascii_alphabetic() || c == '_' {
+            let mut name = c.to_string();
+            while let Some(&c) = chars
+                .peek()
+                .filter(|c| c.is_ascii_alphanumeric() || **c == '_')
+            {
+                name.push(c);
+                chars.next();
+            }
+            match assignment.get(name.as_str()) {
+                Some(true) => result.push_str(r"\mathbf{T}"),
+                Some(false) => result.push_str(r"\mathbf{F}"),
+                None => result.push_str(&name),
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    formula_to_tex(&result)
+}
+
+#[test]
+fn test_parse_assignment() {
+    assert!(parse_assignment("P ∧ Q").is_none());
+    assert!(parse_assignment("P=Q").is_none());
+    let assignment = parse_assignment("P=T Q=0").unwrap();
+    assert!(assignment["P"]);
+    assert!(!assignment["Q"]);
+    assert!(parse_assignment("P=T Q").is_none());
+    assert_eq!(
+        substitute_formula("P ∧ PQ", &assignment),
+        formula_to_tex(r"\mathbf{T} ∧ PQ")
+    );
+}
+
+pub(super) struct TruthTable<'a> {
+    /// Column order, which is also the bit order of term indices
+    propositions: Vec<&'a str>,
+    rows: Vec<(Assignment<'a>, bool)>,
+}
+
+impl<'a> TruthTable<'a> {
+    fn new(propositions: &[&'a str], rows: Vec<(Assignment<'a>, bool)>) -> Self {
+        Self {
+            propositions: propositions.to_vec(),
+            rows,
+        }
+    }
+
+    /// Keeps the first `len` rows, returning whether any were dropped.
+    fn truncate(&mut self, len: usize) -> bool {
+        let truncated = self.rows.len() > len;
+        self.rows.truncate(len);
+        truncated
+    }
+
+    fn is_tautology(&self) -> bool {
+        self.iter().all(|(_, result)| *result)
+    }
+
+    fn is_contradiction(&self) -> bool {
+        self.iter().all(|(_, result)| !*result)
+    }
+
+    /// Binary index of `assignment` over the column order, the first proposition being the
+    /// most significant bit and true being 1.
+    fn index(&self, assignment: &Assignment) -> usize {
+        self.propositions
+            .iter()
+            .fold(0, |index, p| index << 1 | assignment[p] as usize)
+    }
+
+    /// Sorted indices of the minterms in the disjunctive normal form.
+    pub(super) fn minterm_indices(&self) -> Vec<usize> {
+        self.iter()
+            .filter(|(_, result)| *result)
+            .map(|(assignment, _)| self.index(assignment))
+            .sorted()
+            .collect_vec()
+    }
+
+    /// Sorted indices of the maxterms in the conjunctive normal form.
+    fn maxterm_indices(&self) -> Vec<usize> {
+        self.iter()
+            .filter(|(_, result)| !*result)
+            .map(|(assignment, _)| self.index(assignment))
+            .sorted()
+            .collect_vec()
+    }
+
+    /// `\top` if there is no maxterm (the formula is a tautology).
     fn conjunctive_normal_form(&self) -> String {
+        if self.is_tautology() {
+            return r"\top".to_string();
+        }
         self.iter()
             .filter_map(|(assignment, result)| {
                 if *result {
@@ -174,9 +855,8 @@ impl TruthTable<'_> {
                 } else {
                     Some(format!(
                         r" \left({}\right) ",
-                        assignment
-                            .keys()
-                            .sorted()
+                        self.propositions
+                            .iter()
                             .map(|p| {
                                 if assignment[p] {
                                     format!(r"\lnot {}", p)
@@ -191,15 +871,18 @@ impl TruthTable<'_> {
             .join(r" \land ")
     }
 
+    /// `\bot` if there is no minterm (the formula is a contradiction).
     fn disjunctive_normal_form(&self) -> String {
+        if self.is_contradiction() {
+            return r"\bot".to_string();
+        }
         self.iter()
             .filter_map(|(assignment, result)| {
                 if *result {
                     Some(format!(
                         r" \left({}\right) ",
-                        assignment
-                            .keys()
-                            .sorted()
+                        self.propositions
+                            .iter()
                             .map(|p| {
                                 if assignment[p] {
                                     p.to_string()
@@ -217,22 +900,194 @@ impl TruthTable<'_> {
     }
 }
 
-impl<'a> From<Vec<(Assignment<'a>, bool)>> for TruthTable<'a> {
-    fn from(value: Vec<(Assignment<'a>, bool)>) -> Self {
-        Self(value)
-    }
+#[test]
+fn test_term_indices() {
+    let expr = Expr::parse("P → Q").unwrap();
+    let truth_table = expr.truth_table();
+    assert_eq!(truth_table.minterm_indices(), [0, 1, 3]);
+    assert_eq!(truth_table.maxterm_indices(), [2]);
 }
 
 impl<'a> Deref for TruthTable<'a> {
     type Target = Vec<(Assignment<'a>, bool)>;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.rows
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq)]
-pub struct Exp1;
+/// Plain text minterm like `m3 = ¬P ∧ Q ∧ R` of a true row, or maxterm of a false one.
+fn term_of_row(
+    propositions: &[&str],
+    assignment: &Assignment,
+    result: bool,
+    index: usize,
+) -> String {
+    let literal = |p: &&str| {
+        if assignment[p] == result {
+            p.to_string()
+        } else {
+            format!("¬{p}")
+        }
+    };
+    if result {
+        format!(
+            "m{index} = {}",
+            propositions.iter().map(literal).join(" ∧ ")
+        )
+    } else {
+        format!(
+            "M{index} = {}",
+            propositions.iter().map(literal).join(" ∨ ")
+        )
+    }
+}
+
+#[test]
+fn test_term_of_row() {
+    let assignment = parse_assignment("P=F Q=T").unwrap();
+    assert_eq!(
+        term_of_row(&["P", "Q"], &assignment, true, 1),
+        "m1 = ¬P ∧ Q"
+    );
+    assert_eq!(
+        term_of_row(&["P", "Q"], &assignment, false, 1),
+        "M1 = P ∨ ¬Q"
+    );
+}
+
+/// Summary like `\bigvee m_{0,3,5}`, or `empty` if there is no term.
+fn terms_to_tex(big_operator: &str, name: &str, indices: &[usize], empty: &str) -> String {
+    if indices.is_empty() {
+        empty.to_string()
+    } else {
+        format!(r"{big_operator} {name}_{{{}}}", indices.iter().join(","))
+    }
+}
+
+fn formula_type(tautology: bool, contradiction: bool) -> &'static str {
+    if tautology {
+        "重言式 (永真式)."
+    } else if contradiction {
+        "矛盾式 (永假式)."
+    } else {
+        "可满足式 (非重言式)."
+    }
+}
+
+/// Answer of [`Exp1`] in TeX besides the truth table.
+#[derive(Debug, Clone, PartialEq)]
+struct NormalFormsTex {
+    formula_type: &'static str,
+    /// Fully parenthesized formula.
+    parsed: String,
+    /// Constant folding if it changes anything, the negation and the conjunctive normal form.
+    equivalences: String,
+    dnf: String,
+    /// The disjunctive normal form by minterm indices, e.g. `\bigvee(m_1, m_3)`.
+    minterms: String,
+    cnf: String,
+    maxterms: String,
+}
+
+fn normal_forms_tex(expr: &Expr, truth_table: &TruthTable) -> NormalFormsTex {
+    let folded = expr.fold_constants();
+    NormalFormsTex {
+        formula_type: formula_type(truth_table.is_tautology(), truth_table.is_contradiction()),
+        parsed: expr.to_string(),
+        equivalences: format!(
+            r"\begin{{align*}} {}&\text{{否定范式: }} {} \\[1ex] &\text{{合取范式: }} {} \end{{align*}}",
+            if folded == *expr {
+                String::new()
+            } else {
+                format!(r"&\text{{常量化简: }} {} \\[1ex] ", folded.to_tex())
+            },
+            expr.to_nnf().to_tex(),
+            expr.to_cnf().to_tex()
+        ),
+        dnf: truth_table.disjunctive_normal_form(),
+        minterms: terms_to_tex(r"\bigvee", "m", &truth_table.minterm_indices(), r"\bot"),
+        cnf: truth_table.conjunctive_normal_form(),
+        maxterms: terms_to_tex(r"\bigwedge", "M", &truth_table.maxterm_indices(), r"\top"),
+    }
+}
+
+#[test]
+fn test_normal_forms_tex() {
+    let expr = Expr::parse("P ∨ F").unwrap();
+    let answer = normal_forms_tex(&expr, &expr.truth_table());
+    assert_eq!(answer.formula_type, "可满足式 (非重言式).");
+    assert_eq!(answer.parsed, r"(P \lor \mathbf{F})");
+    assert!(answer.equivalences.contains(r"\text{常量化简: } P"));
+    assert_eq!(answer.dnf, r" \left(P\right) ");
+    let expr = Expr::parse("P ∨ ¬P").unwrap();
+    let answer = normal_forms_tex(&expr, &expr.truth_table());
+    assert_eq!(answer.formula_type, "重言式 (永真式).");
+    assert!(!answer.equivalences.contains("常量化简"));
+    assert_eq!(answer.maxterms, r"\top");
+}
+
+/// Answer of [`Exp1`] under an assignment in TeX.
+#[derive(Debug, Clone, PartialEq)]
+struct AssignmentTex {
+    assignment: String,
+    substituted: String,
+    value: &'static str,
+}
+
+fn assignment_tex(formula: &str, assignment: &Assignment) -> Result<AssignmentTex, String> {
+    let expr = Expr::parse(formula).map_err(|e| syntax_error(&e))?;
+    let propositions = expr.propositions().into_iter().sorted().collect_vec();
+    let missing = propositions
+        .iter()
+        .filter(|p| !assignment.contains_key(*p))
+        .join(", ");
+    if !missing.is_empty() {
+        return Err(format!("赋值缺少命题变元: {missing}."));
+    }
+    let to_tex = |v: bool| if v { r"\mathbf{T}" } else { r"\mathbf{F}" };
+    Ok(AssignmentTex {
+        assignment: propositions
+            .iter()
+            .map(|p| format!("{p} = {}", to_tex(assignment[p])))
+            .join(r",\ "),
+        substituted: substitute_formula(formula, assignment),
+        value: to_tex(expr.substitute(assignment)),
+    })
+}
+
+#[test]
+fn test_assignment_tex() {
+    let assignment = parse_assignment("P=T Q=F").unwrap();
+    let answer = assignment_tex("P → Q", &assignment).unwrap();
+    assert_eq!(answer.assignment, r"P = \mathbf{T},\ Q = \mathbf{F}");
+    assert_eq!(answer.value, r"\mathbf{F}");
+    assert_eq!(
+        assignment_tex("P ∧ R", &assignment),
+        Err("赋值缺少命题变元: R.".to_string())
+    );
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exp1 {
+    /// Formulas with more propositions are not given a full truth table.
+    pub max_propositions: usize,
+    /// Maximum number of terms listed in each normal form of such formulas.
+    pub max_terms: usize,
+    /// Show a truth table column for each intermediate subformula, also enabled by a first
+    /// line `subformulas`.
+    pub subformula_columns: bool,
+}
+
+impl Default for Exp1 {
+    fn default() -> Self {
+        Self {
+            max_propositions: 16,
+            max_terms: 256,
+            subformula_columns: false,
+        }
+    }
+}
 
 impl Solver for Exp1 {
     fn id(&self) -> String {
@@ -244,27 +1099,59 @@ impl Solver for Exp1 {
     }
 
     fn description(&self) -> View {
-        "输入命题公式.".into_view()
+        "输入命题公式, 运算符也可写作 &, |, !, -> 和 <->. 首行为 subformulas 时, 真值表列出每个子公式的真值. 可在首行以 order: P, Q, R 指定命题变元在真值表中的顺序, 也决定极小项与极大项的编号. 可在末行给出一组赋值 (如 P=T Q=F), 此时只求公式在该赋值下的真值. 多个公式之间用单独一行 --- 分隔.".into_view()
     }
 
     fn default_input(&self) -> String {
         "((P ∧ (T → Q)) → ¬(R ⇄ Q)) ∧ ¬S".to_string()
     }
 
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
     fn solve(&self, input: String) -> View {
+        let (subformulas, rest) = split_keyword_line(&input, "subformulas");
+        let subformula_columns = subformulas || self.subformula_columns;
+        let input = rest.to_string();
+        let (order, input) = match input.split_once('\n') {
+            Some((header, formula)) => match parse_order(header) {
+                Some(order) => (Some(order), formula.to_string()),
+                None => (None, input.clone()),
+            },
+            None => (None, input.clone()),
+        };
+        let lines = input.trim_end().lines().collect_vec();
+        if let Some((last, formula)) = lines
+            .split_last()
+            .filter(|(_, formula)| !formula.is_empty())
+        {
+            if let Some(assignment) = parse_assignment(last) {
+                return self.solve_with_assignment(&formula.join("\n"), &assignment);
+            }
+        }
         let expr = match Expr::parse(input.as_str()) {
             Ok(expr) => expr,
-            Err(e) => {
-                return view! {
-                    <pre class="text-red-500"> {
-                        format!("error: invalid syntax \n{}", e.with_path("<Input Section>"))
-                    } </pre>
-                }
-                .into_view()
-            }
+            Err(e) => return self.render_error(&syntax_error(&e)),
+        };
+        let propositions = match order {
+            Some(order) => match ordered_propositions(&expr, &order) {
+                Ok(propositions) => propositions,
+                Err(e) => return self.render_error(&e),
+            },
+            None => expr.propositions().into_iter().sorted().collect_vec(),
+        };
+        if propositions.len() > self.max_propositions {
+            return self.solve_without_truth_table(&expr, &propositions);
+        }
+        let truth_table = expr.truth_table_with_propositions(&propositions);
+        let intermediates = if subformula_columns {
+            let mut subformulas = expr.subformulas();
+            subformulas.pop();
+            subformulas
+        } else {
+            vec![]
         };
-        let propositions = expr.propositions().into_iter().sorted().collect_vec();
-        let truth_table = expr.truth_table();
         let (class_name, style_val) = style_str! {
             thead > tr {
                 border-top: 1px solid #333;
@@ -287,10 +1174,43 @@ impl Solver for Exp1 {
                 text-align: center;
                 padding: 0.3rem 1.5rem;
             }
+
+            td.result {
+                cursor: help;
+            }
+
+            tr.minterm:has(td.result:hover) td.literal {
+                background-color: rgb(205, 233, 255);
+            }
+
+            tr.maxterm:has(td.result:hover) td.literal {
+                background-color: rgb(255, 221, 221);
+            }
         };
+        let NormalFormsTex {
+            formula_type,
+            parsed,
+            equivalences,
+            dnf,
+            minterms,
+            cnf,
+            maxterms,
+        } = normal_forms_tex(&expr, &truth_table);
         view! {
             class = class_name,
             <Style> {style_val} </Style>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "公式类型" </p>
+                <p class="text-xl font-bold"> { formula_type } </p>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "解析后的公式" </p>
+                <KaTeX expr={ parsed } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "等值演算" </p>
+                <KaTeX display_mode=true fleqn=true expr={ equivalences } />
+            </div>
             <div class="mb-10">
                 <p class="font-bold mb-2"> "真值表" </p>
                 <table>
@@ -302,20 +1222,35 @@ impl Solver for Exp1 {
                                     <th><KaTeX expr={ p.to_string() } /></th>
                                 }).collect_vec()
                             }
-                            <th><KaTeX expr={ input.clone() } /></th>
+                            {
+                                intermediates.iter().map(|subformula| view! {
+                                    class = class_name,
+                                    <th><KaTeX expr={ subformula.to_tex() } /></th>
+                                }).collect_vec()
+                            }
+                            <th><KaTeX expr={ formula_to_tex(input.as_str()) } /></th>
                         </tr>
                     </thead>
                     <tbody> {
                         truth_table.iter().map(|(assignment, result)| view! {
                             class = class_name,
-                            <tr>
+                            // hovering the result highlights the literals of the row's minterm or maxterm
+                            <tr class:minterm=*result class:maxterm=!*result data-term={ term_of_row(&propositions, assignment, *result, truth_table.index(assignment)) }>
                                 {
                                     propositions.iter().map(|p| view! {
                                         class = class_name,
-                                        <td><KaTeX expr={ if assignment[p] { r"\mathbf{T}" } else { r"\mathbf{F}" } } /></td>
+                                        <td class="literal"><KaTeX expr={ if assignment[p] { r"\mathbf{T}" } else { r"\mathbf{F}" } } /></td>
                                     }).collect_vec()
                                 }
-                                <td><KaTeX expr={ if *result { r"\mathbf{T}" } else { r"\mathbf{F}" } } /></td>
+                                {
+                                    intermediates.iter().map(|subformula| view! {
+                                        class = class_name,
+                                        <td><KaTeX expr={ if subformula.substitute(assignment) { r"\mathbf{T}" } else { r"\mathbf{F}" } } /></td>
+                                    }).collect_vec()
+                                }
+                                <td class="result" title={ term_of_row(&propositions, assignment, *result, truth_table.index(assignment)) }>
+                                    <KaTeX expr={ if *result { r"\mathbf{T}" } else { r"\mathbf{F}" } } />
+                                </td>
                             </tr>
                         }).collect_vec()
                     } </tbody>
@@ -323,11 +1258,87 @@ impl Solver for Exp1 {
             </div>
             <div class="mb-10">
                 <p class="font-bold mb-2"> "主析取范式" </p>
-                <KaTeX expr={ truth_table.disjunctive_normal_form() } />
+                <KaTeX expr={ dnf } />
+                <KaTeX expr={ minterms } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "主合取范式" </p>
+                <KaTeX expr={ cnf } />
+                <KaTeX expr={ maxterms } />
+            </div>
+        }
+        .into_view()
+    }
+}
+
+impl Exp1 {
+    fn solve_with_assignment(&self, formula: &str, assignment: &Assignment) -> View {
+        let AssignmentTex {
+            assignment,
+            substituted,
+            value,
+        } = match assignment_tex(formula, assignment) {
+            Ok(answer) => answer,
+            Err(e) => return self.render_error(&e),
+        };
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "赋值" </p>
+                <KaTeX expr={ assignment } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "代入后的公式" </p>
+                <KaTeX expr={ substituted } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "真值" </p>
+                <KaTeX expr={ value } />
+            </div>
+        }
+        .into_view()
+    }
+
+    fn solve_without_truth_table(&self, expr: &Expr, propositions: &[&str]) -> View {
+        let n = propositions.len();
+        // one more row than shown tells whether any were left out
+        let mut minterms = expr.rows_with_result(propositions, true, self.max_terms + 1);
+        let mut maxterms = expr.rows_with_result(propositions, false, self.max_terms + 1);
+        let minterms_truncated = minterms.truncate(self.max_terms);
+        let maxterms_truncated = maxterms.truncate(self.max_terms);
+        let truncated = |truncated: bool| {
+            if truncated {
+                view! {
+                    <p class="mt-2"> { format!("仅列出前 {} 项.", self.max_terms) } </p>
+                }
+                .into_view()
+            } else {
+                ().into_view()
+            }
+        };
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "警告" </p>
+                <p class="text-red-500"> {
+                    format!(
+                        "公式含有 {n} 个命题变元, 真值表共有 2^{n} = {} 行, 超过上限 {} 个命题变元, 不列出真值表. 以下范式通过剪枝枚举求得.",
+                        BigInt::from(2).pow(n),
+                        self.max_propositions
+                    )
+                } </p>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "公式类型" </p>
+                <p class="text-xl font-bold"> { formula_type(maxterms.is_empty(), minterms.is_empty()) } </p>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "主析取范式" </p>
+                <KaTeX expr={ minterms.disjunctive_normal_form() } />
+                { truncated(minterms_truncated) }
             </div>
             <div class="mb-10">
                 <p class="font-bold mb-2"> "主合取范式" </p>
-                <KaTeX expr={ truth_table.conjunctive_normal_form() } />
+                <KaTeX expr={ maxterms.conjunctive_normal_form() } />
+                { truncated(maxterms_truncated) }
             </div>
         }
         .into_view()
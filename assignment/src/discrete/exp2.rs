@@ -6,7 +6,113 @@ use num::{BigInt, Zero};
 use shiyanyi::*;
 use stylers::style_str;
 
-use crate::common::Matrix;
+use crate::common::{Matrix, MatrixParseError};
+
+fn reflexive_closure(matrix: &Matrix<bool>) -> Matrix<bool> {
+    let mut r = matrix.clone();
+    for i in 0..r.len() {
+        r[i][i] = true;
+    }
+    r
+}
+
+fn symmetric_closure(matrix: &Matrix<bool>) -> Matrix<bool> {
+    matrix.or(&matrix.transpose())
+}
+
+/// Warshall 算法, 依次为经过顶点 1 到 m 后的关系矩阵
+fn transitive_closure_steps(matrix: &Matrix<bool>) -> Vec<Matrix<bool>> {
+    let m = matrix.len();
+    let mut t = matrix.clone();
+    let mut steps = Vec::with_capacity(m);
+    for i in 0..m {
+        for j in 0..m {
+            if t[j][i] {
+                for k in 0..m {
+                    if t[i][k] {
+                        t[j][k] = true;
+                    }
+                }
+            }
+        }
+        steps.push(t.clone());
+    }
+    steps
+}
+
+fn transitive_closure(matrix: &Matrix<bool>) -> Matrix<bool> {
+    transitive_closure_steps(matrix)
+        .pop()
+        .unwrap_or_else(|| matrix.clone())
+}
+
+#[test]
+fn test_closures() {
+    let matrix = Matrix(vec![
+        vec![false, true, false],
+        vec![false, false, true],
+        vec![false, false, false],
+    ]);
+    assert_eq!(
+        symmetric_closure(&matrix),
+        Matrix(vec![
+            vec![false, true, false],
+            vec![true, false, true],
+            vec![false, true, false],
+        ])
+    );
+    assert_eq!(
+        reflexive_closure(&transitive_closure(&matrix)),
+        Matrix(vec![
+            vec![true, true, true],
+            vec![false, true, true],
+            vec![false, false, true],
+        ])
+    );
+    let steps = transitive_closure_steps(&matrix);
+    assert_eq!(steps.len(), 3);
+    // 1 -> 2 -> 3 only appears after passing through vertex 2
+    assert!(!steps[0][0][2]);
+    assert!(steps[1][0][2]);
+    assert_eq!(steps[2], transitive_closure(&matrix));
+}
+
+/// Connected components of the symmetric `matrix`, each sorted, in order of their smallest
+/// element.
+fn equivalence_classes(matrix: &Matrix<bool>) -> Vec<Vec<usize>> {
+    let m = matrix.len();
+    let mut visited = vec![false; m];
+    let mut classes = Vec::new();
+    for i in 0..m {
+        if visited[i] {
+            continue;
+        }
+        visited[i] = true;
+        let mut class = vec![i];
+        let mut stack = vec![i];
+        while let Some(j) = stack.pop() {
+            for k in 0..m {
+                if matrix[j][k] && !visited[k] {
+                    visited[k] = true;
+                    class.push(k);
+                    stack.push(k);
+                }
+            }
+        }
+        class.sort();
+        classes.push(class);
+    }
+    classes
+}
+
+#[test]
+fn test_equivalence_classes() {
+    let matrix = "1 0 1 0\n0 1 0 1\n1 0 1 0\n0 1 0 1"
+        .parse::<Matrix<BigInt>>()
+        .unwrap()
+        .map(|x| !x.is_zero());
+    assert_eq!(equivalence_classes(&matrix), [vec![0, 2], vec![1, 3]]);
+}
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Exp2;
@@ -21,7 +127,7 @@ impl Solver for Exp2 {
     }
 
     fn description(&self) -> View {
-        "输入关系矩阵.".into_view()
+        "输入关系矩阵; 输入以空行分隔的两个关系矩阵时另求其复合关系.".into_view()
     }
 
     fn default_input(&self) -> String {
@@ -36,43 +142,21 @@ impl Solver for Exp2 {
     }
 
     fn solve(&self, input: String) -> View {
-        let matrix = match input.parse::<Matrix<BigInt>>() {
-            Ok(matrix) => matrix,
-            Err(_) => {
-                return view! {
-                    <p> "Failed to parse." </p>
-                }
-                .into_view()
-            }
+        let RelationTex {
+            matrix,
+            reflexive_closure,
+            symmetric_closure,
+            transitive_closure,
+            transitive_closure_steps,
+            reflexive_transitive_closure,
+            properties,
+            equivalence_classes,
+            composition,
+        } = match relation_tex(&input) {
+            Ok(answer) => answer,
+            Err(e) => return self.render_error(&e),
         };
-        let (m, n) = matrix.shape();
-        if m != n {
-            return view! {
-                <p> "Matrix is not square." </p>
-            }
-            .into_view();
-        }
-        let matrix = matrix.map(|x| !x.is_zero());
-        let reflexive /* 自反性 */ = (0..m).all(|i| matrix[i][i]);
-        let irreflexive /* 反自反性 */ = (0..m).all(|i| !matrix[i][i]);
-        let symmetric /* 对称性 */ = (0..m).flat_map(|i| (0..i).map(|j| (i, j)).collect_vec()).all(|(i, j)| matrix[i][j] == matrix[j][i]);
-        let antisymmetric /* 反对称性 */ = (0..m).flat_map(|i| (0..i).map(|j| (i, j)).collect_vec()).all(|(i, j)| !matrix[i][j] || !matrix[j][i]);
-        // Warshall 算法
-        let mut t /* 传递闭包 */ = matrix.clone();
-        for i in 0..m {
-            for j in 0..m {
-                if t[j][i] {
-                    for k in 0..m {
-                        if t[i][k] {
-                            t[j][k] = true;
-                        }
-                    }
-                }
-            }
-        }
-        let transitive /* 传递性 */ = matrix == t;
-        let matrix = matrix.map(|x| if *x { "1" } else { "0" });
-        let t = t.map(|x| if *x { "1" } else { "0" });
+        let (steps_shown, set_steps_shown) = create_signal(false);
         let (class_name, style_val) = style_str! {
             tr {
                 border-top: 1px solid #333;
@@ -97,40 +181,281 @@ impl Solver for Exp2 {
             <Style> {style_val} </Style>
             <div class="mb-10">
                 <p class="font-bold mb-2"> "关系矩阵" </p>
-                <KaTeX expr={ format!(r"\begin{{bmatrix}} {} \end{{bmatrix}}", matrix) } />
+                <KaTeX expr={ matrix } />
             </div>
             <div class="mb-10">
-                <p class="font-bold mb-2"> "传递闭包的关系矩阵" </p>
-                <KaTeX expr={ format!(r"\begin{{bmatrix}} {} \end{{bmatrix}}", t) } />
+                <p class="font-bold mb-2"> "自反闭包的关系矩阵" </p>
+                <KaTeX expr={ reflexive_closure } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "对称闭包的关系矩阵" </p>
+                <KaTeX expr={ symmetric_closure } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2">
+                    "传递闭包的关系矩阵"
+                    <label class="ml-4 font-normal">
+                        <input
+                            type="checkbox"
+                            class="mr-1"
+                            on:change=move |ev| set_steps_shown(event_target_checked(&ev))
+                        />
+                        "显示步骤"
+                    </label>
+                </p>
+                <Show
+                    when=steps_shown
+                    fallback=move || view! { <KaTeX expr={ transitive_closure.clone() } /> }
+                > {
+                    transitive_closure_steps.iter().enumerate().map(|(k, step)| view! {
+                        <p class="mt-2"> { format!("经过顶点 {}", k + 1) } </p>
+                        <KaTeX expr={ step.clone() } />
+                    }).collect_vec()
+                } </Show>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "自反传递闭包的关系矩阵" </p>
+                <KaTeX expr={ reflexive_transitive_closure } />
             </div>
             <div class="mb-10">
                 <p class="font-bold mb-2"> "关系性质" </p>
-                <table>
-                    <tbody>
-                        <tr>
-                            <td> "自反性" </td>
-                            <td> { if reflexive { "是" } else { "否" } } </td>
-                        </tr>
-                        <tr>
-                            <td> "反自反性" </td>
-                            <td> { if irreflexive { "是" } else { "否" } } </td>
-                        </tr>
-                        <tr>
-                            <td> "对称性" </td>
-                            <td> { if symmetric { "是" } else { "否" } } </td>
-                        </tr>
-                        <tr>
-                            <td> "反对称性" </td>
-                            <td> { if antisymmetric { "是" } else { "否" } } </td>
-                        </tr>
-                        <tr>
-                            <td> "传递性" </td>
-                            <td> { if transitive { "是" } else { "否" } } </td>
-                        </tr>
-                    </tbody>
-                </table>
+                { properties_view(class_name, &properties) }
             </div>
+            {
+                equivalence_classes.map(|classes| view! {
+                    <div class="mb-10">
+                        <p class="font-bold mb-2"> "等价类" </p>
+                        <KaTeX expr={ classes } />
+                    </div>
+                })
+            }
+            {
+                composition.map(|(composition, properties)| {
+                    view! {
+                        class = class_name,
+                        <div class="mb-10">
+                            <p class="font-bold mb-2"> "复合关系" </p>
+                            <KaTeX expr={ composition } />
+                        </div>
+                        <div class="mb-10">
+                            <p class="font-bold mb-2"> "复合关系的性质" </p>
+                            { properties_view(class_name, &properties) }
+                        </div>
+                    }
+                })
+            }
         }
         .into_view()
     }
 }
+
+/// Answer of [`Exp2`] in TeX.
+#[derive(Debug, Clone, PartialEq)]
+struct RelationTex {
+    matrix: String,
+    reflexive_closure: String,
+    symmetric_closure: String,
+    transitive_closure: String,
+    /// The matrix after passing through each vertex in Warshall's algorithm.
+    transitive_closure_steps: Vec<String>,
+    reflexive_transitive_closure: String,
+    properties: RelationProperties,
+    /// `None` if the relation is not an equivalence relation.
+    equivalence_classes: Option<String>,
+    /// `M_{R ∘ S} = ...` and the properties of the composition, if a second matrix is given.
+    composition: Option<(String, RelationProperties)>,
+}
+
+fn relation_matrix_to_tex(matrix: &Matrix<bool>) -> String {
+    format!(
+        r"\begin{{bmatrix}} {} \end{{bmatrix}}",
+        matrix.map(|x| if *x { "1" } else { "0" })
+    )
+}
+
+/// Parse one or two relation matrices separated by a blank line and answer, the error is a
+/// message to show.
+fn relation_tex(input: &str) -> Result<RelationTex, String> {
+    // each block with the number of lines before it
+    let mut blocks: Vec<(usize, Vec<&str>)> = Vec::new();
+    let mut in_block = false;
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            in_block = false;
+        } else if in_block {
+            blocks.last_mut().unwrap().1.push(line);
+        } else {
+            blocks.push((i, vec![line]));
+            in_block = true;
+        }
+    }
+    let blocks = blocks
+        .into_iter()
+        .map(|(offset, lines)| (offset, lines.join("\n")))
+        .collect_vec();
+    let (matrix, other) = match &blocks[..] {
+        [] => ((0, ""), None),
+        [(offset, matrix)] => ((*offset, matrix.as_str()), None),
+        [(offset, matrix), (other_offset, other)] => (
+            (*offset, matrix.as_str()),
+            Some((*other_offset, other.as_str())),
+        ),
+        _ => return Err("至多输入两个矩阵.".to_string()),
+    };
+    let matrix = parse_relation(matrix.1, matrix.0)?;
+    let other = match other
+        .map(|(offset, other)| parse_relation(other, offset))
+        .transpose()?
+    {
+        Some(other) if other.shape() != matrix.shape() => {
+            return Err("两个矩阵大小不同.".to_string())
+        }
+        other => other,
+    };
+    let properties = relation_properties(&matrix);
+    let t_steps = transitive_closure_steps(&matrix);
+    let t /* 传递闭包 */ = t_steps.last().cloned().unwrap_or_else(|| matrix.clone());
+    let equivalence_classes = properties.equivalence.then(|| {
+        equivalence_classes(&matrix)
+            .into_iter()
+            .map(|class| {
+                format!(
+                    r"[{}] = \{{{}\}}",
+                    class[0] + 1,
+                    class.iter().map(|i| i + 1).join(", ")
+                )
+            })
+            .join(r",\quad ")
+    });
+    let composition = other.map(|other| {
+        let composition = matrix.bool_product(&other).unwrap();
+        (
+            format!(
+                r"\bm M_{{R \circ S}} = \bm M_R \cdot \bm M_S = {} \cdot {} = {}",
+                relation_matrix_to_tex(&matrix),
+                relation_matrix_to_tex(&other),
+                relation_matrix_to_tex(&composition)
+            ),
+            relation_properties(&composition),
+        )
+    });
+    Ok(RelationTex {
+        matrix: relation_matrix_to_tex(&matrix),
+        reflexive_closure: relation_matrix_to_tex(&reflexive_closure(&matrix)),
+        symmetric_closure: relation_matrix_to_tex(&symmetric_closure(&matrix)),
+        transitive_closure: relation_matrix_to_tex(&t),
+        transitive_closure_steps: t_steps.iter().map(relation_matrix_to_tex).collect_vec(),
+        reflexive_transitive_closure: relation_matrix_to_tex(&reflexive_closure(&t)),
+        properties,
+        equivalence_classes,
+        composition,
+    })
+}
+
+#[test]
+fn test_relation_tex() {
+    let answer = relation_tex("1 1 0\n1 1 0\n0 0 1").unwrap();
+    assert_eq!(
+        answer.matrix,
+        r"\begin{bmatrix} 1 & 1 & 0 \\[1ex] 1 & 1 & 0 \\[1ex] 0 & 0 & 1 \end{bmatrix}"
+    );
+    assert_eq!(
+        answer.equivalence_classes.as_deref(),
+        Some(r"[1] = \{1, 2\},\quad [3] = \{3\}")
+    );
+    assert_eq!(answer.composition, None);
+    assert!(answer.properties.equivalence && !answer.properties.partial_order);
+    let answer = relation_tex("0 1\n0 0\n\n0 0\n1 0").unwrap();
+    assert_eq!(answer.equivalence_classes, None);
+    assert!(answer
+        .composition
+        .unwrap()
+        .0
+        .ends_with(r"= \begin{bmatrix} 1 & 0 \\[1ex] 0 & 0 \end{bmatrix}"));
+    assert_eq!(
+        relation_tex("1 0\n0 1\n\n1 0\n0"),
+        Err("矩阵第 5 行有 1 个元素, 应为 2 个".to_string())
+    );
+    assert_eq!(
+        relation_tex("1 0 1\n0 1 0"),
+        Err("矩阵不是方阵.".to_string())
+    );
+}
+
+/// Square relation matrix with nonzero entries as related, lines in errors are moved down by
+/// `line_offset` unless in bracket notation.
+fn parse_relation(s: &str, line_offset: usize) -> Result<Matrix<bool>, String> {
+    let line_offset = if s.contains('[') { 0 } else { line_offset };
+    let matrix = match s.parse::<Matrix<BigInt>>() {
+        Ok(matrix) => matrix,
+        Err(MatrixParseError::Empty) => return Err("输入为空.".to_string()),
+        Err(e) => return Err(e.offset_line(line_offset).to_string()),
+    };
+    let (m, n) = matrix.shape();
+    if m != n {
+        return Err("矩阵不是方阵.".to_string());
+    }
+    Ok(matrix.map(|x| !x.is_zero()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct RelationProperties {
+    reflexive: bool,
+    irreflexive: bool,
+    symmetric: bool,
+    antisymmetric: bool,
+    transitive: bool,
+    equivalence: bool,
+    partial_order: bool,
+}
+
+impl RelationProperties {
+    /// Properties with their names in display order.
+    fn named(&self) -> [(&'static str, bool); 7] {
+        [
+            ("自反性", self.reflexive),
+            ("反自反性", self.irreflexive),
+            ("对称性", self.symmetric),
+            ("反对称性", self.antisymmetric),
+            ("传递性", self.transitive),
+            ("等价关系", self.equivalence),
+            ("偏序关系", self.partial_order),
+        ]
+    }
+}
+
+fn relation_properties(matrix: &Matrix<bool>) -> RelationProperties {
+    let m = matrix.len();
+    let reflexive /* 自反性 */ = (0..m).all(|i| matrix[i][i]);
+    let irreflexive /* 反自反性 */ = (0..m).all(|i| !matrix[i][i]);
+    let symmetric /* 对称性 */ = (0..m).flat_map(|i| (0..i).map(|j| (i, j)).collect_vec()).all(|(i, j)| matrix[i][j] == matrix[j][i]);
+    let antisymmetric /* 反对称性 */ = (0..m).flat_map(|i| (0..i).map(|j| (i, j)).collect_vec()).all(|(i, j)| !matrix[i][j] || !matrix[j][i]);
+    let transitive /* 传递性 */ = *matrix == transitive_closure(matrix);
+    RelationProperties {
+        reflexive,
+        irreflexive,
+        symmetric,
+        antisymmetric,
+        transitive,
+        equivalence: reflexive && symmetric && transitive,
+        partial_order: reflexive && antisymmetric && transitive,
+    }
+}
+
+fn properties_view(class_name: &'static str, properties: &RelationProperties) -> View {
+    view! {
+        class = class_name,
+        <table>
+            <tbody> {
+                properties.named().into_iter().map(|(name, value)| view! {
+                    class = class_name,
+                    <tr>
+                        <td> { name } </td>
+                        <td> { if value { "是" } else { "否" } } </td>
+                    </tr>
+                }).collect_vec()
+            } </tbody>
+        </table>
+    }
+    .into_view()
+}
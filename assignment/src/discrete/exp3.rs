@@ -8,7 +8,7 @@ use num::{BigInt, Zero};
 use shiyanyi::*;
 use stylers::style_str;
 
-use crate::common::Matrix;
+use crate::common::{Matrix, MatrixParseError};
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct Exp3;
@@ -43,6 +43,321 @@ fn greatest_lower_bound(matrix: &Matrix<bool>, a: usize, b: usize) -> Option<usi
     bound
 }
 
+/// Kahn 算法 ignoring the diagonal, taking the smallest available element first. A cycle
+/// (listed from its smallest element without returning to it) if the relation is not acyclic.
+fn topological_sort(matrix: &Matrix<bool>) -> Result<Vec<usize>, Vec<usize>> {
+    let m = matrix.shape().0;
+    let mut in_degree = (0..m)
+        .map(|j| (0..m).filter(|&i| i != j && matrix[i][j]).count())
+        .collect_vec();
+    let mut order = Vec::new();
+    let mut removed = vec![false; m];
+    while let Some(current) = (0..m).find(|&i| !removed[i] && in_degree[i] == 0) {
+        removed[current] = true;
+        order.push(current);
+        for j in (0..m).filter(|&j| j != current && matrix[current][j]) {
+            in_degree[j] -= 1;
+        }
+    }
+    let Some(start) = (0..m).find(|&i| !removed[i]) else {
+        return Ok(order);
+    };
+    // every remaining element has a remaining predecessor, so walking backwards revisits one
+    let mut walk = vec![start];
+    loop {
+        let current = *walk.last().unwrap();
+        let previous = (0..m)
+            .find(|&i| i != current && !removed[i] && matrix[i][current])
+            .unwrap();
+        if let Some(k) = walk.iter().position(|&i| i == previous) {
+            let mut cycle = walk.split_off(k);
+            cycle.reverse();
+            let smallest = cycle.iter().position_min().unwrap();
+            cycle.rotate_left(smallest);
+            return Err(cycle);
+        }
+        walk.push(previous);
+    }
+}
+
+#[test]
+fn test_topological_sort() {
+    // divisibility on 1 2 3 4 6 12
+    let divides = [1, 2, 3, 4, 6, 12];
+    let matrix = Matrix(
+        divides
+            .iter()
+            .map(|a| divides.iter().map(|b| b % a == 0).collect_vec())
+            .collect_vec(),
+    );
+    let order = topological_sort(&matrix).unwrap();
+    assert_eq!(order, [0, 1, 2, 3, 4, 5]);
+    for (i, &a) in order.iter().enumerate() {
+        for &b in &order[..i] {
+            assert!(!matrix[a][b]);
+        }
+    }
+    // 0 -> 1 -> 2 -> 0
+    let mut matrix = Matrix(vec![vec![false; 4]; 4]);
+    for (a, b) in [(0, 1), (1, 2), (2, 0), (3, 0)] {
+        matrix[a][b] = true;
+    }
+    assert_eq!(topological_sort(&matrix), Err(vec![0, 1, 2]));
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Level of each element, the length of the longest chain from a minimal element. Relaxed at
+/// most `m` rounds so that a cyclic relation still terminates.
+fn levels(m: usize, covering: &[(usize, usize)]) -> Vec<usize> {
+    let mut levels = vec![0; m];
+    for _ in 0..m {
+        let mut changed = false;
+        for &(i, j) in covering {
+            if levels[j] < levels[i] + 1 {
+                levels[j] = levels[i] + 1;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    levels
+}
+
+/// Elements of each connected component of the covering relation, in order of their smallest
+/// element.
+fn components(m: usize, covering: &[(usize, usize)]) -> Vec<Vec<usize>> {
+    let mut component = (0..m).collect_vec();
+    fn find(component: &mut [usize], i: usize) -> usize {
+        if component[i] != i {
+            component[i] = find(component, component[i]);
+        }
+        component[i]
+    }
+    for &(i, j) in covering {
+        let (i, j) = (find(&mut component, i), find(&mut component, j));
+        component[i.max(j)] = i.min(j);
+    }
+    (0..m)
+        .into_group_map_by(|&i| find(&mut component, i))
+        .into_iter()
+        .sorted()
+        .map(|(_, elements)| elements)
+        .collect_vec()
+}
+
+#[test]
+fn test_hasse_layout() {
+    // 1 < 2 < 4, 1 < 3 and an isolated 5
+    let covering = [(0, 1), (0, 2), (1, 3)];
+    assert_eq!(levels(5, &covering), [0, 1, 1, 2, 0]);
+    assert_eq!(components(5, &covering), [vec![0, 1, 2, 3], vec![4]]);
+}
+
+/// SVG of the Hasse diagram, connected components laid out separately from left to right.
+fn hasse_diagram(set: &[&str], covering: &[(usize, usize)]) -> String {
+    const SPACING: usize = 60;
+    let levels = levels(set.len(), covering);
+    let height = levels.iter().max().map_or(0, |level| level + 1);
+    let mut position = vec![(0, 0); set.len()];
+    let mut offset = 0;
+    for component in components(set.len(), covering) {
+        let by_level = component.into_iter().into_group_map_by(|&i| levels[i]);
+        let width = by_level.values().map(Vec::len).max().unwrap();
+        for (level, elements) in by_level {
+            // center each level within the component
+            let start = offset * SPACING + (width - elements.len()) * SPACING / 2;
+            for (k, i) in elements.into_iter().enumerate() {
+                position[i] = (
+                    start + k * SPACING + SPACING / 2,
+                    (height - 1 - level) * SPACING + SPACING / 2,
+                );
+            }
+        }
+        offset += width;
+    }
+    let lines = covering
+        .iter()
+        .map(|&(i, j)| {
+            let ((x1, y1), (x2, y2)) = (position[i], position[j]);
+            format!(r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="black" />"#)
+        })
+        .join("");
+    let vertices = position
+        .iter()
+        .zip_eq(set)
+        .map(|(&(x, y), label)| {
+            format!(
+                r#"<circle cx="{x}" cy="{y}" r="4" fill="black" /><text x="{}" y="{}" font-size="14">{}</text>"#,
+                x + 8,
+                y + 5,
+                escape_xml(label)
+            )
+        })
+        .join("");
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}">{lines}{vertices}</svg>"#,
+        offset * SPACING + SPACING / 2,
+        height * SPACING
+    )
+}
+
+/// Answer of [`Exp3`], the matrix in TeX and the rest in plain text.
+#[derive(Debug, Clone, PartialEq)]
+struct LatticeAnswer {
+    matrix: String,
+    covering: String,
+    /// SVG of the Hasse diagram.
+    hasse_diagram: String,
+    /// The elements in topological order, or the cycle found.
+    topological_order: String,
+    is_lattice: bool,
+    complemented: bool,
+    /// The atoms and the complements of each element, `None` if not a lattice.
+    atoms_and_complements: Option<(String, Vec<(String, String)>)>,
+}
+
+/// Parse the element set and the relation matrix and answer, the error is a message to show.
+fn lattice_answer(input: &str) -> Result<LatticeAnswer, String> {
+    let (set, matrix) = match input.split_once('\n') {
+        Some(x) => x,
+        None => return Err("无法解析输入.".to_string()),
+    };
+    let set = set.split_whitespace().collect_vec();
+    let matrix = match matrix.parse::<Matrix<BigInt>>() {
+        Ok(x) => x,
+        Err(MatrixParseError::Empty) => return Err("输入为空.".to_string()),
+        Err(e) => return Err(e.to_string()),
+    };
+    let (m, n) = matrix.shape();
+    if m != n {
+        return Err("矩阵不是方阵.".to_string());
+    }
+    if m != set.len() {
+        return Err("元素集合与关系矩阵不符.".to_string());
+    }
+    let matrix = matrix.map(|x| !x.is_zero());
+    let mut covering /* 盖住关系 */ = Vec::new();
+    for i in 0..m {
+        for j in 0..m {
+            if i != j
+                && matrix[i][j]
+                && !(0..m).any(|k| k != i && k != j && matrix[i][k] && matrix[k][j])
+            {
+                covering.push((i, j));
+            }
+        }
+    }
+    let mut map_bound: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
+    let mut is_lattice = true;
+    for i in 0..m {
+        for j in 0..m {
+            let upper = least_upper_bound(&matrix, i, j);
+            let lower = greatest_lower_bound(&matrix, i, j);
+            if upper.is_none() || lower.is_none() {
+                is_lattice = false;
+                break;
+            }
+            map_bound.insert((i, j), (upper.unwrap(), lower.unwrap()));
+        }
+    }
+    // 原子和各元素的补元
+    let atoms_and_complements = if is_lattice {
+        let maximum /* 最大元 */ = (0..m).rfind(|&i| (0..m).all(|j| matrix[j][i])).unwrap();
+        let minimum /* 最小元 */ = (0..m).rfind(|&i| (0..m).all(|j| matrix[i][j])).unwrap();
+        let atoms = covering
+            .iter()
+            .filter(|(i, _)| *i == minimum)
+            .map(|(_, j)| *j)
+            .collect_vec();
+        let complements = (0..m)
+            .map(|i| {
+                (0..m)
+                    .filter(|&j| map_bound[&(i, j)] == (maximum, minimum))
+                    .collect_vec()
+            })
+            .collect_vec();
+        Some((atoms, complements))
+    } else {
+        None
+    };
+    let complemented /* 有补格 */ = atoms_and_complements
+        .as_ref()
+        .is_some_and(|(_, complements)| complements.iter().all(|c| !c.is_empty()));
+    let labels = |elements: &[usize]| {
+        if elements.is_empty() {
+            "无".to_string()
+        } else {
+            elements.iter().map(|i| set[*i]).join(", ")
+        }
+    };
+    Ok(LatticeAnswer {
+        matrix: format!(
+            r"\begin{{bmatrix}} {} \end{{bmatrix}}",
+            matrix.map(|x| if *x { "1" } else { "0" })
+        ),
+        covering: covering
+            .iter()
+            .map(|(i, j)| format!("<{}, {}>", set[*i], set[*j]))
+            .join(", "),
+        hasse_diagram: hasse_diagram(&set, &covering),
+        topological_order: match topological_sort(&matrix) {
+            Ok(order) => order.iter().map(|i| set[*i]).join(", "),
+            Err(cycle) => format!(
+                "存在环 {}, 不是偏序关系.",
+                cycle
+                    .iter()
+                    .chain(cycle.first())
+                    .map(|i| set[*i])
+                    .join(" → ")
+            ),
+        },
+        is_lattice,
+        complemented,
+        atoms_and_complements: atoms_and_complements.map(|(atoms, complements)| {
+            (
+                labels(&atoms),
+                complements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, c)| (set[i].to_string(), labels(c)))
+                    .collect_vec(),
+            )
+        }),
+    })
+}
+
+#[test]
+fn test_lattice_answer() {
+    let answer = lattice_answer("a b c\n\n1 1 1\n0 1 1\n0 0 1").unwrap();
+    assert_eq!(answer.covering, "<a, b>, <b, c>");
+    assert_eq!(answer.topological_order, "a, b, c");
+    assert!(answer.is_lattice);
+    assert!(!answer.complemented);
+    assert_eq!(
+        answer.atoms_and_complements,
+        Some((
+            "b".to_string(),
+            vec![
+                ("a".to_string(), "c".to_string()),
+                ("b".to_string(), "无".to_string()),
+                ("c".to_string(), "a".to_string()),
+            ]
+        ))
+    );
+    assert_eq!(
+        lattice_answer("a b\n\n1 1\n0 1\n0 0"),
+        Err("矩阵不是方阵.".to_string())
+    );
+}
+
 impl Solver for Exp3 {
     fn id(&self) -> String {
         "exp3".to_string()
@@ -71,67 +386,18 @@ impl Solver for Exp3 {
     }
 
     fn solve(&self, input: String) -> View {
-        let (set, matrix) = match input.split_once('\n') {
-            Some(x) => x,
-            None => return "Failed to parse.".into_view(),
-        };
-        let set = set.split_whitespace().collect_vec();
-        let matrix = match matrix.parse::<Matrix<BigInt>>() {
-            Ok(x) => x,
-            Err(_) => return "Failed to parse.".into_view(),
+        let LatticeAnswer {
+            matrix,
+            covering,
+            hasse_diagram,
+            topological_order,
+            is_lattice,
+            complemented,
+            atoms_and_complements,
+        } = match lattice_answer(&input) {
+            Ok(answer) => answer,
+            Err(e) => return self.render_error(&e),
         };
-        let (m, n) = matrix.shape();
-        if m != n {
-            return "Matrix is not square.".into_view();
-        }
-        if m != set.len() {
-            return "Incorrect element set.".into_view();
-        }
-        let matrix = matrix.map(|x| !x.is_zero());
-        let mut covering /* 盖住关系 */ = Vec::new();
-        for i in 0..m {
-            for j in 0..m {
-                if i != j
-                    && matrix[i][j]
-                    && !(0..m).any(|k| k != i && k != j && matrix[i][k] && matrix[k][j])
-                {
-                    covering.push((i, j));
-                }
-            }
-        }
-        let mut map_bound: HashMap<(usize, usize), (usize, usize)> = HashMap::new();
-        let mut is_lattice = true;
-        for i in 0..m {
-            for j in 0..m {
-                let upper = least_upper_bound(&matrix, i, j);
-                let lower = greatest_lower_bound(&matrix, i, j);
-                if upper.is_none() || lower.is_none() {
-                    is_lattice = false;
-                    break;
-                }
-                map_bound.insert((i, j), (upper.unwrap(), lower.unwrap()));
-            }
-        }
-        let complemented /* 有补格 */  = if is_lattice {
-            let mut maximum  /* 最大元 */= None;
-            for i in 0..m {
-                if (0..m).all(|j| matrix[j][i]) {
-                    maximum = Some(i)
-                }
-            }
-            let maximum = maximum.unwrap();
-            let mut minimum  /* 最小元 */= None;
-            for i in 0..m {
-                if (0..m).all(|j| matrix[i][j]) {
-                    minimum = Some(i)
-                }
-            }
-            let minimum = minimum.unwrap();
-            (0..m).all(|i| (0..m).any(|j| map_bound[&(i, j)] == (maximum, minimum)))
-        } else {
-            false
-        };
-        let matrix = matrix.map(|x| if *x { "1" } else { "0" });
         let (class_name, style_val) = style_str! {
             tr {
                 border-top: 1px solid #333;
@@ -152,32 +418,67 @@ impl Solver for Exp3 {
             }
         };
         view! {
-                class = class_name,
-                <Style> {style_val} </Style>
-                <div class="mb-10">
-                    <p class="font-bold mb-2"> "关系矩阵" </p>
-                    <KaTeX expr={ format!(r"\begin{{bmatrix}} {} \end{{bmatrix}}", matrix) } />
-                </div>
-                <div class="mb-10">
-                    <p class="font-bold mb-2"> "盖住关系" </p>
-                    <p> { covering.iter().map(|(i, j)| format!("<{}, {}>", set[*i], set[*j])).join(", ") } </p>
-                </div>
-                <div class="mb-10">
-                    <p class="font-bold mb-2"> "格的判定" </p>
-                    <table>
-                        <tbody>
-                            <tr>
-                                <td> "格" </td>
-                                <td> { if is_lattice { "是" } else { "否" } } </td>
-                            </tr>
-                            <tr>
-                                <td> "有补格" </td>
-                                <td> { if complemented { "是" } else { "否" } } </td>
-                            </tr>
-                        </tbody>
-                    </table>
-                </div>
+            class = class_name,
+            <Style> {style_val} </Style>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "关系矩阵" </p>
+                <KaTeX expr={ matrix } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "盖住关系" </p>
+                <p> { covering } </p>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "哈斯图" </p>
+                <div inner_html={ hasse_diagram }></div>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "拓扑排序" </p>
+                <p> { topological_order } </p>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "格的判定" </p>
+                <table>
+                    <tbody>
+                        <tr>
+                            <td> "格" </td>
+                            <td> { if is_lattice { "是" } else { "否" } } </td>
+                        </tr>
+                        <tr>
+                            <td> "有补格" </td>
+                            <td> { if complemented { "是" } else { "否" } } </td>
+                        </tr>
+                    </tbody>
+                </table>
+            </div>
+            {
+                atoms_and_complements.map(|(atoms, complements)| {
+                    view! {
+                        class = class_name,
+                        <div class="mb-10">
+                            <p class="font-bold mb-2"> "原子与补元" </p>
+                            <table>
+                                <tbody>
+                                    <tr>
+                                        <td> "原子" </td>
+                                        <td> { atoms } </td>
+                                    </tr>
+                                    {
+                                        complements.into_iter().map(|(element, c)| view! {
+                                            class = class_name,
+                                            <tr>
+                                                <td> { format!("{element} 的补元") } </td>
+                                                <td> { c } </td>
+                                            </tr>
+                                        }).collect_vec()
+                                    }
+                                </tbody>
+                            </table>
+                        </div>
+                    }
+                })
             }
-            .into_view()
+        }
+        .into_view()
     }
 }
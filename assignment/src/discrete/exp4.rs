@@ -1,3 +1,8 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, VecDeque},
+};
+
 use itertools::{repeat_n, Itertools};
 use leptos::*;
 use leptos_meta::Style;
@@ -40,112 +45,914 @@ fn connected_component_count(matrix: &Matrix<bool>) -> usize {
     count
 }
 
-impl Solver for Exp4 {
-    fn id(&self) -> String {
-        "exp4".to_string()
+/// How the edges of a random graph are generated.
+#[derive(Debug, Clone, PartialEq)]
+enum GraphDistribution {
+    /// Exactly this many distinct edges chosen uniformly
+    EdgeCount(usize),
+    /// Each possible edge independently with this probability (Erdős–Rényi)
+    Probability(f64),
+    /// Graph with this degree sequence, undirected only
+    DegreeSequence(Vec<usize>),
+}
+
+/// Parses `[directed] <vertices> (<edges> | gnp <p> | degree <d_0> ... <d_{n-1}>) [seed]`.
+fn parse_random_header(
+    header: &str,
+) -> Result<(bool, usize, GraphDistribution, Option<u64>), &'static str> {
+    let mut header = header.split_whitespace().peekable();
+    let directed = header.next_if_eq(&"directed").is_some();
+    let vertex_count = header
+        .next()
+        .and_then(|s| s.parse::<usize>().ok())
+        .ok_or("无法解析输入.")?;
+    let max_edge_count =
+        vertex_count * vertex_count.saturating_sub(1) / if directed { 1 } else { 2 };
+    let distribution = match header.next() {
+        Some("gnp") => {
+            let p = header
+                .next()
+                .and_then(|s| s.parse::<f64>().ok())
+                .ok_or("无法解析输入.")?;
+            if !(0.0..=1.0).contains(&p) {
+                return Err("概率应在 0 和 1 之间.");
+            }
+            GraphDistribution::Probability(p)
+        }
+        Some("degree") => {
+            if directed {
+                return Err("度数序列仅支持无向图.");
+            }
+            let degrees = header
+                .by_ref()
+                .take(vertex_count)
+                .map(|s| s.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| "无法解析输入.")?;
+            if degrees.len() != vertex_count {
+                return Err("度数序列应为每个节点给出一个度数.");
+            }
+            if havel_hakimi(&degrees).is_none() {
+                return Err("度数序列不可图化.");
+            }
+            GraphDistribution::DegreeSequence(degrees)
+        }
+        Some(s) => {
+            let edge_count = s.parse::<usize>().map_err(|_| "无法解析输入.")?;
+            if edge_count > max_edge_count {
+                return Err("边数过多.");
+            }
+            GraphDistribution::EdgeCount(edge_count)
+        }
+        None => return Err("无法解析输入."),
+    };
+    let seed = match header.next() {
+        Some(s) => Some(s.parse::<u64>().map_err(|_| "无法解析输入.")?),
+        None => None,
+    };
+    if header.next().is_some() {
+        return Err("无法解析输入.");
     }
+    Ok((directed, vertex_count, distribution, seed))
+}
 
-    fn title(&self) -> String {
-        "图的随机生成及欧拉（回）路的确定".to_string()
+/// Inverse of [`parse_random_header`] with the seed filled in.
+fn random_header(
+    directed: bool,
+    vertex_count: usize,
+    distribution: &GraphDistribution,
+    seed: u64,
+) -> String {
+    let distribution = match distribution {
+        GraphDistribution::EdgeCount(edge_count) => edge_count.to_string(),
+        GraphDistribution::Probability(p) => format!("gnp {p}"),
+        GraphDistribution::DegreeSequence(degrees) => {
+            format!("degree {}", degrees.iter().join(" "))
+        }
+    };
+    let directed = if directed { "directed " } else { "" };
+    format!("{directed}{vertex_count} {distribution} {seed}")
+}
+
+fn random_graph(
+    vertex_count: usize,
+    distribution: &GraphDistribution,
+    directed: bool,
+    seed: u64,
+) -> Matrix<bool> {
+    let mut rng = ChaCha12Rng::seed_from_u64(seed);
+    match distribution {
+        GraphDistribution::EdgeCount(edge_count) => {
+            let max_edge_count =
+                vertex_count * vertex_count.saturating_sub(1) / if directed { 1 } else { 2 };
+            // near the complete graph, remove the few non-edges instead to keep rejection cheap
+            let dense = *edge_count > max_edge_count / 2;
+            let toggled_count = if dense {
+                max_edge_count - edge_count
+            } else {
+                *edge_count
+            };
+            let mut matrix = Matrix::<bool>(
+                (0..vertex_count)
+                    .map(|i| (0..vertex_count).map(|j| dense && i != j).collect_vec())
+                    .collect_vec(),
+            );
+            for _ in 0..toggled_count {
+                loop {
+                    let a = rng.gen_range(0..vertex_count);
+                    let b = rng.gen_range(0..vertex_count);
+                    if a != b && matrix[a][b] == dense {
+                        matrix[a][b] = !dense;
+                        if !directed {
+                            matrix[b][a] = !dense;
+                        }
+                        break;
+                    }
+                }
+            }
+            matrix
+        }
+        GraphDistribution::Probability(p) => {
+            let mut matrix = Matrix::<bool>(
+                repeat_n(repeat_n(false, vertex_count).collect_vec(), vertex_count).collect_vec(),
+            );
+            for a in 0..vertex_count {
+                for b in 0..vertex_count {
+                    if a != b && (directed || a < b) && rng.gen_bool(*p) {
+                        matrix[a][b] = true;
+                        if !directed {
+                            matrix[b][a] = true;
+                        }
+                    }
+                }
+            }
+            matrix
+        }
+        GraphDistribution::DegreeSequence(degrees) => {
+            let mut matrix = havel_hakimi(degrees).expect("degree sequence is not graphical");
+            // degree-preserving double edge swaps a - b, c - d => a - d, c - b
+            let mut edges = (0..vertex_count)
+                .flat_map(|a| (a + 1..vertex_count).map(move |b| (a, b)))
+                .filter(|&(a, b)| matrix[a][b])
+                .collect_vec();
+            if edges.len() >= 2 {
+                for _ in 0..edges.len() * 10 {
+                    let i = rng.gen_range(0..edges.len());
+                    let j = rng.gen_range(0..edges.len());
+                    let ((a, b), (mut c, mut d)) = (edges[i], edges[j]);
+                    if rng.gen_bool(0.5) {
+                        (c, d) = (d, c);
+                    }
+                    if [a, b].contains(&c) || [a, b].contains(&d) || matrix[a][d] || matrix[c][b] {
+                        continue;
+                    }
+                    for (x, y, value) in [(a, b, false), (c, d, false), (a, d, true), (c, b, true)]
+                    {
+                        matrix[x][y] = value;
+                        matrix[y][x] = value;
+                    }
+                    edges[i] = (a, d);
+                    edges[j] = (c, b);
+                }
+            }
+            matrix
+        }
     }
+}
 
-    fn description(&self) -> View {
-        "输入节点数, 边数和可选的随机种.".into_view()
+/// Havel–Hakimi 算法, `None` if the degree sequence is not graphical.
+fn havel_hakimi(degrees: &[usize]) -> Option<Matrix<bool>> {
+    let n = degrees.len();
+    let mut matrix = Matrix::<bool>(repeat_n(repeat_n(false, n).collect_vec(), n).collect_vec());
+    let mut remaining = degrees.to_vec();
+    loop {
+        let mut order = (0..n).collect_vec();
+        order.sort_by_key(|&i| std::cmp::Reverse(remaining[i]));
+        let Some(&current) = order.first() else {
+            return Some(matrix);
+        };
+        let d = remaining[current];
+        if d == 0 {
+            return Some(matrix);
+        }
+        let neighbors = &order[1..];
+        if neighbors.len() < d || remaining[neighbors[d - 1]] == 0 {
+            return None;
+        }
+        remaining[current] = 0;
+        for &i in &neighbors[..d] {
+            remaining[i] -= 1;
+            matrix[current][i] = true;
+            matrix[i][current] = true;
+        }
     }
+}
 
-    fn default_input(&self) -> String {
-        "10 20 1152921504606847241".to_string()
+#[test]
+fn test_random_graph_determinism() {
+    let distribution = GraphDistribution::EdgeCount(5);
+    let matrix = random_graph(5, &distribution, false, 1152921504606847241);
+    assert_eq!(
+        matrix,
+        random_graph(5, &distribution, false, 1152921504606847241)
+    );
+    let edges = (0..5)
+        .flat_map(|a| (a + 1..5).map(move |b| (a, b)))
+        .filter(|&(a, b)| matrix[a][b])
+        .collect_vec();
+    // pinned so that printed parameters keep reproducing graphs generated by earlier versions
+    assert_eq!(edges, [(0, 1), (0, 3), (1, 3), (2, 3), (2, 4)]);
+}
+
+#[test]
+fn test_random_graph() {
+    let edge_count = |matrix: &Matrix<bool>| matrix.iter().flatten().filter(|x| **x).count();
+    let dense = random_graph(10, &GraphDistribution::EdgeCount(44), false, 1);
+    assert_eq!(edge_count(&dense), 88);
+    let dense = random_graph(10, &GraphDistribution::EdgeCount(89), true, 1);
+    assert_eq!(edge_count(&dense), 89);
+    let complete = random_graph(5, &GraphDistribution::Probability(1.0), false, 1);
+    assert_eq!(edge_count(&complete), 20);
+    let empty = random_graph(5, &GraphDistribution::Probability(0.0), true, 1);
+    assert_eq!(edge_count(&empty), 0);
+    let degrees = vec![3, 3, 2, 2, 2, 1, 1];
+    let matrix = random_graph(
+        7,
+        &GraphDistribution::DegreeSequence(degrees.clone()),
+        false,
+        1,
+    );
+    assert_eq!(
+        matrix
+            .iter()
+            .map(|row| row.iter().filter(|x| **x).count())
+            .collect_vec(),
+        degrees
+    );
+    assert!(havel_hakimi(&[3, 3, 1, 1]).is_none());
+    assert!(havel_hakimi(&[]).is_some());
+    assert_eq!(
+        parse_random_header("directed 4 gnp 0.5 7"),
+        Ok((true, 4, GraphDistribution::Probability(0.5), Some(7)))
+    );
+    assert_eq!(
+        parse_random_header("3 degree 1 1 0"),
+        Ok((
+            false,
+            3,
+            GraphDistribution::DegreeSequence(vec![1, 1, 0]),
+            None
+        ))
+    );
+    assert!(parse_random_header("3 4").is_err());
+    for header in ["directed 4 gnp 0.5 7", "3 degree 1 1 0 2", "10 20 3"] {
+        let (directed, vertex_count, distribution, seed) = parse_random_header(header).unwrap();
+        assert_eq!(
+            random_header(directed, vertex_count, &distribution, seed.unwrap()),
+            header
+        );
     }
+    assert!(parse_random_header("3 degree 1 1").is_err());
+}
 
-    fn solve(&self, input: String) -> View {
-        let mut input = input.split_whitespace();
-        let vertex_count = match input.next().and_then(|s| s.parse::<usize>().ok()) {
-            Some(v) => v,
-            None => return "Failed to parse.".into_view(),
-        };
-        let edge_count = match input.next().and_then(|s| s.parse::<usize>().ok()) {
-            Some(e) => e,
-            None => return "Failed to parse.".into_view(),
-        };
-        let seed = match input.next().and_then(|s| s.parse::<u64>().ok()) {
-            Some(s) => s,
-            None => random(),
+/// Adjacency matrix from lines of `a b` or weighted `a b w` edges with vertices numbered from
+/// 0, along with the weights if any edge is weighted, where unweighted edges weigh 1.
+fn parse_edges(
+    vertex_count: usize,
+    directed: bool,
+    lines: &[&str],
+) -> Result<(Matrix<bool>, Option<Matrix<u64>>), &'static str> {
+    let mut matrix = Matrix::<bool>(
+        repeat_n(repeat_n(false, vertex_count).collect_vec(), vertex_count).collect_vec(),
+    );
+    let mut weights = Matrix::<u64>(
+        repeat_n(repeat_n(0, vertex_count).collect_vec(), vertex_count).collect_vec(),
+    );
+    let mut weighted = false;
+    for line in lines {
+        let numbers = line
+            .split_whitespace()
+            .map(|s| s.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "无法解析输入.")?;
+        let (a, b, weight) = match numbers[..] {
+            [a, b] => (a, b, 1),
+            [a, b, w] => {
+                weighted = true;
+                (a, b, w as u64)
+            }
+            _ => return Err("无法解析输入."),
         };
-        if edge_count > vertex_count * (vertex_count - 1) / 2 {
-            return "Too many edges.".into_view();
+        if a >= vertex_count || b >= vertex_count {
+            return Err("节点编号超出范围.");
         }
-        let mut matrix = Matrix::<bool>(
-            repeat_n(repeat_n(false, vertex_count).collect_vec(), vertex_count).collect_vec(),
-        );
-        let mut rng = ChaCha12Rng::seed_from_u64(seed);
-        let mut degree = repeat_n(0usize, vertex_count).collect_vec();
-        for _ in 0..edge_count {
-            loop {
-                let a = rng.gen_range(0..vertex_count);
-                let b = rng.gen_range(0..vertex_count);
-                if a != b && !matrix[a][b] {
-                    matrix[a][b] = true;
-                    matrix[b][a] = true;
-                    degree[a] += 1;
-                    degree[b] += 1;
-                    break;
+        if a == b {
+            return Err("不允许自环.");
+        }
+        if matrix[a][b] {
+            return Err("不允许重边.");
+        }
+        matrix[a][b] = true;
+        weights[a][b] = weight;
+        if !directed {
+            matrix[b][a] = true;
+            weights[b][a] = weight;
+        }
+    }
+    Ok((matrix, weighted.then_some(weights)))
+}
+
+/// Graph given by a random header or an edge list.
+pub(super) struct Graph {
+    pub(super) directed: bool,
+    pub(super) vertex_count: usize,
+    pub(super) matrix: Matrix<bool>,
+    pub(super) weights: Option<Matrix<u64>>,
+    /// Seed of a random graph, whether it was generated and the header reproducing the graph
+    pub(super) seed: Option<(u64, bool, String)>,
+}
+
+/// Parses a random header alone, or a header of the vertex count followed by edges.
+pub(super) fn parse_graph(lines: &[&str]) -> Result<Graph, &'static str> {
+    let header = lines.first().copied().unwrap_or("");
+    let edges = lines.get(1..).unwrap_or_default();
+    if edges.is_empty() {
+        let (directed, vertex_count, distribution, seed) = parse_random_header(header)?;
+        let generated = seed.is_none();
+        let seed = seed.unwrap_or_else(random);
+        return Ok(Graph {
+            directed,
+            vertex_count,
+            matrix: random_graph(vertex_count, &distribution, directed, seed),
+            weights: None,
+            seed: Some((
+                seed,
+                generated,
+                random_header(directed, vertex_count, &distribution, seed),
+            )),
+        });
+    }
+    let mut header = header.split_whitespace().peekable();
+    let directed = header.next_if_eq(&"directed").is_some();
+    let vertex_count = header
+        .next()
+        .and_then(|s| s.parse::<usize>().ok())
+        .ok_or("无法解析输入.")?;
+    if header.next().is_some() {
+        return Err("无法解析输入.");
+    }
+    let (matrix, weights) = parse_edges(vertex_count, directed, edges)?;
+    Ok(Graph {
+        directed,
+        vertex_count,
+        matrix,
+        weights,
+        seed: None,
+    })
+}
+
+/// Splits off the optional last line `path <source> <target>`.
+fn split_path_query<'a>(lines: &[&'a str]) -> (Vec<&'a str>, Option<&'a str>) {
+    match lines.split_last() {
+        Some((last, rest)) if last.trim_start().starts_with("path") => (rest.to_vec(), Some(last)),
+        _ => (lines.to_vec(), None),
+    }
+}
+
+/// Parses `path <source> <target>` with both vertices below `vertex_count`.
+fn parse_path_query(line: &str, vertex_count: usize) -> Result<(usize, usize), &'static str> {
+    let (source, target) = match line
+        .split_whitespace()
+        .skip(1)
+        .map(|s| s.parse::<usize>())
+        .collect_tuple()
+    {
+        Some((Ok(source), Ok(target))) => (source, target),
+        _ => return Err("最短路查询无法解析."),
+    };
+    if source >= vertex_count || target >= vertex_count {
+        return Err("最短路查询的节点编号超出范围.");
+    }
+    Ok((source, target))
+}
+
+/// Shortest path from `source` to `target` and its length, by BFS if unweighted or Dijkstra
+/// 算法 otherwise. `None` if unreachable.
+fn shortest_path(
+    matrix: &Matrix<bool>,
+    weights: Option<&Matrix<u64>>,
+    source: usize,
+    target: usize,
+) -> Option<(Vec<usize>, u64)> {
+    let n = matrix.shape().0;
+    let mut distance = repeat_n(None, n).collect_vec();
+    let mut previous = repeat_n(None, n).collect_vec();
+    distance[source] = Some(0u64);
+    match weights {
+        None => {
+            let mut queue = VecDeque::from([source]);
+            while let Some(current) = queue.pop_front() {
+                for next in (0..n).filter(|&next| matrix[current][next]) {
+                    if distance[next].is_none() {
+                        distance[next] = distance[current].map(|d| d + 1);
+                        previous[next] = Some(current);
+                        queue.push_back(next);
+                    }
                 }
             }
         }
-        let matrix = matrix;
-        let is_connected = connected_component_count(&matrix) == 1;
-        let (is_eulerian, is_semi_eulerian, path) = if is_connected {
-            let mut odd_degree_vertices = Vec::new();
-            for (i, d) in degree.iter().enumerate().take(vertex_count) {
-                if d.is_odd() {
-                    odd_degree_vertices.push(i)
+        Some(weights) => {
+            let mut heap = BinaryHeap::from([Reverse((0u64, source))]);
+            while let Some(Reverse((d, current))) = heap.pop() {
+                if distance[current].is_some_and(|best| d > best) {
+                    continue;
                 }
-            }
-            if odd_degree_vertices.len() > 2 {
-                (false, false, Vec::new())
-            } else {
-                let mut matrix1 = matrix.clone();
-                let mut path = Vec::new();
-                let mut current = if odd_degree_vertices.is_empty() {
-                    0usize
-                } else {
-                    odd_degree_vertices[0]
-                };
-                let mut previous_connected_component_count = connected_component_count(&matrix1);
-                while matrix1[current].iter().any(|x| *x) {
-                    path.push(current);
-                    for next in 0..vertex_count {
-                        if current != next && matrix1[current][next] {
-                            matrix1[current][next] = false;
-                            matrix1[next][current] = false;
-                            let current_connected_component_count =
-                                connected_component_count(&matrix1);
-                            if current_connected_component_count
-                                == previous_connected_component_count
-                                || !matrix1[current].iter().any(|x| *x)
-                            {
-                                current = next;
-                                previous_connected_component_count =
-                                    current_connected_component_count;
-                                break;
-                            } else {
-                                matrix1[current][next] = true;
-                                matrix1[next][current] = true;
-                            }
-                        }
+                for next in (0..n).filter(|&next| matrix[current][next]) {
+                    let candidate = d + weights[current][next];
+                    if distance[next].is_none_or(|best| candidate < best) {
+                        distance[next] = Some(candidate);
+                        previous[next] = Some(current);
+                        heap.push(Reverse((candidate, next)));
                     }
                 }
-                path.push(current);
-                if odd_degree_vertices.is_empty() {
-                    (true, false, path)
-                } else {
-                    (false, true, path)
+            }
+        }
+    }
+    let length = distance[target]?;
+    let mut path = vec![target];
+    while let Some(vertex) = previous[*path.last().unwrap()] {
+        path.push(vertex);
+    }
+    path.reverse();
+    Some((path, length))
+}
+
+#[test]
+fn test_shortest_path() {
+    let (matrix, weights) = parse_edges(4, false, &["0 1", "1 2", "2 3", "0 3"]).unwrap();
+    assert_eq!(weights, None);
+    assert_eq!(shortest_path(&matrix, None, 0, 2).map(|(_, d)| d), Some(2));
+    assert_eq!(shortest_path(&matrix, None, 1, 1), Some((vec![1], 0)));
+    let (matrix, weights) = parse_edges(4, true, &["0 1 1", "1 2 1", "2 3 1", "0 3 5"]).unwrap();
+    assert_eq!(
+        shortest_path(&matrix, weights.as_ref(), 0, 3),
+        Some((vec![0, 1, 2, 3], 3))
+    );
+    assert_eq!(shortest_path(&matrix, weights.as_ref(), 3, 0), None);
+    assert_eq!(
+        split_path_query(&["4", "0 1", "path 0 1"]),
+        (vec!["4", "0 1"], Some("path 0 1"))
+    );
+    assert!(parse_path_query("path 0 4", 4).is_err());
+}
+
+#[test]
+fn test_parse_edges() {
+    let (matrix, _) = parse_edges(3, false, &["0 1", "1 2"]).unwrap();
+    assert!(matrix[0][1] && matrix[1][0] && matrix[2][1] && !matrix[0][2]);
+    assert!(parse_edges(3, false, &["0 1", "1 0"]).is_err());
+    assert!(parse_edges(3, true, &["0 1", "1 0"]).is_ok());
+    assert!(parse_edges(3, false, &["1 1"]).is_err());
+    assert!(parse_edges(3, false, &["0 3"]).is_err());
+}
+
+/// Whether the vertices with nonzero degree are strongly connected, false if there is no edge.
+fn is_strongly_connected(matrix: &Matrix<bool>) -> bool {
+    let m = matrix.shape().0;
+    let non_isolated = (0..m)
+        .filter(|&i| (0..m).any(|j| matrix[i][j] || matrix[j][i]))
+        .collect_vec();
+    let Some(&start) = non_isolated.first() else {
+        return false;
+    };
+    [matrix.clone(), matrix.transpose()].iter().all(|matrix| {
+        let mut visited = repeat_n(false, m).collect_vec();
+        dfs1(matrix, start, &mut visited);
+        non_isolated.iter().all(|&i| visited[i])
+    })
+}
+
+/// Hierholzer 算法, assuming an Euler path starts from `start`.
+fn hierholzer(matrix: &Matrix<bool>, start: usize, directed: bool) -> Vec<usize> {
+    let mut matrix = matrix.clone();
+    let mut stack = vec![start];
+    let mut path = Vec::new();
+    while let Some(&current) = stack.last() {
+        match matrix[current].iter().position(|x| *x) {
+            Some(next) => {
+                matrix[current][next] = false;
+                if !directed {
+                    matrix[next][current] = false;
                 }
+                stack.push(next);
             }
-        } else {
+            None => path.push(stack.pop().unwrap()),
+        }
+    }
+    path.reverse();
+    path
+}
+
+/// Euler circuit or path of the connected undirected graph, starting from the smallest odd
+/// degree vertex if there is one. `None` if there is none.
+fn euler_path(adj: &Matrix<bool>, degrees: &[usize]) -> Option<Vec<usize>> {
+    if connected_component_count(adj) != 1 {
+        return None;
+    }
+    let odd_degree_vertices = (0..degrees.len())
+        .filter(|&i| degrees[i].is_odd())
+        .collect_vec();
+    if odd_degree_vertices.len() > 2 {
+        return None;
+    }
+    let start = odd_degree_vertices.first().copied().unwrap_or(0);
+    Some(hierholzer(adj, start, false))
+}
+
+#[test]
+fn test_euler_path() {
+    let check = |vertex_count: usize, edges: &[&str]| {
+        let (matrix, _) = parse_edges(vertex_count, false, edges).unwrap();
+        let degrees = matrix
+            .iter()
+            .map(|row| row.iter().filter(|x| **x).count())
+            .collect_vec();
+        let path = euler_path(&matrix, &degrees).unwrap();
+        // every edge traversed exactly once
+        let mut remaining = matrix.clone();
+        for (&a, &b) in path.iter().tuple_windows() {
+            assert!(remaining[a][b]);
+            remaining[a][b] = false;
+            remaining[b][a] = false;
+        }
+        assert!(remaining.iter().flatten().all(|x| !*x));
+        path
+    };
+    // triangle
+    let path = check(3, &["0 1", "1 2", "2 0"]);
+    assert_eq!(path.first(), path.last());
+    // figure-eight of two triangles sharing vertex 0
+    let path = check(5, &["0 1", "1 2", "2 0", "0 3", "3 4", "4 0"]);
+    assert_eq!(path.first(), path.last());
+    // path 1 - 0 - 2 - 3 with odd vertices 1 and 3
+    let path = check(4, &["1 0", "0 2", "2 3"]);
+    assert_eq!((path[0], path[path.len() - 1]), (1, 3));
+    let (star, _) = parse_edges(4, false, &["0 1", "0 2", "0 3"]).unwrap();
+    assert_eq!(euler_path(&star, &[3, 1, 1, 1]), None);
+}
+
+#[test]
+fn test_directed_euler_path() {
+    // edges 0 -> 1, 1 -> 2, 2 -> 0 and 0 -> 2
+    let mut matrix = Matrix(vec![vec![false; 3]; 3]);
+    for (a, b) in [(0, 1), (1, 2), (2, 0), (0, 2)] {
+        matrix[a][b] = true;
+    }
+    assert!(is_strongly_connected(&matrix));
+    assert_eq!(hierholzer(&matrix, 0, true), [0, 1, 2, 0, 2]);
+    matrix[2][0] = false;
+    assert!(!is_strongly_connected(&matrix));
+}
+
+/// Hamiltonian paths are only searched in graphs with at most this many vertices.
+const MAX_HAMILTONIAN_VERTEX_COUNT: usize = 15;
+
+/// Hamiltonian cycle (listed without returning to the start) or path found by backtracking.
+fn hamiltonian_path(matrix: &Matrix<bool>, cycle: bool) -> Option<Vec<usize>> {
+    fn search(
+        matrix: &Matrix<bool>,
+        cycle: bool,
+        path: &mut Vec<usize>,
+        visited: &mut Vec<bool>,
+    ) -> bool {
+        let n = matrix.shape().0;
+        let current = *path.last().unwrap();
+        if path.len() == n {
+            return !cycle || matrix[current][path[0]];
+        }
+        // an unvisited vertex unreachable from the remaining ones and the current one is a dead end
+        let dead_end = (0..n).any(|i| {
+            !visited[i] && !matrix[current][i] && !(0..n).any(|j| !visited[j] && matrix[j][i])
+        });
+        if dead_end {
+            return false;
+        }
+        for next in 0..n {
+            if !visited[next] && matrix[current][next] {
+                visited[next] = true;
+                path.push(next);
+                if search(matrix, cycle, path, visited) {
+                    return true;
+                }
+                path.pop();
+                visited[next] = false;
+            }
+        }
+        false
+    }
+    let n = matrix.shape().0;
+    if n == 0 || (cycle && n < 3) {
+        return None;
+    }
+    // a cycle can start anywhere
+    let starts = if cycle { 0..1 } else { 0..n };
+    for start in starts {
+        let mut path = vec![start];
+        let mut visited = repeat_n(false, n).collect_vec();
+        visited[start] = true;
+        if search(matrix, cycle, &mut path, &mut visited) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+#[test]
+fn test_hamiltonian_path() {
+    // square 0 - 1 - 2 - 3 - 0 with a diagonal 0 - 2
+    let (matrix, _) = parse_edges(4, false, &["0 1", "1 2", "2 3", "3 0", "0 2"]).unwrap();
+    let cycle = hamiltonian_path(&matrix, true).unwrap();
+    assert_eq!(cycle.iter().copied().sorted().collect_vec(), [0, 1, 2, 3]);
+    assert!(cycle
+        .iter()
+        .zip(cycle.iter().cycle().skip(1))
+        .all(|(&a, &b)| matrix[a][b]));
+    // star has neither
+    let (matrix, _) = parse_edges(4, false, &["0 1", "0 2", "0 3"]).unwrap();
+    assert_eq!(hamiltonian_path(&matrix, true), None);
+    assert_eq!(hamiltonian_path(&matrix, false), None);
+}
+
+/// SVG of the graph with vertices on a circle. Edges of `path` are highlighted and numbered in
+/// order, and `highlighted` vertices are colored.
+pub(super) fn graph_diagram(
+    matrix: &Matrix<bool>,
+    directed: bool,
+    path: &[usize],
+    highlighted: &[bool],
+) -> String {
+    let n = matrix.shape().0;
+    let radius = (n as f64 * 15.0).max(80.0);
+    let size = radius * 2.0 + 60.0;
+    let position = (0..n)
+        .map(|i| {
+            let angle =
+                2.0 * std::f64::consts::PI * i as f64 / n as f64 - std::f64::consts::FRAC_PI_2;
+            (
+                size / 2.0 + radius * angle.cos(),
+                size / 2.0 + radius * angle.sin(),
+            )
+        })
+        .collect_vec();
+    let marker = if directed {
+        r#" marker-end="url(#arrow)""#
+    } else {
+        ""
+    };
+    let edges = (0..n)
+        .flat_map(|i| (0..n).map(move |j| (i, j)))
+        .filter(|&(i, j)| matrix[i][j] && (directed || i < j))
+        .map(|(i, j)| {
+            let ((x1, y1), (x2, y2)) = (position[i], position[j]);
+            format!(
+                r##"<line x1="{x1:.1}" y1="{y1:.1}" x2="{x2:.1}" y2="{y2:.1}" stroke="#999" stroke-width="1"{marker} />"##
+            )
+        })
+        .join("");
+    let path_edges = path
+        .iter()
+        .tuple_windows()
+        .enumerate()
+        .map(|(k, (&i, &j))| {
+            let ((x1, y1), (x2, y2)) = (position[i], position[j]);
+            format!(
+                r##"<line x1="{x1:.1}" y1="{y1:.1}" x2="{x2:.1}" y2="{y2:.1}" stroke="#dc2626" stroke-width="1.5"{marker} /><text x="{:.1}" y="{:.1}" font-size="11" fill="#dc2626">{}</text>"##,
+                (x1 + x2) / 2.0 + 3.0,
+                (y1 + y2) / 2.0 - 3.0,
+                k + 1
+            )
+        })
+        .join("");
+    let vertices = position
+        .iter()
+        .enumerate()
+        .map(|(i, &(x, y))| {
+            format!(
+                r##"<circle cx="{x:.1}" cy="{y:.1}" r="10" fill="{}" stroke="black" /><text x="{x:.1}" y="{:.1}" font-size="11" text-anchor="middle">{i}</text>"##,
+                if highlighted[i] { "#fde68a" } else { "white" },
+                y + 4.0
+            )
+        })
+        .join("");
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}"><defs><marker id="arrow" viewBox="0 0 10 10" refX="20" refY="5" markerWidth="6" markerHeight="6" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" /></marker></defs>{edges}{path_edges}{vertices}</svg>"##
+    )
+}
+
+/// A shortest path with its length and diagram.
+type ShortestPath = (Vec<usize>, u64, String);
+
+/// A Hamiltonian cycle, closed at its start, and a Hamiltonian path.
+type HamiltonianPaths = (Option<Vec<usize>>, Option<Vec<usize>>);
+
+/// Answer of [`Exp4`], the matrix in TeX, the diagrams in SVG and the rest as plain data.
+#[derive(Debug, Clone, PartialEq)]
+struct GraphAnswer {
+    directed: bool,
+    seed: Option<(u64, bool, String)>,
+    matrix: String,
+    diagram: String,
+    in_degree: Vec<usize>,
+    out_degree: Vec<usize>,
+    is_connected: bool,
+    is_eulerian: bool,
+    is_semi_eulerian: bool,
+    /// The Euler circuit or path, empty if there is neither.
+    euler_path: Vec<usize>,
+    /// Source, target and the shortest path with its length and diagram, if queried.
+    shortest: Option<(usize, usize, Option<ShortestPath>)>,
+    /// `None` if the graph is too large.
+    hamiltonian: Option<HamiltonianPaths>,
+}
+
+/// Parse or generate the graph and answer, the error is a message to show.
+fn graph_answer(input: &str) -> Result<GraphAnswer, &'static str> {
+    let lines = input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect_vec();
+    let (lines, path_query) = split_path_query(&lines);
+    let Graph {
+        directed,
+        vertex_count,
+        matrix,
+        weights,
+        seed,
+    } = parse_graph(&lines)?;
+    let path_query = match path_query.map(|line| parse_path_query(line, vertex_count)) {
+        Some(Ok(query)) => Some(query),
+        Some(Err(e)) => return Err(e),
+        None => None,
+    };
+    let degree = matrix
+        .iter()
+        .map(|row| row.iter().filter(|x| **x).count())
+        .collect_vec();
+    let out_degree = degree.clone();
+    let in_degree = (0..vertex_count)
+        .map(|j| (0..vertex_count).filter(|&i| matrix[i][j]).count())
+        .collect_vec();
+    let is_connected = if directed {
+        is_strongly_connected(&matrix)
+    } else {
+        connected_component_count(&matrix) == 1
+    };
+    let (is_eulerian, is_semi_eulerian, path) = if directed {
+        // 出度减入度
+        let excess = (0..vertex_count)
+            .map(|i| out_degree[i] as isize - in_degree[i] as isize)
+            .collect_vec();
+        let sources = (0..vertex_count).filter(|&i| excess[i] == 1).collect_vec();
+        let sinks = (0..vertex_count).filter(|&i| excess[i] == -1).collect_vec();
+        let balanced = excess.iter().all(|e| e.abs() <= 1);
+        if !is_connected || !balanced || sources.len() > 1 || sinks.len() != sources.len() {
             (false, false, Vec::new())
+        } else if sources.is_empty() {
+            let start = (0..vertex_count).find(|&i| out_degree[i] > 0).unwrap();
+            (true, false, hierholzer(&matrix, start, true))
+        } else {
+            (false, true, hierholzer(&matrix, sources[0], true))
+        }
+    } else {
+        match euler_path(&matrix, &degree) {
+            Some(path) => {
+                let is_eulerian = degree.iter().all(|d| d.is_even());
+                (is_eulerian, !is_eulerian, path)
+            }
+            None => (false, false, Vec::new()),
+        }
+    };
+    // 奇度节点, 有向图中为入度与出度不等的节点
+    let highlighted = (0..vertex_count)
+        .map(|i| {
+            if directed {
+                in_degree[i] != out_degree[i]
+            } else {
+                degree[i].is_odd()
+            }
+        })
+        .collect_vec();
+    let hamiltonian = (vertex_count <= MAX_HAMILTONIAN_VERTEX_COUNT).then(|| {
+        let cycle = hamiltonian_path(&matrix, true).map(|mut cycle| {
+            cycle.push(cycle[0]);
+            cycle
+        });
+        (cycle, hamiltonian_path(&matrix, false))
+    });
+    let diagram = graph_diagram(&matrix, directed, &path, &highlighted);
+    let shortest = path_query.map(|(source, target)| {
+        let result =
+            shortest_path(&matrix, weights.as_ref(), source, target).map(|(path, length)| {
+                let endpoints = (0..vertex_count)
+                    .map(|i| i == source || i == target)
+                    .collect_vec();
+                let diagram = graph_diagram(&matrix, directed, &path, &endpoints);
+                (path, length, diagram)
+            });
+        (source, target, result)
+    });
+    Ok(GraphAnswer {
+        directed,
+        seed,
+        matrix: format!(
+            r"\begin{{bmatrix}} {} \end{{bmatrix}}",
+            matrix.map(|x| if *x { "1" } else { "0" })
+        ),
+        diagram,
+        in_degree,
+        out_degree,
+        is_connected,
+        is_eulerian,
+        is_semi_eulerian,
+        euler_path: path,
+        shortest,
+        hamiltonian,
+    })
+}
+
+#[test]
+fn test_graph_answer() {
+    let answer = graph_answer("3\n0 1\n1 2\n2 0\npath 0 2").unwrap();
+    assert!(!answer.directed);
+    assert!(answer.is_eulerian);
+    assert_eq!(answer.euler_path.len(), 4);
+    assert_eq!(answer.hamiltonian.unwrap().0.unwrap().len(), 4);
+    let (source, target, result) = answer.shortest.unwrap();
+    assert_eq!((source, target), (0, 2));
+    let (path, length, _) = result.unwrap();
+    assert_eq!((path, length), (vec![0, 2], 1));
+    let answer = graph_answer("directed 3\n0 1\n1 2").unwrap();
+    assert_eq!(answer.in_degree, [0, 1, 1]);
+    assert_eq!(answer.out_degree, [1, 1, 0]);
+    assert!(!answer.is_connected);
+    assert_eq!(answer.hamiltonian.unwrap(), (None, Some(vec![0, 1, 2])));
+}
+
+impl Solver for Exp4 {
+    fn id(&self) -> String {
+        "exp4".to_string()
+    }
+
+    fn title(&self) -> String {
+        "图的随机生成及欧拉（回）路的确定".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入节点数, 边数和可选的随机种, 其中边数也可以是 gnp 和每条边出现的概率, 或 degree 和各节点的度数 (仅无向图); 或在首行输入节点数, 其后每行输入一条边的两个端点 (从 0 开始编号) 和可选的边权. 以 directed 开头时为有向图. 末行输入 path 和起点, 终点时求最短路.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "10 20 1152921504606847241".to_string()
+    }
+
+    fn validate_input(&self, input: &str) -> Result<(), View> {
+        let lines = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect_vec();
+        let (lines, path_query) = split_path_query(&lines);
+        let header = lines.first().copied().unwrap_or("");
+        if lines.len() > 1 {
+            // edge lists and path queries are validated while parsing
+            return Ok(());
+        }
+        let (_, vertex_count, _, _) =
+            parse_random_header(header).map_err(|e| self.render_error(e))?;
+        if let Some(path_query) = path_query {
+            parse_path_query(path_query, vertex_count).map_err(|e| self.render_error(e))?;
+        }
+        Ok(())
+    }
+
+    fn solve(&self, input: String) -> View {
+        let GraphAnswer {
+            directed,
+            seed,
+            matrix,
+            diagram,
+            in_degree,
+            out_degree,
+            is_connected,
+            is_eulerian,
+            is_semi_eulerian,
+            euler_path,
+            shortest,
+            hamiltonian,
+        } = match graph_answer(&input) {
+            Ok(answer) => answer,
+            Err(e) => return self.render_error(e),
         };
-        let matrix = matrix.map(|x| if *x { "1" } else { "0" });
+        let vertex_count = in_degree.len();
         let (class_name, style_val) = style_str! {
             tr {
                 border-top: 1px solid #333;
@@ -168,20 +975,64 @@ impl Solver for Exp4 {
         view! {
             class = class_name,
             <Style> {style_val} </Style>
+            {
+                seed.map(|(seed, generated, header)| view! {
+                    <div class="mb-10">
+                        <p class="font-bold mb-2"> {
+                            if generated { "随机生成的种子 (点击复制)" } else { "随机种 (点击复制)" }
+                        } </p>
+                        <p class="mb-2">
+                            <button class="font-mono underline" on:click=move |_| copy_to_clipboard(&seed.to_string())>
+                                { seed }
+                            </button>
+                        </p>
+                        <p> "输入 " <code class="font-mono"> { header } </code> " 可以重新生成相同的图." </p>
+                    </div>
+                })
+            }
             <div class="mb-10">
-                <p class="font-bold mb-2"> "随机种" </p>
-                <p class="mb-6"> { seed } </p>
+                <p class="font-bold mb-2"> "邻接矩阵" </p>
+                <KaTeX expr={ matrix } />
             </div>
             <div class="mb-10">
-                <p class="font-bold mb-2"> "邻接矩阵" </p>
-                <KaTeX expr={ format!(r"\begin{{bmatrix}} {} \end{{bmatrix}}", matrix) } />
+                <p class="font-bold mb-2"> "图" </p>
+                <div inner_html={ diagram }></div>
             </div>
+            {
+                if directed {
+                    view! {
+                        class = class_name,
+                        <div class="mb-10">
+                            <p class="font-bold mb-2"> "入度与出度" </p>
+                            <table>
+                                <tbody>
+                                    <tr>
+                                        <td> "节点" </td>
+                                        { (0..vertex_count).map(|i| view! { class = class_name, <td> { i } </td> }).collect_vec() }
+                                    </tr>
+                                    <tr>
+                                        <td> "入度" </td>
+                                        { in_degree.iter().map(|d| view! { class = class_name, <td> { *d } </td> }).collect_vec() }
+                                    </tr>
+                                    <tr>
+                                        <td> "出度" </td>
+                                        { out_degree.iter().map(|d| view! { class = class_name, <td> { *d } </td> }).collect_vec() }
+                                    </tr>
+                                </tbody>
+                            </table>
+                        </div>
+                    }
+                    .into_view()
+                } else {
+                    ().into_view()
+                }
+            }
             <div class="mb-10">
                 <p class="font-bold mb-2"> "图的判定" </p>
                 <table>
                     <tbody>
                         <tr>
-                            <td> "连通图" </td>
+                            <td> { if directed { "强连通图 (忽略孤立节点)" } else { "连通图" } } </td>
                             <td> { if is_connected { "是" } else { "否" } } </td>
                         </tr>
                         {
@@ -210,13 +1061,71 @@ impl Solver for Exp4 {
                         class = class_name,
                         <div class="mb-10">
                             <p class="font-bold mb-2"> { if is_eulerian { "欧拉回路" } else { "欧拉路" } } </p>
-                            <p> { path.iter().map(|v| v.to_string()).join(" ") } </p>
+                            <p> { euler_path.iter().map(|v| v.to_string()).join(" ") } </p>
                         </div>
                     }.into_view()
                 } else {
                     ().into_view()
                 }
             }
+            {
+                shortest.map(|(source, target, result)| view! {
+                    class = class_name,
+                    <div class="mb-10">
+                        <p class="font-bold mb-2"> { format!("{source} 到 {target} 的最短路") } </p>
+                        {
+                            match result {
+                                Some((path, length, diagram)) => view! {
+                                    class = class_name,
+                                    <p> { path.iter().join(" ") } </p>
+                                    <p class="mb-2"> { format!("长度为 {length}.") } </p>
+                                    <div inner_html={ diagram }></div>
+                                }
+                                .into_view(),
+                                None => view! { class = class_name, <p> "不可达." </p> }.into_view(),
+                            }
+                        }
+                    </div>
+                })
+            }
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "哈密顿回路与哈密顿路" </p>
+                {
+                    match hamiltonian {
+                        Some((cycle, path)) => {
+                            let to_string = |path: Option<Vec<usize>>| match path {
+                                Some(path) => path.iter().map(|v| v.to_string()).join(" "),
+                                None => "不存在".to_string(),
+                            };
+                            view! {
+                                class = class_name,
+                                <table>
+                                    <tbody>
+                                        <tr>
+                                            <td> "哈密顿回路" </td>
+                                            <td> { to_string(cycle) } </td>
+                                        </tr>
+                                        <tr>
+                                            <td> "哈密顿路" </td>
+                                            <td> { to_string(path) } </td>
+                                        </tr>
+                                    </tbody>
+                                </table>
+                                <p class="mt-2"> {
+                                    format!("回溯搜索的时间随节点数指数增长, 仅对不超过 {MAX_HAMILTONIAN_VERTEX_COUNT} 个节点的图进行搜索.")
+                                } </p>
+                            }
+                            .into_view()
+                        }
+                        None => view! {
+                            <p> {
+                                format!("节点数超过 {MAX_HAMILTONIAN_VERTEX_COUNT}, 回溯搜索耗时过长, 未进行搜索.")
+                            } </p>
+                        }
+                        .into_view(),
+                    }
+                }
+            </div>
         }
         .into_view()
     }
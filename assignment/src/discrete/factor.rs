@@ -0,0 +1,172 @@
+use itertools::Itertools;
+use leptos::*;
+use num::{BigInt, Integer, One, Signed, Zero};
+use shiyanyi::*;
+
+/// Trial division stops at this divisor, leaving larger factors unfactored.
+const MAX_TRIAL_DIVISOR: u64 = 1_000_000;
+
+/// Prime factors `(p, a)` of `n > 0` in ascending order by trial division, along with the
+/// remaining factor above 1 if the search stops at [`MAX_TRIAL_DIVISOR`]. Divides in `u64` once
+/// the remaining factor fits.
+fn factorize(n: &BigInt) -> (Vec<(BigInt, u32)>, Option<BigInt>) {
+    let mut n = n.clone();
+    let mut factors = Vec::new();
+    let mut p = 2u64;
+    let next = |p: u64| if p == 2 { 3 } else { p + 2 };
+    // p * p <= n always holds here as p stays below 2^32
+    while u64::try_from(&n).is_err() {
+        if p > MAX_TRIAL_DIVISOR {
+            return (factors, Some(n));
+        }
+        let mut a = 0;
+        loop {
+            let (q, r) = n.div_rem(&BigInt::from(p));
+            if !r.is_zero() {
+                break;
+            }
+            n = q;
+            a += 1;
+        }
+        if a > 0 {
+            factors.push((BigInt::from(p), a));
+        }
+        p = next(p);
+    }
+    let mut n = u64::try_from(&n).unwrap();
+    while p * p <= n {
+        if p > MAX_TRIAL_DIVISOR {
+            return (factors, Some(n.into()));
+        }
+        let mut a = 0;
+        while n % p == 0 {
+            n /= p;
+            a += 1;
+        }
+        if a > 0 {
+            factors.push((p.into(), a));
+        }
+        p = next(p);
+    }
+    if n > 1 {
+        factors.push((n.into(), 1));
+    }
+    (factors, None)
+}
+
+#[test]
+fn test_factorize() {
+    let factors = |n: u64| {
+        let (factors, rest) = factorize(&BigInt::from(n));
+        assert_eq!(rest, None);
+        factors
+            .into_iter()
+            .map(|(p, a)| (p.try_into().unwrap(), a))
+            .collect::<Vec<(u64, u32)>>()
+    };
+    assert_eq!(factors(360), [(2, 3), (3, 2), (5, 1)]);
+    assert_eq!(factors(1_000_000_007), [(1_000_000_007, 1)]);
+    assert_eq!(factors(1), []);
+    assert_eq!(factors(999_983 * 999_983 * 2), [(2, 1), (999_983, 2)]);
+    let (factors, rest) = factorize(&"170141183460469231731687303715884105727".parse().unwrap());
+    assert!(factors.is_empty());
+    assert!(rest.is_some());
+    let (factors, rest) = factorize(&BigInt::from(1_000_003u64 * 1_000_033 * 4));
+    assert_eq!(factors, [(BigInt::from(2), 2)]);
+    assert_eq!(rest, Some(BigInt::from(1_000_003u64 * 1_000_033)));
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PrimeFactorizationSolver;
+
+impl Solver for PrimeFactorizationSolver {
+    fn id(&self) -> String {
+        "factor".to_string()
+    }
+
+    fn title(&self) -> String {
+        "素性判定与素因数分解".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入正整数.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "360".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let n = match input.trim().parse::<BigInt>() {
+            Ok(n) => n,
+            Err(_) => return self.render_error("无法解析输入."),
+        };
+        if !n.is_positive() {
+            return self.render_error("整数应为正数.");
+        }
+        let (factors, rest) = factorize(&n);
+        if let Some(rest) = rest {
+            let factorization = factors
+                .iter()
+                .map(|(p, a)| factor_to_tex(p, *a))
+                .chain([rest.to_string()])
+                .join(r" \cdot ");
+            return view! {
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "部分分解" </p>
+                    <KaTeX expr={ format!("{n} = {factorization}") } />
+                    <p class="mt-2"> {
+                        format!("试除至 {MAX_TRIAL_DIVISOR} 为止, 分解可能不完整: 最后一个因子 {rest} 没有更小的素因子, 但未判定其是否为素数.")
+                    } </p>
+                </div>
+            }
+            .into_view();
+        }
+        let primality = if n.is_one() {
+            "1 既不是素数也不是合数."
+        } else if factors.len() == 1 && factors[0].1 == 1 {
+            "是素数."
+        } else {
+            "是合数."
+        };
+        let factorization = if factors.is_empty() {
+            "1".to_string()
+        } else {
+            factors
+                .iter()
+                .map(|(p, a)| factor_to_tex(p, *a))
+                .join(r" \cdot ")
+        };
+        // σ_0(n) = ∏ (a + 1), σ_1(n) = ∏ (p^{a + 1} - 1) / (p - 1)
+        let divisor_count = factors
+            .iter()
+            .fold(BigInt::one(), |acc, (_, a)| acc * (a + 1));
+        let divisor_sum = factors.iter().fold(BigInt::one(), |acc, (p, a)| {
+            acc * (num::pow(p.clone(), *a as usize + 1) - 1) / (p - 1)
+        });
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "素性" </p>
+                <p class="text-xl font-bold"> { primality } </p>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "素因数分解" </p>
+                <KaTeX expr={ format!("{n} = {factorization}") } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "因数个数与因数和" </p>
+                <KaTeX expr={ format!(r"\sigma_0({n}) = {divisor_count}") } />
+                <KaTeX expr={ format!(r"\sigma_1({n}) = {divisor_sum}") } />
+            </div>
+        }
+        .into_view()
+    }
+}
+
+fn factor_to_tex(p: &BigInt, a: u32) -> String {
+    if a == 1 {
+        p.to_string()
+    } else {
+        format!("{p}^{{{a}}}")
+    }
+}
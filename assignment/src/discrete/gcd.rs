@@ -0,0 +1,136 @@
+use itertools::Itertools;
+use leptos::*;
+use num::{BigInt, Integer, One, Signed, Zero};
+use shiyanyi::*;
+
+/// Steps `(a, q, b, r)` with `a = q b + r` of the Euclidean algorithm on `|a|` and `|b|`.
+fn euclidean_steps(a: &BigInt, b: &BigInt) -> Vec<(BigInt, BigInt, BigInt, BigInt)> {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    let mut steps = Vec::new();
+    while !b.is_zero() {
+        let (q, r) = a.div_rem(&b);
+        steps.push((a, q, b.clone(), r.clone()));
+        (a, b) = (b, r);
+    }
+    steps
+}
+
+/// `(g, s, t)` with `g = gcd(a, b) = s a + t b` and `g >= 0`.
+fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a.abs(), b.abs());
+    let (mut old_s, mut s) = (BigInt::one(), BigInt::zero());
+    let (mut old_t, mut t) = (BigInt::zero(), BigInt::one());
+    while !r.is_zero() {
+        let q = &old_r / &r;
+        (old_r, r) = (r.clone(), old_r - &q * r);
+        (old_s, s) = (s.clone(), old_s - &q * s);
+        (old_t, t) = (t.clone(), old_t - &q * t);
+    }
+    if a.is_negative() {
+        old_s = -old_s;
+    }
+    if b.is_negative() {
+        old_t = -old_t;
+    }
+    (old_r, old_s, old_t)
+}
+
+#[test]
+fn test_gcd() {
+    let (a, b) = (BigInt::from(1071), BigInt::from(462));
+    assert_eq!(
+        euclidean_steps(&a, &b)
+            .into_iter()
+            .map(|(_, q, _, r)| (q, r))
+            .collect_vec(),
+        [(2, 147), (3, 21), (7, 0)]
+            .into_iter()
+            .map(|(q, r)| (BigInt::from(q), BigInt::from(r)))
+            .collect_vec()
+    );
+    assert_eq!(
+        extended_gcd(&a, &b),
+        (BigInt::from(21), BigInt::from(-3), BigInt::from(7))
+    );
+    let (g, s, t) = extended_gcd(&-&a, &b);
+    assert_eq!(g, BigInt::from(21));
+    assert_eq!(s * -&a + t * &b, g);
+    assert_eq!(
+        extended_gcd(&BigInt::zero(), &BigInt::from(-5)),
+        (BigInt::from(5), BigInt::zero(), BigInt::from(-1))
+    );
+}
+
+/// Parenthesized if negative.
+fn to_tex(n: &BigInt) -> String {
+    if n.is_negative() {
+        format!(r"\left({n}\right)")
+    } else {
+        n.to_string()
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GcdSolver;
+
+impl Solver for GcdSolver {
+    fn id(&self) -> String {
+        "gcd".to_string()
+    }
+
+    fn title(&self) -> String {
+        "辗转相除法求最大公因数".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入两个整数.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "1071 462".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let (a, b) = match input
+            .split_whitespace()
+            .map(|s| s.parse::<BigInt>())
+            .collect_tuple()
+        {
+            Some((Ok(a), Ok(b))) => (a, b),
+            _ => return self.render_error("无法解析输入."),
+        };
+        let steps = euclidean_steps(&a, &b)
+            .into_iter()
+            .map(|(a, q, b, r)| format!(r"{a} &= {q} \times {b} + {r}"))
+            .collect_vec();
+        let (g, s, t) = extended_gcd(&a, &b);
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "最大公因数" </p>
+                <KaTeX expr={ format!(r"\gcd({a}, {b}) = {g}") } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "辗转相除" </p>
+                {
+                    if steps.is_empty() {
+                        view! { <p> "其中一数为零, 无需相除." </p> }.into_view()
+                    } else {
+                        view! {
+                            <KaTeX display_mode=true fleqn=true expr={
+                                format!(r"\begin{{align*}} {} \end{{align*}}", steps.join(r" \\ "))
+                            } />
+                        }
+                        .into_view()
+                    }
+                }
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "裴蜀等式" </p>
+                <KaTeX expr={
+                    format!(r"{g} = {} \times {} + {} \times {}", to_tex(&s), to_tex(&a), to_tex(&t), to_tex(&b))
+                } />
+            </div>
+        }
+        .into_view()
+    }
+}
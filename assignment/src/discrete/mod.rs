@@ -6,3 +6,21 @@ mod exp3;
 pub use exp3::Exp3;
 mod exp4;
 pub use exp4::Exp4;
+mod spanning;
+pub use spanning::SpanningTreeCountSolver;
+mod equiv;
+pub use equiv::EquivalenceSolver;
+mod gcd;
+pub use gcd::GcdSolver;
+mod modexp;
+pub use modexp::ModularExponentiationSolver;
+mod qmc;
+pub use qmc::QuineMcCluskeySolver;
+mod sets;
+pub use sets::SetOperationSolver;
+mod comb;
+pub use comb::CombinatoricsSolver;
+mod factor;
+pub use factor::PrimeFactorizationSolver;
+mod simplify;
+pub use simplify::BooleanSimplificationSolver;
@@ -0,0 +1,164 @@
+use itertools::Itertools;
+use leptos::*;
+use num::{BigInt, Integer, One, Signed, Zero};
+use shiyanyi::*;
+
+/// Steps `(k, r)` with `r = base^k mod modulus` of square-and-multiply, `k` running over the
+/// binary prefixes of `exponent`. The last step holds the result.
+fn square_and_multiply(
+    base: &BigInt,
+    exponent: &BigInt,
+    modulus: &BigInt,
+) -> Vec<(BigInt, BigInt)> {
+    let base = base.mod_floor(modulus);
+    let mut k = BigInt::zero();
+    let mut r = BigInt::one().mod_floor(modulus);
+    let mut steps = vec![(k.clone(), r.clone())];
+    for i in (0..exponent.bits()).rev() {
+        k = &k * 2;
+        r = (&r * &r).mod_floor(modulus);
+        if exponent.bit(i) {
+            k += 1;
+            r = (r * &base).mod_floor(modulus);
+        }
+        steps.push((k.clone(), r.clone()));
+    }
+    steps
+}
+
+/// Multiplicative orders are only searched up to this bound.
+const MAX_ORDER: u64 = 1_000_000;
+
+/// Multiplicative order of `base` modulo `modulus`, assuming they are coprime. `None` if it
+/// exceeds [`MAX_ORDER`].
+fn multiplicative_order(base: &BigInt, modulus: &BigInt) -> Option<u64> {
+    let base = base.mod_floor(modulus);
+    let one = BigInt::one().mod_floor(modulus);
+    let mut r = base.clone();
+    for k in 1..=MAX_ORDER {
+        if r == one {
+            return Some(k);
+        }
+        r = (r * &base).mod_floor(modulus);
+    }
+    None
+}
+
+#[test]
+fn test_modexp() {
+    let (base, exponent, modulus) = (BigInt::from(3), BigInt::from(13), BigInt::from(7));
+    // 13 = 0b1101
+    assert_eq!(
+        square_and_multiply(&base, &exponent, &modulus),
+        [(0, 1), (1, 3), (3, 6), (6, 1), (13, 3)]
+            .into_iter()
+            .map(|(k, r)| (BigInt::from(k), BigInt::from(r)))
+            .collect_vec()
+    );
+    assert_eq!(multiplicative_order(&base, &modulus), Some(6));
+    assert_eq!(multiplicative_order(&BigInt::from(-1), &modulus), Some(2));
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ModularExponentiationSolver;
+
+impl Solver for ModularExponentiationSolver {
+    fn id(&self) -> String {
+        "modexp".to_string()
+    }
+
+    fn title(&self) -> String {
+        "模幂运算与乘法阶".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入底数, 非负整数指数和正整数模数.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "3 13 7".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let (base, exponent, modulus) = match input
+            .split_whitespace()
+            .map(|s| s.parse::<BigInt>())
+            .collect_tuple()
+        {
+            Some((Ok(base), Ok(exponent), Ok(modulus))) => (base, exponent, modulus),
+            _ => return self.render_error("无法解析输入."),
+        };
+        if !modulus.is_positive() {
+            return self.render_error("模数应为正整数.");
+        }
+        if exponent.is_negative() {
+            return self.render_error("指数应为非负整数.");
+        }
+        let steps = square_and_multiply(&base, &exponent, &modulus);
+        let result = steps.last().unwrap().1.clone();
+        let base_tex = if base.is_negative() {
+            format!(r"\left({base}\right)")
+        } else {
+            base.to_string()
+        };
+        let trace = steps
+            .iter()
+            .tuple_windows()
+            .map(|((previous, _), (k, r))| {
+                let square = format!(r"\left({base_tex}^{{{previous}}}\right)^2");
+                let operation = if k.is_odd() {
+                    format!(r"{square} \cdot {base_tex}")
+                } else {
+                    square
+                };
+                format!(r"{base_tex}^{{{k}}} &= {operation} \equiv {r} \pmod{{{modulus}}}")
+            })
+            .collect_vec();
+        let order = if base.gcd(&modulus).is_one() {
+            match multiplicative_order(&base, &modulus) {
+                Some(order) => view! {
+                    <KaTeX expr={ format!(r"\operatorname{{ord}}_{{{modulus}}}({base}) = {order}") } />
+                }
+                .into_view(),
+                None => view! {
+                    <p> { format!("乘法阶超过 {MAX_ORDER}, 未求出.") } </p>
+                }
+                .into_view(),
+            }
+        } else {
+            view! {
+                <p> "底数与模数不互素, 乘法阶不存在." </p>
+            }
+            .into_view()
+        };
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "模幂" </p>
+                <KaTeX expr={ format!(r"{base_tex}^{{{exponent}}} \equiv {result} \pmod{{{modulus}}}") } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "平方-乘算法" </p>
+                {
+                    if trace.is_empty() {
+                        view! { <p> "指数为零, 无需计算." </p> }.into_view()
+                    } else {
+                        view! {
+                            <p class="mb-2"> {
+                                format!("指数的二进制表示为 {}.", exponent.to_str_radix(2))
+                            } </p>
+                            <KaTeX display_mode=true fleqn=true expr={
+                                format!(r"\begin{{align*}} {} \end{{align*}}", trace.join(r" \\ "))
+                            } />
+                        }
+                        .into_view()
+                    }
+                }
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "乘法阶" </p>
+                { order }
+            </div>
+        }
+        .into_view()
+    }
+}
@@ -0,0 +1,511 @@
+use itertools::Itertools;
+use leptos::*;
+use leptos_meta::Style;
+use shiyanyi::*;
+use stylers::style_str;
+
+use super::exp1::{syntax_error, Expr};
+
+/// Functions with more variables are not minimized.
+const MAX_VARIABLE_COUNT: usize = 12;
+
+/// The exact search for a minimal cover gives up after visiting this many nodes.
+const MAX_SEARCH_NODES: usize = 10_000;
+
+/// With more prime implicants, the exact search is skipped for a greedy cover.
+const MAX_SEARCH_PRIMES: usize = 64;
+
+/// Product term covering every index `x` with `x & !mask == value`, bits in `mask` are the
+/// eliminated variables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Implicant {
+    value: usize,
+    mask: usize,
+}
+
+impl Implicant {
+    fn covers(&self, index: usize) -> bool {
+        index & !self.mask == self.value
+    }
+
+    fn literal_count(&self, n: usize) -> usize {
+        n - self.mask.count_ones() as usize
+    }
+
+    /// Pattern like `1-0`, the first variable being the most significant bit.
+    fn pattern(&self, n: usize) -> String {
+        (0..n)
+            .rev()
+            .map(|i| {
+                if self.mask >> i & 1 == 1 {
+                    '-'
+                } else if self.value >> i & 1 == 1 {
+                    '1'
+                } else {
+                    '0'
+                }
+            })
+            .collect()
+    }
+
+    fn to_tex(self, variables: &[String]) -> String {
+        let n = variables.len();
+        let literals = variables
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.mask >> (n - 1 - i) & 1 == 0)
+            .map(|(i, variable)| {
+                if self.value >> (n - 1 - i) & 1 == 1 {
+                    variable.clone()
+                } else {
+                    format!(r"\lnot {variable}")
+                }
+            })
+            .join(r" \land ");
+        if literals.is_empty() {
+            r"\top".to_string()
+        } else {
+            literals
+        }
+    }
+}
+
+/// Prime implicants of the function with `minterms` and `dont_cares`.
+fn prime_implicants(minterms: &[usize], dont_cares: &[usize]) -> Vec<Implicant> {
+    let mut implicants = minterms
+        .iter()
+        .chain(dont_cares)
+        .map(|&value| Implicant { value, mask: 0 })
+        .unique()
+        .collect_vec();
+    let mut primes = Vec::new();
+    while !implicants.is_empty() {
+        let mut combined = vec![false; implicants.len()];
+        let mut next = Vec::new();
+        for i in 0..implicants.len() {
+            for j in i + 1..implicants.len() {
+                let (a, b) = (implicants[i], implicants[j]);
+                let diff = a.value ^ b.value;
+                if a.mask == b.mask && diff.count_ones() == 1 {
+                    combined[i] = true;
+                    combined[j] = true;
+                    next.push(Implicant {
+                        value: a.value & !diff,
+                        mask: a.mask | diff,
+                    });
+                }
+            }
+        }
+        primes.extend(
+            implicants
+                .iter()
+                .zip(combined)
+                .filter(|(_, combined)| !combined)
+                .map(|(implicant, _)| *implicant),
+        );
+        implicants = next.into_iter().unique().collect_vec();
+    }
+    primes.into_iter().sorted().collect_vec()
+}
+
+/// Number of terms then of literals, less is simpler.
+fn cover_cost(n: usize, cover: &[Implicant]) -> (usize, usize) {
+    (
+        cover.len(),
+        cover.iter().map(|p| p.literal_count(n)).sum::<usize>(),
+    )
+}
+
+/// A cover of `minterms` by `primes` with the fewest terms then the fewest literals, essential
+/// prime implicants first, and whether it is proven minimal. With more than
+/// [`MAX_SEARCH_PRIMES`] primes, or after [`MAX_SEARCH_NODES`] nodes of search, the best cover
+/// found or a greedy one is returned.
+fn minimal_cover(n: usize, minterms: &[usize], primes: &[Implicant]) -> (Vec<Implicant>, bool) {
+    fn search(
+        n: usize,
+        uncovered: &[usize],
+        primes: &[Implicant],
+        chosen: &mut Vec<Implicant>,
+        best: &mut Option<Vec<Implicant>>,
+        nodes: &mut usize,
+    ) {
+        if *nodes >= MAX_SEARCH_NODES
+            || best
+                .as_ref()
+                .is_some_and(|best| cover_cost(n, chosen) >= cover_cost(n, best))
+        {
+            return;
+        }
+        *nodes += 1;
+        // branch on the minterm with the fewest covering primes
+        let Some(minterm) = uncovered
+            .iter()
+            .min_by_key(|&&m| primes.iter().filter(|p| p.covers(m)).count())
+        else {
+            *best = Some(chosen.clone());
+            return;
+        };
+        for prime in primes.iter().filter(|p| p.covers(*minterm)) {
+            chosen.push(*prime);
+            let uncovered = uncovered
+                .iter()
+                .copied()
+                .filter(|&m| !prime.covers(m))
+                .collect_vec();
+            search(n, &uncovered, primes, chosen, best, nodes);
+            chosen.pop();
+        }
+    }
+    let essential = essential_prime_implicants(minterms, primes);
+    let uncovered = minterms
+        .iter()
+        .copied()
+        .filter(|&m| !essential.iter().any(|p| p.covers(m)))
+        .collect_vec();
+    let mut best = None;
+    let mut nodes = if primes.len() > MAX_SEARCH_PRIMES {
+        MAX_SEARCH_NODES
+    } else {
+        0
+    };
+    search(
+        n,
+        &uncovered,
+        primes,
+        &mut essential.clone(),
+        &mut best,
+        &mut nodes,
+    );
+    if nodes < MAX_SEARCH_NODES {
+        return (best.unwrap_or(essential), true);
+    }
+    let greedy = greedy_cover(n, &uncovered, primes, essential);
+    match best {
+        Some(best) if cover_cost(n, &best) <= cover_cost(n, &greedy) => (best, false),
+        _ => (greedy, false),
+    }
+}
+
+/// Extends `chosen` by repeatedly taking the prime covering the most of `uncovered`, then with
+/// the fewest literals.
+fn greedy_cover(
+    n: usize,
+    uncovered: &[usize],
+    primes: &[Implicant],
+    mut chosen: Vec<Implicant>,
+) -> Vec<Implicant> {
+    let mut uncovered = uncovered.to_vec();
+    while !uncovered.is_empty() {
+        let prime = *primes
+            .iter()
+            .max_by_key(|p| {
+                let count = uncovered.iter().filter(|&&m| p.covers(m)).count();
+                (count, std::cmp::Reverse(p.literal_count(n)))
+            })
+            .unwrap();
+        uncovered.retain(|&m| !prime.covers(m));
+        chosen.push(prime);
+    }
+    chosen
+}
+
+fn essential_prime_implicants(minterms: &[usize], primes: &[Implicant]) -> Vec<Implicant> {
+    minterms
+        .iter()
+        .filter_map(|&m| primes.iter().filter(|p| p.covers(m)).exactly_one().ok())
+        .copied()
+        .unique()
+        .sorted()
+        .collect_vec()
+}
+
+#[test]
+fn test_quine_mccluskey() {
+    let minimize = |n: usize, minterms: &[usize], dont_cares: &[usize]| {
+        let primes = prime_implicants(minterms, dont_cares);
+        let (cover, minimal) = minimal_cover(n, minterms, &primes);
+        assert!(minimal);
+        for index in 0..1 << n {
+            if !dont_cares.contains(&index) {
+                assert_eq!(
+                    cover.iter().any(|p| p.covers(index)),
+                    minterms.contains(&index)
+                );
+            }
+        }
+        cover.iter().map(|p| p.pattern(n)).sorted().collect_vec()
+    };
+    assert_eq!(minimize(3, &[1, 3, 5, 7], &[]), ["--1"]);
+    assert_eq!(
+        minimize(4, &[1, 3, 7, 11, 15], &[0, 2, 5]),
+        ["--11", "00--"]
+    );
+    // cyclic chart with two minimal covers of three terms
+    assert_eq!(minimize(3, &[0, 1, 2, 5, 6, 7], &[]).len(), 3);
+    assert_eq!(
+        minimize(4, &[4, 8, 10, 11, 12, 15], &[9, 14]),
+        ["-100", "1-1-", "10--"]
+    );
+    assert_eq!(minimize(2, &[0, 1, 2, 3], &[]), ["--"]);
+    assert!(minimize(2, &[], &[]).is_empty());
+    // too many primes for the exact search, which falls back to the greedy cover
+    let minterms = (0..1 << 10)
+        .filter(|i: &usize| i.count_ones().is_multiple_of(2) || i.is_multiple_of(3))
+        .collect_vec();
+    let primes = prime_implicants(&minterms, &[]);
+    let (cover, minimal) = minimal_cover(10, &minterms, &primes);
+    assert!(!minimal);
+    assert!(minterms.iter().all(|&m| cover.iter().any(|p| p.covers(m))));
+}
+
+fn parse_indices(s: &str) -> Option<Vec<usize>> {
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse::<usize>().ok())
+        .collect()
+}
+
+/// Indices after `prefix` and a separator, like `m 1 3`, `m: 1, 3` or `m(1, 3)`. At least one
+/// index is required, so that `m` and `m1` are read as propositions.
+fn parse_index_list(line: &str, prefix: char) -> Option<Vec<usize>> {
+    let rest = line.strip_prefix(prefix)?;
+    let rest = if let Some(rest) = rest.strip_prefix('(') {
+        rest.strip_suffix(')')?
+    } else if let Some(rest) = rest.strip_prefix(':') {
+        rest
+    } else if rest.starts_with(char::is_whitespace) {
+        rest
+    } else {
+        return None;
+    };
+    parse_indices(rest).filter(|indices| !indices.is_empty())
+}
+
+#[test]
+fn test_parse_index_list() {
+    assert_eq!(parse_index_list("m 1 3", 'm'), Some(vec![1, 3]));
+    assert_eq!(parse_index_list("m(1, 3)", 'm'), Some(vec![1, 3]));
+    assert_eq!(parse_index_list("d: 2", 'd'), Some(vec![2]));
+    assert_eq!(parse_index_list("m", 'm'), None);
+    assert_eq!(parse_index_list("m1", 'm'), None);
+    assert_eq!(parse_index_list("m ", 'm'), None);
+    assert_eq!(parse_index_list("m ∧ P", 'm'), None);
+}
+
+/// Function to minimize.
+struct Specification {
+    variables: Vec<String>,
+    minterms: Vec<usize>,
+    dont_cares: Vec<usize>,
+}
+
+fn parse_specification(input: &str) -> Result<Specification, String> {
+    let lines = input
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect_vec();
+    // an index list is tried before a formula
+    let (dont_cares, lines) = match lines.split_last() {
+        Some((last, rest)) if !rest.is_empty() => match parse_index_list(last, 'd') {
+            Some(dont_cares) => (dont_cares, rest),
+            None => (vec![], &lines[..]),
+        },
+        _ => (vec![], &lines[..]),
+    };
+    let (variables, minterms) = match lines {
+        [] => return Err("输入为空.".to_string()),
+        [line] if parse_index_list(line, 'm').is_some() => {
+            let minterms = parse_index_list(line, 'm').unwrap();
+            let max = minterms.iter().chain(&dont_cares).max().copied();
+            let n = max.map_or(1, |max| (usize::BITS - max.leading_zeros()).max(1) as usize);
+            if n > MAX_VARIABLE_COUNT {
+                return Err(format!("编号需要超过 {MAX_VARIABLE_COUNT} 个变元."));
+            }
+            let variables = (0..n)
+                .map(|i| char::from(b'A' + i as u8).to_string())
+                .collect_vec();
+            (variables, minterms)
+        }
+        lines => {
+            let formula = lines.join("\n");
+            let expr = Expr::parse(formula.as_str()).map_err(|e| syntax_error(&e))?;
+            let variables = expr
+                .propositions()
+                .into_iter()
+                .sorted()
+                .map(|p| p.to_string())
+                .collect_vec();
+            if variables.len() > MAX_VARIABLE_COUNT {
+                return Err(format!("公式含有超过 {MAX_VARIABLE_COUNT} 个命题变元."));
+            }
+            (variables, expr.truth_table().minterm_indices())
+        }
+    };
+    let n = variables.len();
+    if let Some(index) = minterms.iter().chain(&dont_cares).find(|&&i| i >> n != 0) {
+        return Err(format!("编号 {index} 超出范围."));
+    }
+    let minterms = minterms.into_iter().sorted().dedup().collect_vec();
+    let dont_cares = dont_cares
+        .into_iter()
+        .filter(|i| !minterms.contains(i))
+        .sorted()
+        .dedup()
+        .collect_vec();
+    Ok(Specification {
+        variables,
+        minterms,
+        dont_cares,
+    })
+}
+
+fn sum_of_products_to_tex(terms: &[Implicant], variables: &[String]) -> String {
+    if terms.is_empty() {
+        return r"\bot".to_string();
+    }
+    terms
+        .iter()
+        .map(|term| {
+            if terms.len() > 1 && term.literal_count(variables.len()) > 1 {
+                format!(r"\left({}\right)", term.to_tex(variables))
+            } else {
+                term.to_tex(variables)
+            }
+        })
+        .join(r" \lor ")
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct QuineMcCluskeySolver;
+
+impl Solver for QuineMcCluskeySolver {
+    fn id(&self) -> String {
+        "qmc".to_string()
+    }
+
+    fn title(&self) -> String {
+        "奎因-麦克拉斯基算法化简逻辑函数".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入命题公式, 或以 m 开头的极小项编号, 如 m 1 3 或 m(1, 3). 可在末行以同样格式给出以 d 开头的无关项编号.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "m 4 8 10 11 12 15\nd 9 14".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let Specification {
+            variables,
+            minterms,
+            dont_cares,
+        } = match parse_specification(&input) {
+            Ok(specification) => specification,
+            Err(e) => return self.render_error(&e),
+        };
+        let n = variables.len();
+        let primes = prime_implicants(&minterms, &dont_cares);
+        let essential = essential_prime_implicants(&minterms, &primes);
+        let (cover, minimal) = minimal_cover(n, &minterms, &primes);
+        let function = format!(
+            r"F({}) = \sum m({}){}",
+            variables.join(", "),
+            minterms.iter().join(", "),
+            if dont_cares.is_empty() {
+                String::new()
+            } else {
+                format!(r" + \sum d({})", dont_cares.iter().join(", "))
+            }
+        );
+        let (class_name, style_val) = style_str! {
+            thead > tr {
+                border-top: 1px solid #333;
+                border-bottom: 1px solid #333;
+            }
+
+            tbody > tr:last-child {
+                border-bottom: 1px solid #333;
+            }
+
+            th:first-child, td:first-child {
+                border-left: 1px solid #333;
+            }
+
+            th:last-child, td:last-child {
+                border-right: 1px solid #333;
+            }
+
+            th, td {
+                text-align: center;
+                padding: 0.3rem 1rem;
+            }
+
+            tr.essential {
+                background-color: rgb(205, 233, 255);
+            }
+        };
+        view! {
+            class = class_name,
+            <Style> {style_val} </Style>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "逻辑函数" </p>
+                <KaTeX expr={ function } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "质蕴涵项表" </p>
+                {
+                    if minterms.is_empty() {
+                        view! { <p> "没有极小项." </p> }.into_view()
+                    } else {
+                        view! {
+                            class = class_name,
+                            <table>
+                                <thead>
+                                    <tr>
+                                        <th> "质蕴涵项" </th>
+                                        <th> "编码" </th>
+                                        {
+                                            minterms.iter().map(|m| view! {
+                                                class = class_name,
+                                                <th><KaTeX expr={ format!("m_{{{m}}}") } /></th>
+                                            }).collect_vec()
+                                        }
+                                    </tr>
+                                </thead>
+                                <tbody> {
+                                    primes.iter().map(|prime| view! {
+                                        class = class_name,
+                                        <tr class:essential={ essential.contains(prime) }>
+                                            <td><KaTeX expr={ prime.to_tex(&variables) } /></td>
+                                            <td><code> { prime.pattern(n) } </code></td>
+                                            {
+                                                minterms.iter().map(|m| view! {
+                                                    class = class_name,
+                                                    <td> { if prime.covers(*m) { "✕" } else { "" } } </td>
+                                                }).collect_vec()
+                                            }
+                                        </tr>
+                                    }).collect_vec()
+                                } </tbody>
+                            </table>
+                            <p class="mt-2"> "高亮行为必要质蕴涵项." </p>
+                        }
+                        .into_view()
+                    }
+                }
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "最简与或式" </p>
+                <KaTeX expr={ format!("F = {}", sum_of_products_to_tex(&cover, &variables)) } />
+                {
+                    (!minimal).then(|| view! {
+                        <p class="mt-2"> "质蕴涵项过多, 搜索未完成, 结果可能不是最简的." </p>
+                    })
+                }
+            </div>
+        }
+        .into_view()
+    }
+}
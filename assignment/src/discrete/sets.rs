@@ -0,0 +1,366 @@
+use std::collections::{BTreeSet, HashMap};
+
+use indoc::*;
+use itertools::Itertools;
+use leptos::*;
+use num::BigInt;
+use shiyanyi::*;
+
+/// Power sets and Cartesian products with more elements are not computed.
+const MAX_RESULT_LEN: usize = 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Element {
+    Number(BigInt),
+    Atom(String),
+    Tuple(Vec<Element>),
+    Set(BTreeSet<Element>),
+}
+
+impl Element {
+    fn parse(s: &str) -> Result<Self, String> {
+        if let Ok(n) = s.parse::<BigInt>() {
+            Ok(Element::Number(n))
+        } else if !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        {
+            Ok(Element::Atom(s.to_string()))
+        } else {
+            Err(format!("非法元素 `{s}`."))
+        }
+    }
+
+    fn to_tex(&self) -> String {
+        match self {
+            Element::Number(n) => n.to_string(),
+            Element::Atom(atom) => format!(r"\mathrm{{{}}}", atom.replace('_', r"\_")),
+            Element::Tuple(elements) => {
+                format!(
+                    r"\left({}\right)",
+                    elements.iter().map(Element::to_tex).join(", ")
+                )
+            }
+            Element::Set(set) => set_to_tex(set),
+        }
+    }
+}
+
+fn set_to_tex(set: &BTreeSet<Element>) -> String {
+    if set.is_empty() {
+        r"\varnothing".to_string()
+    } else {
+        format!(
+            r"\left\{{{}\right\}}",
+            set.iter().map(Element::to_tex).join(", ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SetOperator {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+    CartesianProduct,
+}
+
+impl SetOperator {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '∪' | '|' => Some(SetOperator::Union),
+            '∩' | '&' => Some(SetOperator::Intersection),
+            '\\' | '-' => Some(SetOperator::Difference),
+            '△' | '^' => Some(SetOperator::SymmetricDifference),
+            '×' | '*' => Some(SetOperator::CartesianProduct),
+            _ => None,
+        }
+    }
+
+    /// `∩` and `×` bind tighter than the others.
+    fn precedence(&self) -> u8 {
+        match self {
+            SetOperator::Intersection | SetOperator::CartesianProduct => 2,
+            _ => 1,
+        }
+    }
+
+    fn to_tex(self) -> &'static str {
+        match self {
+            SetOperator::Union => r"\cup",
+            SetOperator::Intersection => r"\cap",
+            SetOperator::Difference => r"\setminus",
+            SetOperator::SymmetricDifference => r"\bigtriangleup",
+            SetOperator::CartesianProduct => r"\times",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum SetExpr {
+    Name(String),
+    PowerSet(Box<SetExpr>),
+    BinOp {
+        lhs: Box<SetExpr>,
+        op: SetOperator,
+        rhs: Box<SetExpr>,
+    },
+}
+
+impl SetExpr {
+    fn parse(input: &str) -> Result<Self, String> {
+        let mut chars = input.chars().filter(|c| !c.is_whitespace()).peekable();
+        let expr = Self::parse_binop(&mut chars, 1)?;
+        match chars.next() {
+            None => Ok(expr),
+            Some(c) => Err(format!("意外的字符 `{c}`.")),
+        }
+    }
+
+    /// Operators with at least `min_precedence`, left associative.
+    fn parse_binop(
+        chars: &mut std::iter::Peekable<impl Iterator<Item = char>>,
+        min_precedence: u8,
+    ) -> Result<Self, String> {
+        let mut lhs = if min_precedence < 2 {
+            Self::parse_binop(chars, 2)?
+        } else {
+            Self::parse_primary(chars)?
+        };
+        while let Some(op) = chars
+            .peek()
+            .and_then(|c| SetOperator::from_char(*c))
+            .filter(|op| op.precedence() == min_precedence)
+        {
+            chars.next();
+            let rhs = if min_precedence < 2 {
+                Self::parse_binop(chars, 2)?
+            } else {
+                Self::parse_primary(chars)?
+            };
+            lhs = SetExpr::BinOp {
+                lhs: Box::new(lhs),
+                op,
+                rhs: Box::new(rhs),
+            };
+        }
+        Ok(lhs)
+    }
+
+    fn parse_primary(
+        chars: &mut std::iter::Peekable<impl Iterator<Item = char>>,
+    ) -> Result<Self, String> {
+        match chars.next() {
+            Some('(') => {
+                let expr = Self::parse_binop(chars, 1)?;
+                match chars.next() {
+                    Some(')') => Ok(expr),
+                    _ => Err("缺少 `)`.".to_string()),
+                }
+            }
+            Some(c) if c.is_alphanumeric() || c == '_' => {
+                let mut name = c.to_string();
+                while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric() || **c == '_') {
+                    name.push(c);
+                    chars.next();
+                }
+                // `P(A)` is the power set
+                if chars.peek() == Some(&'(') && (name == "P" || name == "𝒫") {
+                    chars.next();
+                    let expr = Self::parse_binop(chars, 1)?;
+                    return match chars.next() {
+                        Some(')') => Ok(SetExpr::PowerSet(Box::new(expr))),
+                        _ => Err("缺少 `)`.".to_string()),
+                    };
+                }
+                Ok(SetExpr::Name(name))
+            }
+            Some(c) => Err(format!("意外的字符 `{c}`.")),
+            None => Err("表达式意外结束.".to_string()),
+        }
+    }
+
+    fn evaluate(
+        &self,
+        sets: &HashMap<String, BTreeSet<Element>>,
+    ) -> Result<BTreeSet<Element>, String> {
+        match self {
+            SetExpr::Name(name) => sets
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("集合 `{name}` 未定义.")),
+            SetExpr::PowerSet(expr) => {
+                let set = expr.evaluate(sets)?;
+                if set.len() >= usize::BITS as usize || 1 << set.len() > MAX_RESULT_LEN {
+                    return Err(format!("幂集的元素超过 {MAX_RESULT_LEN} 个."));
+                }
+                Ok(set
+                    .iter()
+                    .powerset()
+                    .map(|subset| Element::Set(subset.into_iter().cloned().collect()))
+                    .collect())
+            }
+            SetExpr::BinOp { lhs, op, rhs } => {
+                let (lhs, rhs) = (lhs.evaluate(sets)?, rhs.evaluate(sets)?);
+                Ok(match op {
+                    SetOperator::Union => lhs.union(&rhs).cloned().collect(),
+                    SetOperator::Intersection => lhs.intersection(&rhs).cloned().collect(),
+                    SetOperator::Difference => lhs.difference(&rhs).cloned().collect(),
+                    SetOperator::SymmetricDifference => {
+                        lhs.symmetric_difference(&rhs).cloned().collect()
+                    }
+                    SetOperator::CartesianProduct => {
+                        if lhs.len().saturating_mul(rhs.len()) > MAX_RESULT_LEN {
+                            return Err(format!("笛卡尔积的元素超过 {MAX_RESULT_LEN} 个."));
+                        }
+                        lhs.iter()
+                            .cartesian_product(rhs.iter())
+                            .map(|(a, b)| Element::Tuple(vec![a.clone(), b.clone()]))
+                            .collect()
+                    }
+                })
+            }
+        }
+    }
+
+    fn to_tex(&self) -> String {
+        let paren = |expr: &SetExpr, min_precedence: u8| match expr {
+            SetExpr::BinOp { op, .. } if op.precedence() < min_precedence => {
+                format!(r"\left({}\right)", expr.to_tex())
+            }
+            _ => expr.to_tex(),
+        };
+        match self {
+            SetExpr::Name(name) => name.clone(),
+            SetExpr::PowerSet(expr) => format!(r"\mathcal{{P}}\left({}\right)", expr.to_tex()),
+            SetExpr::BinOp { lhs, op, rhs } => format!(
+                "{} {} {}",
+                paren(lhs, op.precedence()),
+                op.to_tex(),
+                paren(rhs, op.precedence() + 1)
+            ),
+        }
+    }
+}
+
+/// Parse a definition like `A = {1, 2, 3}`.
+fn parse_definition(line: &str) -> Result<(String, BTreeSet<Element>), String> {
+    let (name, set) = line
+        .split_once('=')
+        .ok_or_else(|| format!("非法定义 `{line}`."))?;
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        return Err(format!("非法集合名 `{name}`."));
+    }
+    let set = set
+        .trim()
+        .strip_prefix('{')
+        .and_then(|set| set.strip_suffix('}'))
+        .ok_or_else(|| format!("集合 `{name}` 的元素应以花括号括起."))?;
+    let set = set
+        .split(',')
+        .map(|element| element.trim())
+        .filter(|element| !element.is_empty())
+        .map(Element::parse)
+        .collect::<Result<_, _>>()?;
+    Ok((name.to_string(), set))
+}
+
+#[test]
+fn test_set_operations() {
+    let sets = ["A = {1, 2, 3}", "B = {2, 3, 4}"]
+        .into_iter()
+        .map(|line| parse_definition(line).unwrap())
+        .collect::<HashMap<_, _>>();
+    let evaluate = |input: &str| {
+        SetExpr::parse(input)
+            .and_then(|expr| expr.evaluate(&sets))
+            .map(|set| set.iter().map(Element::to_tex).collect_vec())
+    };
+    assert_eq!(evaluate("A ∪ B").unwrap(), ["1", "2", "3", "4"]);
+    assert_eq!(evaluate("A ∩ B").unwrap(), ["2", "3"]);
+    assert_eq!(evaluate(r"A \ B").unwrap(), ["1"]);
+    assert_eq!(evaluate("A △ B").unwrap(), ["1", "4"]);
+    // ∩ binds tighter than ∪
+    assert_eq!(evaluate("A △ A ∩ B").unwrap(), ["1"]);
+    assert_eq!(evaluate("(A △ A) ∩ B").unwrap(), Vec::<String>::new());
+    assert_eq!(evaluate("P(A ∩ B)").unwrap().len(), 4);
+    assert_eq!(evaluate("A × B").unwrap().len(), 9);
+    assert_eq!(evaluate("A ∪ C"), Err("集合 `C` 未定义.".to_string()));
+    assert!(evaluate("A ∪").is_err());
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SetOperationSolver;
+
+impl Solver for SetOperationSolver {
+    fn id(&self) -> String {
+        "sets".to_string()
+    }
+
+    fn title(&self) -> String {
+        "集合运算".to_string()
+    }
+
+    fn description(&self) -> View {
+        "每行定义一个集合 (如 A = {1, 2, 3}), 末行输入集合表达式. 支持 ∪ (|), ∩ (&), \\ (-), △ (^), × (*) 和幂集 P(A).".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        indoc! {"
+            A = {1, 2, 3}
+            B = {2, 3, 4}
+            (A ∪ B) \\ (A ∩ B)
+        "}
+        .to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let lines = input
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect_vec();
+        let Some((expression, definitions)) = lines.split_last() else {
+            return self.render_error("输入为空.");
+        };
+        let definitions = match definitions
+            .iter()
+            .map(|line| parse_definition(line))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(definitions) => definitions,
+            Err(e) => return self.render_error(&e),
+        };
+        let sets = definitions.iter().cloned().collect::<HashMap<_, _>>();
+        let expr = match SetExpr::parse(expression) {
+            Ok(expr) => expr,
+            Err(e) => return self.render_error(&format!("集合表达式无法解析: {e}")),
+        };
+        let result = match expr.evaluate(&sets) {
+            Ok(result) => result,
+            Err(e) => return self.render_error(&e),
+        };
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "集合" </p>
+                <KaTeX display_mode=true fleqn=true expr={
+                    format!(
+                        r"\begin{{align*}} {} \end{{align*}}",
+                        definitions
+                            .iter()
+                            .map(|(name, set)| format!("{name} &= {}", set_to_tex(set)))
+                            .join(r" \\ ")
+                    )
+                } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "运算结果" </p>
+                <KaTeX expr={ format!("{} = {}", expr.to_tex(), set_to_tex(&result)) } />
+                <KaTeX expr={ format!(r"\left|{}\right| = {}", expr.to_tex(), result.len()) } />
+            </div>
+        }
+        .into_view()
+    }
+}
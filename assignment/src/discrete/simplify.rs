@@ -0,0 +1,232 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+use leptos::*;
+use shiyanyi::*;
+
+use super::exp1::{syntax_error, Expr, Operator};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Law {
+    DoubleNegation,
+    Identity,
+    Domination,
+    Idempotent,
+    Absorption,
+    Contradiction,
+    ExcludedMiddle,
+    DeMorgan,
+}
+
+impl Display for Law {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Law::DoubleNegation => "双重否定律",
+            Law::Identity => "同一律",
+            Law::Domination => "零律",
+            Law::Idempotent => "幂等律",
+            Law::Absorption => "吸收律",
+            Law::Contradiction => "矛盾律",
+            Law::ExcludedMiddle => "排中律",
+            Law::DeMorgan => "德摩根律",
+        })
+    }
+}
+
+/// Whether `expr` is `a ∨ b` (`a ∧ b` if `conjunction`) with `target` as either operand.
+fn has_operand(expr: &Expr, conjunction: bool, target: &Expr) -> bool {
+    match expr {
+        Expr::BinOp {
+            lhs,
+            op: Operator::Conjunction,
+            rhs,
+        } if conjunction => **lhs == *target || **rhs == *target,
+        Expr::BinOp {
+            lhs,
+            op: Operator::Disjunction,
+            rhs,
+        } if !conjunction => **lhs == *target || **rhs == *target,
+        _ => false,
+    }
+}
+
+fn is_negation_of(expr: &Expr, target: &Expr) -> bool {
+    matches!(expr, Expr::Negation(inner) if **inner == *target)
+}
+
+/// The law applicable to `expr` itself and the result, if any.
+fn rewrite_root(expr: &Expr) -> Option<(Law, Expr)> {
+    match expr {
+        Expr::Negation(inner) => match &**inner {
+            Expr::Negation(e) => Some((Law::DoubleNegation, (**e).clone())),
+            Expr::BinOp { lhs, op, rhs } => {
+                let op = match op {
+                    Operator::Conjunction => Operator::Disjunction,
+                    Operator::Disjunction => Operator::Conjunction,
+                    _ => return None,
+                };
+                Some((
+                    Law::DeMorgan,
+                    Expr::binop(
+                        Expr::negation((**lhs).clone()),
+                        op,
+                        Expr::negation((**rhs).clone()),
+                    ),
+                ))
+            }
+            _ => None,
+        },
+        Expr::BinOp { lhs, op, rhs } => {
+            let conjunction = match op {
+                Operator::Conjunction => true,
+                Operator::Disjunction => false,
+                _ => return None,
+            };
+            // `T` is the identity of `∧` and `F` of `∨`, the other one dominates
+            for (a, b) in [(lhs, rhs), (rhs, lhs)] {
+                match **a {
+                    Expr::Literal(v) if v == conjunction => {
+                        return Some((Law::Identity, (**b).clone()))
+                    }
+                    Expr::Literal(v) => return Some((Law::Domination, Expr::Literal(v))),
+                    _ => {}
+                }
+            }
+            if lhs == rhs {
+                return Some((Law::Idempotent, (**lhs).clone()));
+            }
+            for (a, b) in [(lhs, rhs), (rhs, lhs)] {
+                if has_operand(b, !conjunction, a) {
+                    return Some((Law::Absorption, (**a).clone()));
+                }
+            }
+            if is_negation_of(lhs, rhs) || is_negation_of(rhs, lhs) {
+                return Some(if conjunction {
+                    (Law::Contradiction, Expr::Literal(false))
+                } else {
+                    (Law::ExcludedMiddle, Expr::Literal(true))
+                });
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+/// Applies one law to the outermost applicable subformula, left to right.
+fn rewrite_once(expr: &Expr) -> Option<(Law, Expr)> {
+    if let Some(step) = rewrite_root(expr) {
+        return Some(step);
+    }
+    match expr {
+        Expr::Literal(_) | Expr::Proposition(_) => None,
+        Expr::Negation(inner) => {
+            rewrite_once(inner).map(|(law, inner)| (law, Expr::negation(inner)))
+        }
+        Expr::BinOp { lhs, op, rhs } => {
+            if let Some((law, lhs)) = rewrite_once(lhs) {
+                return Some((law, Expr::binop(lhs, op.clone(), (**rhs).clone())));
+            }
+            rewrite_once(rhs).map(|(law, rhs)| (law, Expr::binop((**lhs).clone(), op.clone(), rhs)))
+        }
+    }
+}
+
+/// Applies laws until none applies, returning each law with the formula after it.
+fn simplify(expr: &Expr) -> Vec<(Law, Expr)> {
+    let mut steps: Vec<(Law, Expr)> = Vec::new();
+    while let Some(step) = rewrite_once(steps.last().map_or(expr, |(_, e)| e)) {
+        steps.push(step);
+    }
+    steps
+}
+
+#[test]
+fn test_simplify() {
+    let simplified = |input: &str| {
+        let expr = Expr::parse(input).unwrap();
+        simplify(&expr)
+            .last()
+            .map_or(expr.to_tex(), |(_, e)| e.to_tex())
+    };
+    let steps = simplify(&Expr::parse("P ∧ (P ∨ Q)").unwrap());
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0].0, Law::Absorption);
+    assert_eq!(steps[0].1, Expr::Proposition("P".to_string()));
+    assert_eq!(simplified("¬(¬P ∨ ¬Q) ∧ T"), r"P \land Q");
+    assert_eq!(simplified("(P ∨ P) ∧ ¬P"), r"\mathbf{F}");
+    assert_eq!(simplified("P → Q"), r"P \to Q");
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct BooleanSimplificationSolver;
+
+impl Solver for BooleanSimplificationSolver {
+    fn id(&self) -> String {
+        "simplify".to_string()
+    }
+
+    fn title(&self) -> String {
+        "利用基本等价式化简命题公式".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入命题公式. 反复应用同一律, 零律, 幂等律, 吸收律, 矛盾律, 排中律, 双重否定律和德摩根律, 直到不能继续化简.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "¬(¬P ∨ ¬(P ∨ Q)) ∧ T".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let expr = match Expr::parse(&input) {
+            Ok(expr) => expr,
+            Err(e) => return self.render_error(&syntax_error(&e)),
+        };
+        let steps = simplify(&expr);
+        let simplified = steps.last().map_or(&expr, |(_, e)| e);
+        let propositions = expr.propositions().into_iter().sorted().collect_vec();
+        let results = |expr: &Expr| {
+            expr.truth_table_with_propositions(&propositions)
+                .iter()
+                .map(|(_, result)| *result)
+                .collect_vec()
+        };
+        let equivalent = results(&expr) == results(simplified);
+        let steps_tex = format!(
+            r"\begin{{align*}} & {} {} \end{{align*}}",
+            expr.to_tex(),
+            steps
+                .iter()
+                .map(|(law, e)| format!(r"\\[1ex] \iff & {} && \text{{({law})}}", e.to_tex()))
+                .join(" ")
+        );
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "化简结果" </p>
+                <KaTeX expr={ simplified.to_tex() } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "化简过程" </p>
+                {
+                    if steps.is_empty() {
+                        view! { <p> "没有可以应用的等价式." </p> }.into_view()
+                    } else {
+                        view! { <KaTeX expr={ steps_tex } /> }.into_view()
+                    }
+                }
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "真值表验证" </p>
+                <p> {
+                    if equivalent {
+                        "化简前后的公式在所有赋值下真值相同."
+                    } else {
+                        "化简前后的公式真值不同."
+                    }
+                } </p>
+            </div>
+        }
+        .into_view()
+    }
+}
@@ -0,0 +1,139 @@
+use itertools::{repeat_n, Itertools};
+use leptos::*;
+use num::{BigRational, One, Zero};
+use shiyanyi::*;
+
+use super::exp4::{graph_diagram, parse_graph, Graph};
+use crate::{
+    common::{Matrix, ToTex},
+    linalg::Determinant,
+};
+
+/// `L = D - A` of the undirected graph.
+fn laplacian(matrix: &Matrix<bool>) -> Matrix<BigRational> {
+    let n = matrix.len();
+    let mut laplacian =
+        Matrix(repeat_n(repeat_n(BigRational::zero(), n).collect_vec(), n).collect_vec());
+    for i in 0..n {
+        for j in 0..n {
+            if matrix[i][j] {
+                laplacian[i][j] -= BigRational::one();
+                laplacian[i][i] += BigRational::one();
+            }
+        }
+    }
+    laplacian
+}
+
+/// `L` with the last row and column deleted.
+fn reduced_laplacian(laplacian: &Matrix<BigRational>) -> Matrix<BigRational> {
+    let n = laplacian.len();
+    laplacian.minor(n - 1, n - 1)
+}
+
+#[test]
+fn test_spanning_tree_count() {
+    let complete = |n: usize| {
+        Matrix(
+            (0..n)
+                .map(|i| (0..n).map(|j| i != j).collect_vec())
+                .collect_vec(),
+        )
+    };
+    let count = |matrix: &Matrix<bool>| reduced_laplacian(&laplacian(matrix)).determinant();
+    assert_eq!(count(&complete(4)), BigRational::from_integer(16.into()));
+    assert_eq!(count(&complete(5)), BigRational::from_integer(125.into()));
+    assert_eq!(count(&complete(1)), BigRational::one());
+    // two disjoint edges
+    let matrix = Matrix(vec![
+        vec![false, true, false, false],
+        vec![true, false, false, false],
+        vec![false, false, false, true],
+        vec![false, false, true, false],
+    ]);
+    assert_eq!(count(&matrix), BigRational::zero());
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SpanningTreeCountSolver;
+
+impl Solver for SpanningTreeCountSolver {
+    fn id(&self) -> String {
+        "spanning-count".to_string()
+    }
+
+    fn title(&self) -> String {
+        "生成树计数".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入无向图, 格式同实验四 (忽略边权). 由矩阵树定理, 生成树的个数等于拉普拉斯矩阵删去一行一列后的行列式.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        "4\n0 1\n0 2\n0 3\n1 2\n1 3\n2 3".to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let lines = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect_vec();
+        let Graph {
+            directed,
+            vertex_count,
+            matrix,
+            seed,
+            ..
+        } = match parse_graph(&lines) {
+            Ok(graph) => graph,
+            Err(e) => return self.render_error(e),
+        };
+        if directed {
+            return self.render_error("图应为无向图.");
+        }
+        if vertex_count == 0 {
+            return self.render_error("图至少应有一个节点.");
+        }
+        let diagram = graph_diagram(&matrix, false, &[], &vec![false; vertex_count]);
+        let laplacian = laplacian(&matrix);
+        let reduced = reduced_laplacian(&laplacian);
+        let count = reduced.determinant();
+        let cofactor = if vertex_count == 1 {
+            // the empty determinant
+            format!(r"\tau(G) = {}", count.to_tex())
+        } else {
+            format!(
+                r"\tau(G) = \begin{{vmatrix}} {} \end{{vmatrix}} = {}",
+                reduced.to_tex(),
+                count.to_tex()
+            )
+        };
+        view! {
+            {
+                seed.map(|(_, _, header)| view! {
+                    <div class="mb-10">
+                        <p> "输入 " <code class="font-mono"> { header } </code> " 可以重新生成相同的图." </p>
+                    </div>
+                })
+            }
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "图" </p>
+                <div inner_html={ diagram }></div>
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "拉普拉斯矩阵" </p>
+                <KaTeX expr={ format!(r"\bm L = \begin{{bmatrix}} {} \end{{bmatrix}}", laplacian.to_tex()) } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "删去最后一行一列的行列式" </p>
+                <KaTeX expr={ cofactor } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "生成树个数" </p>
+                <p class="text-xl font-bold"> { count.to_string() } </p>
+            </div>
+        }
+        .into_view()
+    }
+}
@@ -0,0 +1,354 @@
+use indoc::*;
+use itertools::{repeat_n, Itertools};
+use leptos::*;
+use num::{integer::lcm, BigInt, BigRational, Integer, One, Signed, ToPrimitive, Zero};
+use shiyanyi::*;
+
+use crate::common::*;
+
+fn mul(a: &Matrix<BigRational>, b: &Matrix<BigRational>) -> Matrix<BigRational> {
+    let (m, l) = a.shape();
+    let (_, n) = b.shape();
+    Matrix(
+        (0..m)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        (0..l)
+                            .map(|k| &a[i][k] * &b[k][j])
+                            .fold(BigRational::zero(), |acc, x| acc + x)
+                    })
+                    .collect_vec()
+            })
+            .collect_vec(),
+    )
+}
+
+fn trace(matrix: &Matrix<BigRational>) -> BigRational {
+    (0..matrix.shape().0)
+        .map(|i| matrix[i][i].clone())
+        .fold(BigRational::zero(), |acc, x| acc + x)
+}
+
+/// Coefficients of `det(A - λI)` in ascending order of degree, computed with the
+/// Faddeev–LeVerrier algorithm.
+pub fn characteristic_polynomial(matrix: &Matrix<BigRational>) -> Vec<BigRational> {
+    let (n, _) = matrix.shape();
+    // coefficients of det(λI - A)
+    let mut c = repeat_n(BigRational::zero(), n + 1).collect_vec();
+    c[n] = BigRational::one();
+    let mut m = Matrix(repeat_n(repeat_n(BigRational::zero(), n).collect_vec(), n).collect_vec());
+    for k in 1..=n {
+        // M_k = A M_{k-1} + c_{n-k+1} I
+        m = mul(matrix, &m);
+        for i in 0..n {
+            m[i][i] += &c[n - k + 1];
+        }
+        // c_{n-k} = -tr(A M_k) / k
+        c[n - k] = -trace(&mul(matrix, &m)) / BigRational::from_integer(k.into());
+    }
+    // det(A - λI) = (-1)^n det(λI - A)
+    if n.is_odd() {
+        for x in &mut c {
+            *x = -&*x;
+        }
+    }
+    c
+}
+
+#[test]
+fn test_characteristic_polynomial() {
+    let matrix = "-1 1 0\n-4 3 0\n1 0 2"
+        .parse::<Matrix<BigRational>>()
+        .unwrap();
+    // (2 - λ)(1 - λ)^2 = -λ^3 + 4λ^2 - 5λ + 2
+    assert_eq!(
+        characteristic_polynomial(&matrix),
+        [2, -5, 4, -1]
+            .into_iter()
+            .map(|x| BigRational::from_integer(x.into()))
+            .collect_vec()
+    );
+    let (roots, rest) = rational_roots(&characteristic_polynomial(&matrix)).unwrap();
+    assert_eq!(
+        roots,
+        [(1, 2), (2, 1)]
+            .into_iter()
+            .map(|(r, k)| (BigRational::from_integer(r.into()), k))
+            .collect_vec()
+    );
+    assert_eq!(rest.len(), 1);
+}
+
+fn evaluate(polynomial: &[BigRational], x: &BigRational) -> BigRational {
+    polynomial
+        .iter()
+        .rev()
+        .fold(BigRational::zero(), |acc, c| acc * x + c)
+}
+
+/// Divide `polynomial` by `(λ - root)`, the remainder is assumed to be zero.
+fn divide_linear(polynomial: &[BigRational], root: &BigRational) -> Vec<BigRational> {
+    let mut quotient = repeat_n(BigRational::zero(), polynomial.len() - 1).collect_vec();
+    let mut carry = BigRational::zero();
+    for k in (1..polynomial.len()).rev() {
+        carry = &polynomial[k] + carry * root;
+        quotient[k - 1] = carry.clone();
+    }
+    quotient
+}
+
+/// Positive divisors of `n`, `None` if `n` is too large to enumerate.
+fn divisors(n: &BigInt) -> Option<Vec<BigInt>> {
+    let n = n.abs().to_u64()?;
+    if n > 1_000_000_000_000 {
+        return None;
+    }
+    let mut divisors = Vec::new();
+    let mut i = 1;
+    while i * i <= n {
+        if n % i == 0 {
+            divisors.push(BigInt::from(i));
+            if i * i != n {
+                divisors.push(BigInt::from(n / i));
+            }
+        }
+        i += 1;
+    }
+    Some(divisors)
+}
+
+/// Roots with their multiplicities, and the remaining factor.
+type RationalFactorization = (Vec<(BigRational, usize)>, Vec<BigRational>);
+
+/// Rational roots with their multiplicities by the rational root theorem, along with the
+/// remaining factor. `None` if the coefficients are too large to search.
+fn rational_roots(polynomial: &[BigRational]) -> Option<RationalFactorization> {
+    let mut polynomial = polynomial.to_vec();
+    let mut roots = Vec::new();
+    let mut zero_multiplicity = 0;
+    while polynomial.len() > 1 && polynomial[0].is_zero() {
+        polynomial.remove(0);
+        zero_multiplicity += 1;
+    }
+    if zero_multiplicity > 0 {
+        roots.push((BigRational::zero(), zero_multiplicity));
+    }
+    if polynomial.len() > 1 {
+        let denom = polynomial
+            .iter()
+            .fold(BigInt::one(), |acc, c| lcm(acc, c.denom().clone()));
+        let integer = polynomial
+            .iter()
+            .map(|c| (c * BigRational::from_integer(denom.clone())).to_integer())
+            .collect_vec();
+        let ps = divisors(&integer[0])?;
+        let qs = divisors(integer.last().unwrap())?;
+        let candidates = ps
+            .iter()
+            .cartesian_product(qs.iter())
+            .flat_map(|(p, q)| {
+                let r = BigRational::new(p.clone(), q.clone());
+                [-r.clone(), r]
+            })
+            .unique()
+            .sorted();
+        for r in candidates {
+            let mut multiplicity = 0;
+            while polynomial.len() > 1 && evaluate(&polynomial, &r).is_zero() {
+                polynomial = divide_linear(&polynomial, &r);
+                multiplicity += 1;
+            }
+            if multiplicity > 0 {
+                roots.push((r, multiplicity));
+            }
+        }
+    }
+    roots.sort();
+    Some((roots, polynomial))
+}
+
+fn monomial_to_tex(degree: usize, variable: &str) -> String {
+    match degree {
+        0 => "".to_string(),
+        1 => variable.to_string(),
+        k => format!(r"{variable}^{{{k}}}"),
+    }
+}
+
+/// Coefficients are in ascending order of degree.
+pub(super) fn polynomial_to_tex(polynomial: &[BigRational], variable: &str) -> String {
+    let terms = polynomial
+        .iter()
+        .enumerate()
+        .rev()
+        .filter(|(_, c)| !c.is_zero())
+        .enumerate()
+        .map(|(i, (k, c))| {
+            let sign = if i == 0 {
+                c.sign_to_tex()
+            } else {
+                c.sign_to_tex_with_positive_sign()
+            };
+            if k == 0 {
+                format!("{sign} {}", c.abs().to_tex())
+            } else {
+                format!(
+                    "{sign} {}{}",
+                    c.abs().to_tex_ignore_one(),
+                    monomial_to_tex(k, variable)
+                )
+            }
+        })
+        .join(" ");
+    if terms.is_empty() {
+        "0".to_string()
+    } else {
+        terms
+    }
+}
+
+fn factorization_to_tex(
+    polynomial: &[BigRational],
+    roots: &[(BigRational, usize)],
+    rest: &[BigRational],
+) -> String {
+    let lead = polynomial.last().unwrap();
+    let factors = roots
+        .iter()
+        .map(|(r, k)| {
+            let factor = if r.is_zero() {
+                r"\lambda".to_string()
+            } else {
+                format!(
+                    r"\left(\lambda {} {}\right)",
+                    (-r).sign_to_tex_with_positive_sign(),
+                    r.abs().to_tex()
+                )
+            };
+            if *k == 1 {
+                factor
+            } else {
+                format!("{factor}^{{{k}}}")
+            }
+        })
+        .chain(if rest.len() > 1 {
+            let rest = rest.iter().map(|c| c / lead).collect_vec();
+            Some(format!(
+                r"\left({}\right)",
+                polynomial_to_tex(&rest, r"\lambda")
+            ))
+        } else {
+            None
+        })
+        .join(" ");
+    format!("{}{factors}", lead.to_tex_ignore_one())
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CharacteristicPolynomialSolver;
+
+impl Solver for CharacteristicPolynomialSolver {
+    fn id(&self) -> String {
+        "charpoly".to_string()
+    }
+
+    fn title(&self) -> String {
+        "特征多项式与特征值".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入元素为整数, 小数或分数的方阵. 对于二阶和三阶方阵, 求出其有理特征值.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        indoc! {"
+            -1 1 0
+            -4 3 0
+             1 0 2
+        "}
+        .to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let matrix = match input.parse::<Matrix<BigRational>>() {
+            Ok(matrix) => matrix,
+            Err(MatrixParseError::Empty) => return self.render_error("输入为空."),
+            Err(e) => return self.render_error(&e.to_string()),
+        };
+        let (m, n) = matrix.shape();
+        if m != n {
+            return self.render_error("矩阵不是方阵.");
+        }
+        let polynomial = characteristic_polynomial(&matrix);
+        let characteristic_polynomial = format!(
+            r"\left|\bm A - \lambda \bm E\right| = {}",
+            polynomial_to_tex(&polynomial, r"\lambda")
+        );
+        let eigenvalues = if n <= 3 {
+            match rational_roots(&polynomial) {
+                Some((roots, rest)) => {
+                    let factorization = format!(
+                        r"\left|\bm A - \lambda \bm E\right| = {}",
+                        factorization_to_tex(&polynomial, &roots, &rest)
+                    );
+                    let roots = roots
+                        .iter()
+                        .map(|(r, k)| {
+                            if *k == 1 {
+                                format!(r"\lambda = {}", r.to_tex())
+                            } else {
+                                format!(r"\lambda = {} \ (\text{{{k} 重}})", r.to_tex())
+                            }
+                        })
+                        .join(r",\quad ");
+                    view! {
+                        <div class="mb-10">
+                            <p class="font-bold mb-2"> "因式分解" </p>
+                            <KaTeX expr={ factorization } />
+                        </div>
+                        <div class="mb-10">
+                            <p class="font-bold mb-2"> "有理特征值" </p>
+                            {
+                                if roots.is_empty() {
+                                    view! { <p> "无有理特征值." </p> }.into_view()
+                                } else {
+                                    view! { <KaTeX expr={ roots } /> }.into_view()
+                                }
+                            }
+                            {
+                                if rest.len() > 1 {
+                                    view! { <p class="mt-2"> "其余特征值不是有理数." </p> }.into_view()
+                                } else {
+                                    ().into_view()
+                                }
+                            }
+                        </div>
+                    }
+                    .into_view()
+                }
+                None => view! {
+                    <div class="mb-10">
+                        <p class="font-bold mb-2"> "有理特征值" </p>
+                        <p> "系数过大, 未求有理特征值." </p>
+                    </div>
+                }
+                .into_view(),
+            }
+        } else {
+            ().into_view()
+        };
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "方阵" </p>
+                <KaTeX expr={ format!(r"\bm A = \begin{{pmatrix}}{}\end{{pmatrix}}", matrix.to_tex()) } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "特征多项式" </p>
+                <KaTeX expr={ characteristic_polynomial } />
+            </div>
+            { eigenvalues }
+        }
+        .into_view()
+    }
+}
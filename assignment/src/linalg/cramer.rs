@@ -0,0 +1,154 @@
+use indoc::*;
+use itertools::Itertools;
+use leptos::*;
+use num::{BigRational, Zero};
+use shiyanyi::*;
+
+use crate::common::*;
+use crate::linalg::{Determinant, LinearEquations};
+
+/// Coefficient matrix with column `col` replaced by the constant terms.
+fn substitute_col(lineq: &LinearEquations, col: usize) -> Matrix<BigRational> {
+    let (m, n) = lineq.shape();
+    let rows = (0..m).collect_vec();
+    lineq
+        .submatrix(&rows, &(0..col).collect_vec())
+        .hstack(&lineq.constants())
+        .and_then(|left| left.hstack(&lineq.submatrix(&rows, &(col + 1..n - 1).collect_vec())))
+        .expect("the parts have the same row count")
+}
+
+#[test]
+fn test_substitute_col() {
+    let lineq = "1 2 5\n3 4 6".parse::<LinearEquations>().unwrap();
+    assert_eq!(substitute_col(&lineq, 0), "5 2\n6 4".parse().unwrap());
+    assert_eq!(substitute_col(&lineq, 1), "1 5\n3 6".parse().unwrap());
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CramerSolver;
+
+impl Solver for CramerSolver {
+    fn id(&self) -> String {
+        "cramer".to_string()
+    }
+
+    fn title(&self) -> String {
+        "克拉默法则".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入元素为整数, 小数或分数的增广矩阵, 其系数矩阵须为方阵.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        indoc! {"
+            2  1 -5  1  8
+            1 -3  0 -6  9
+            0  2 -1  2 -5
+            1  4 -7  6  0
+        "}
+        .to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let matrix = match input.parse::<Matrix<BigRational>>() {
+            Ok(matrix) => matrix,
+            Err(MatrixParseError::Empty) => return self.render_error("输入为空."),
+            Err(e) => return self.render_error(&e.to_string()),
+        };
+        let (m, n) = matrix.shape();
+        if n < 2 {
+            return self.render_error("增广矩阵至少应有 2 列.");
+        }
+        if m != n - 1 {
+            return self.render_error("系数矩阵不是方阵.");
+        }
+        let lineq = LinearEquations(matrix);
+        let coefficient_matrix = lineq.coefficient_matrix();
+        let determinant = coefficient_matrix.determinant();
+        let d = format!(
+            r"D = \begin{{vmatrix}}{}\end{{vmatrix}} = {}",
+            coefficient_matrix.to_tex(),
+            determinant.to_tex()
+        );
+        if determinant.is_zero() {
+            return view! {
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "实线性方程组" </p>
+                    <KaTeX display_mode=true fleqn=true expr={ lineq.to_string() } />
+                </div>
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "系数行列式" </p>
+                    <KaTeX expr={ d } />
+                </div>
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "方程组的解" </p>
+                    <p> "系数行列式为零, 不能使用克拉默法则. 请使用 \"实线性方程组\" 求解." </p>
+                </div>
+            }
+            .into_view();
+        }
+        let decimal = create_rw_signal(false);
+        let digits = create_rw_signal(DEFAULT_DECIMAL_DIGITS);
+        let (d_i, x_i): (Vec<_>, Vec<_>) = (0..n - 1)
+            .map(|j| {
+                let substituted = substitute_col(&lineq, j);
+                let substituted_determinant = substituted.determinant();
+                (
+                    format!(
+                        r"D_{{{}}} &= \begin{{vmatrix}}{}\end{{vmatrix}} = {}",
+                        j + 1,
+                        substituted.to_tex(),
+                        substituted_determinant.to_tex()
+                    ),
+                    (j, substituted_determinant / &determinant),
+                )
+            })
+            .unzip();
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "实线性方程组" </p>
+                <KaTeX display_mode=true fleqn=true expr={ lineq.to_string() } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "系数行列式" </p>
+                <KaTeX expr={ d } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "替换常数项后的行列式" </p>
+                <KaTeX display_mode=true fleqn=true expr={
+                    format!(r"\begin{{align*}} {} \end{{align*}}", d_i.join(r" \\[3em] "))
+                } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "方程组的唯一解" <DecimalToggle decimal digits /> </p>
+                {
+                    move || {
+                        let x_i = x_i
+                            .iter()
+                            .map(|(j, x)| {
+                                format!(
+                                    r"x_{{{}}} &= \frac{{D_{{{}}}}}{{D}} {}",
+                                    j + 1,
+                                    j + 1,
+                                    if decimal() {
+                                        format!(r"\approx {}", x.to_decimal_string(digits()))
+                                    } else {
+                                        format!("= {}", x.to_tex())
+                                    }
+                                )
+                            })
+                            .join(r" \\[1ex] ");
+                        view! {
+                            <KaTeX display_mode=true fleqn=true expr={
+                                format!(r"\begin{{align*}} {} \end{{align*}}", x_i)
+                            } />
+                        }
+                    }
+                }
+            </div>
+        }
+        .into_view()
+    }
+}
@@ -0,0 +1,105 @@
+use indoc::*;
+use itertools::Itertools;
+use leptos::*;
+use num::BigRational;
+use shiyanyi::*;
+
+use crate::common::*;
+use crate::linalg::{pivot_columns, row_ops_summary, row_reduction_with_steps, steps_to_tex};
+
+#[test]
+fn test_row_echelon_form_pivots() {
+    for input in ["1 2 3\n4 5 6\n7 8 9", "0 2 4\n1 1 1", "0 0 1\n0 0 2\n1 3 0"] {
+        let matrix = input.parse::<Matrix<BigRational>>().unwrap();
+        let echelon = row_reduction_with_steps(&matrix, true, false)
+            .pop()
+            .map_or_else(|| matrix.clone(), |(_, result)| result);
+        let reduced = matrix.reduced_row_echelon_form();
+        assert_eq!(pivot_columns(&echelon), pivot_columns(&reduced));
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RowEchelonSolver;
+
+impl Solver for RowEchelonSolver {
+    fn id(&self) -> String {
+        "ref".to_string()
+    }
+
+    fn title(&self) -> String {
+        "行阶梯形矩阵".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入元素为整数, 小数或分数的矩阵, 仅消去主元下方的元素. 首行输入 normalize 时将主元化为 1."
+            .into_view()
+    }
+
+    fn default_input(&self) -> String {
+        indoc! {"
+            1   3  -2   5
+            3   5   6   7
+            1/2 1 1/2 3/2
+        "}
+        .to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let (normalize, rest) = split_keyword_line(&input, "normalize");
+        let line_offset = lines_before(&input, rest);
+        let input = rest;
+        let matrix = match input.parse::<Matrix<BigRational>>() {
+            Ok(matrix) => matrix,
+            Err(MatrixParseError::Empty) => return self.render_error("输入为空."),
+            Err(e) => return self.render_error(&e.offset_line(line_offset).to_string()),
+        };
+        let steps = row_reduction_with_steps(&matrix, true, normalize);
+        let echelon = steps
+            .last()
+            .map_or_else(|| matrix.clone(), |(_, result)| result.clone());
+        let pivots = pivot_columns(&echelon);
+        let pivot_positions = pivots
+            .iter()
+            .enumerate()
+            .map(|(i, j)| format!("({}, {})", i + 1, j + 1))
+            .join(r",\ ");
+        let steps = if steps.is_empty() {
+            view! {
+                <KaTeX expr={ format!(r"\begin{{pmatrix}}{}\end{{pmatrix}} \text{{已是行阶梯形矩阵.}}", matrix.to_tex()) } />
+            }
+            .into_view()
+        } else {
+            view! {
+                <KaTeX display_mode=true fleqn=true expr={ steps_to_tex(&matrix.to_tex(), &steps) } />
+                <p class="mt-2"> { row_ops_summary(&steps) } </p>
+            }
+            .into_view()
+        };
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "行阶梯形矩阵" </p>
+                <KaTeX expr={ format!(r"\begin{{pmatrix}}{}\end{{pmatrix}}", echelon.to_tex()) } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "主元位置" </p>
+                {
+                    if pivots.is_empty() {
+                        view! { <p> "零矩阵没有主元." </p> }.into_view()
+                    } else {
+                        view! { <KaTeX expr={ pivot_positions } /> }.into_view()
+                    }
+                }
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "矩阵的秩" </p>
+                <KaTeX expr={ format!(r"\mathrm{{r}}\begin{{pmatrix}}{}\end{{pmatrix}} = {}", matrix.to_tex(), pivots.len()) } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "初等行变换过程" </p>
+                { steps }
+            </div>
+        }
+        .into_view()
+    }
+}
@@ -0,0 +1,117 @@
+use indoc::*;
+use itertools::Itertools;
+use leptos::*;
+use num::{BigRational, Zero};
+use shiyanyi::*;
+
+use super::charpoly::polynomial_to_tex;
+use crate::common::*;
+
+/// Row `b` of Horner's method for coefficients in descending order of degree, where
+/// `b_0 = a_0` and `b_k = b_{k-1} x + a_k`. The last entry is the value at `x`.
+fn horner(coefficients: &[BigRational], x: &BigRational) -> Vec<BigRational> {
+    coefficients
+        .iter()
+        .scan(BigRational::zero(), |carry, a| {
+            *carry = &*carry * x + a;
+            Some(carry.clone())
+        })
+        .collect_vec()
+}
+
+#[test]
+fn test_horner() {
+    let integers = |v: &[i64]| {
+        v.iter()
+            .map(|&x| BigRational::from_integer(x.into()))
+            .collect_vec()
+    };
+    let b = horner(&integers(&[1, -3, 2]), &BigRational::from_integer(2.into()));
+    assert_eq!(b, integers(&[1, -1, 0]));
+    let b = horner(&integers(&[2, 0, -1]), &"1/2".parse().unwrap());
+    assert_eq!(b.last(), Some(&"-1/2".parse().unwrap()));
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HornerSolver;
+
+impl Solver for HornerSolver {
+    fn id(&self) -> String {
+        "horner".to_string()
+    }
+
+    fn title(&self) -> String {
+        "秦九韶算法求多项式的值".to_string()
+    }
+
+    fn description(&self) -> View {
+        "第一行输入多项式由高次到低次的各项系数, 第二行输入自变量的值, 元素为整数, 小数或分数."
+            .into_view()
+    }
+
+    fn default_input(&self) -> String {
+        indoc! {"
+            1 -3 2
+            2
+        "}
+        .to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let lines = input
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect_vec();
+        let [coefficients, x] = lines[..] else {
+            return self.render_error("输入应恰有两行.");
+        };
+        let coefficients = match coefficients
+            .split_whitespace()
+            .map(|text| {
+                parse_rational(text).map_err(|reason| format!("系数 {text:?} 无法解析 ({reason})"))
+            })
+            .try_collect::<_, Vec<_>, _>()
+        {
+            Ok(coefficients) if coefficients.is_empty() => return self.render_error("未给出系数."),
+            Ok(coefficients) => coefficients,
+            Err(e) => return self.render_error(&e),
+        };
+        let x = match parse_rational(x.trim()) {
+            Ok(x) => x,
+            Err(reason) => {
+                return self.render_error(&format!("{:?} 无法解析 ({reason})", x.trim()))
+            }
+        };
+        let b = horner(&coefficients, &x);
+        let value = b.last().unwrap().clone();
+        let ascending = coefficients.iter().rev().cloned().collect_vec();
+        let products = [String::new()]
+            .into_iter()
+            .chain(b.iter().take(b.len() - 1).map(|b| (b * &x).to_tex()))
+            .join(" & ");
+        let table = format!(
+            r"\begin{{array}}{{r|{}}} & {} \\ {} & {} \\ \hline & {} \end{{array}}",
+            "r".repeat(coefficients.len()),
+            coefficients.iter().map(BigRational::to_tex).join(" & "),
+            x.to_tex(),
+            products,
+            b.iter().map(BigRational::to_tex).join(" & "),
+        );
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "多项式" </p>
+                <KaTeX expr={ format!("f(x) = {}", polynomial_to_tex(&ascending, "x")) } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "秦九韶算法" </p>
+                <p class="mb-2"> "第三行每项等于第一行同列的系数加上第二行同列的乘积, 乘积由第三行前一列的值乘以自变量得到." </p>
+                <KaTeX display_mode=true expr={ table } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "多项式的值" </p>
+                <KaTeX expr={ format!("f({}) = {}", x.to_tex(), value.to_tex()) } />
+            </div>
+        }
+        .into_view()
+    }
+}
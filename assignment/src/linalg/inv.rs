@@ -1,9 +1,50 @@
 use itertools::Itertools;
 use leptos::*;
-use num::{zero, BigInt, Integer};
+#[cfg(test)]
+use num::{bigint::ToBigInt, zero};
+use num::{BigInt, Integer};
+#[cfg(test)]
+use rand::prelude::*;
+#[cfg(test)]
+use rand_chacha::ChaCha12Rng;
 use shiyanyi::*;
 
 fn inv(numbers: &[BigInt]) -> BigInt {
+    let mut sorted = numbers.to_vec();
+    let mut buffer = Vec::with_capacity(numbers.len());
+    merge_sort_count(&mut sorted, &mut buffer).into()
+}
+
+/// Sorts `numbers` and returns the count of pairs `i < j` with `numbers[i] > numbers[j]`.
+fn merge_sort_count(numbers: &mut [BigInt], buffer: &mut Vec<BigInt>) -> u64 {
+    let l = numbers.len();
+    if l < 2 {
+        return 0;
+    }
+    let mid = l / 2;
+    let mut ans = merge_sort_count(&mut numbers[..mid], buffer)
+        + merge_sort_count(&mut numbers[mid..], buffer);
+    buffer.clear();
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < l {
+        // equal elements are not inversions, so the left one goes first
+        if numbers[i] <= numbers[j] {
+            buffer.push(numbers[i].clone());
+            i += 1;
+        } else {
+            buffer.push(numbers[j].clone());
+            ans += (mid - i) as u64;
+            j += 1;
+        }
+    }
+    buffer.extend_from_slice(&numbers[i..mid]);
+    buffer.extend_from_slice(&numbers[j..]);
+    numbers.clone_from_slice(buffer);
+    ans
+}
+
+#[cfg(test)]
+fn inv_naive(numbers: &[BigInt]) -> BigInt {
     let l = numbers.len();
     let mut ans = zero();
     for i in 0..l {
@@ -51,6 +92,49 @@ fn test_inv() {
     );
 }
 
+#[test]
+fn test_inv_agrees_with_naive() {
+    let mut rng = ChaCha12Rng::seed_from_u64(0);
+    for len in [0, 1, 2, 3, 10, 100, 257] {
+        let numbers = (0..len)
+            .map(|_| rng.gen_range(-20i32..20).to_bigint().unwrap())
+            .collect_vec();
+        assert_eq!(inv(&numbers), inv_naive(&numbers));
+    }
+}
+
+/// TeX of the inversion number of the permutation in `input`, and whether it is odd. The
+/// error is a message to show.
+pub fn inversion_number_tex(input: &str) -> Result<(String, bool), &'static str> {
+    let numbers: Vec<BigInt> = input
+        .split(|c: char| !c.is_ascii_digit())
+        .map(|s| s.parse::<BigInt>())
+        .try_collect()
+        .map_err(|_| "无法解析输入.")?;
+    if numbers.is_empty() {
+        return Err("输入为空.");
+    }
+    let inversion_number = inv(&numbers[..]);
+    let numbers = numbers.into_iter().join(r" \allowbreak\  ");
+    Ok((
+        format!(r"\tau({numbers}) = {inversion_number}."),
+        inversion_number.is_odd(),
+    ))
+}
+
+#[test]
+fn test_inversion_number_tex() {
+    assert_eq!(
+        inversion_number_tex("4 3 2 1"),
+        Ok((
+            r"\tau(4 \allowbreak\  3 \allowbreak\  2 \allowbreak\  1) = 6.".to_string(),
+            false
+        ))
+    );
+    assert_eq!(inversion_number_tex("2 1").map(|(_, odd)| odd), Ok(true));
+    assert_eq!(inversion_number_tex("a"), Err("无法解析输入."));
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct InversionNumberSolver;
 
@@ -64,37 +148,34 @@ impl Solver for InversionNumberSolver {
     }
 
     fn description(&self) -> View {
-        "输入整数排列.".into_view()
+        "输入整数排列, 多个排列之间用单独一行 --- 分隔.".into_view()
     }
 
     fn default_input(&self) -> String {
         "4 3 2 1".to_string()
     }
 
+    fn live(&self) -> bool {
+        true
+    }
+
+    fn supports_batch(&self) -> bool {
+        true
+    }
+
     fn solve(&self, input: String) -> View {
-        let numbers: Vec<BigInt> = match input
-            .split(|c: char| !c.is_ascii_digit())
-            .map(|s| s.parse::<BigInt>())
-            .try_collect()
-        {
-            Ok(v) => v,
-            Err(_) => {
-                return "Failed to parse.".into_view();
-            }
+        let (inversion_number, is_odd) = match inversion_number_tex(&input) {
+            Ok(answer) => answer,
+            Err(e) => return self.render_error(e),
         };
-        if numbers.is_empty() {
-            return "Input is empty.".into_view();
-        }
-        let inversion_number = inv(&numbers[..]);
-        let numbers = numbers.into_iter().join(r" \allowbreak\  ");
         view! {
             <div class="mb-10">
                 <p class="font-bold mb-2"> "逆序数" </p>
-                <KaTeX expr={ format!(r"\tau({numbers}) = {inversion_number}.") } />
+                <KaTeX expr={ inversion_number } />
             </div>
             <div class="mb-10">
                 <p class="font-bold mb-2"> "排列类型" </p>
-                <p> { if inversion_number.is_odd() { "奇排列." } else { "偶排列." }} </p>
+                <p> { if is_odd { "奇排列." } else { "偶排列." }} </p>
             </div>
         }
         .into_view()
@@ -23,14 +23,19 @@ impl LinearEquations {
         self.iter().all(|r| r[n - 1].is_zero())
     }
 
+    pub fn coefficient_matrix(&self) -> Matrix<BigRational> {
+        let (m, n) = self.shape();
+        self.submatrix(&(0..m).collect_vec(), &(0..n - 1).collect_vec())
+    }
+
+    /// The constant terms as a single column.
+    pub fn constants(&self) -> Matrix<BigRational> {
+        let (m, n) = self.shape();
+        self.submatrix(&(0..m).collect_vec(), &[n - 1])
+    }
+
     pub fn has_any_solution(&self) -> bool {
-        Matrix::<BigRational>(
-            self.iter()
-                .map(|r| r[0..r.len() - 1].to_vec())
-                .collect_vec(),
-        )
-        .rank()
-            == self.rank()
+        self.coefficient_matrix().rank() == self.rank()
     }
 
     pub fn has_infinite_solutions(&self) -> bool {
@@ -53,80 +58,279 @@ impl DerefMut for LinearEquations {
 }
 
 impl FromStr for LinearEquations {
-    type Err = eyre::Report;
+    type Err = MatrixParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(Self(s.parse::<Matrix<BigRational>>()?))
     }
 }
 
-impl Display for LinearEquations {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        if !self.is_empty() {
-            let (_, n) = self.shape();
-            write!(
-                f,
-                r"\left\{{\begin{{alignat*}}{{{}}} {} \end{{alignat*}}\right.",
-                n + 1,
-                self.iter()
-                    .filter(|r| !r.iter().all(|v| v.is_zero()))
-                    .map(|r| {
-                        if r.iter().take(n - 1).all(|v| v.is_zero()) {
-                            format!(
-                                r"{} 0 & = \  & {} &",
-                                repeat_n("& ", (n - 1) * 2).join(""),
-                                r[n - 1].to_tex()
-                            )
-                        } else {
-                            let mut terms = vec!["".to_string()];
-                            let mut first_term = true;
-                            for (j, coeff) in r.iter().enumerate().take(n - 1) {
-                                if coeff.is_zero() {
-                                    terms.push("&".to_string());
-                                    continue;
-                                }
-                                terms.push(format!(
-                                    r" \  {} \  & {} x_{}",
-                                    if first_term {
-                                        first_term = false;
-                                        coeff.sign_to_tex()
-                                    } else {
-                                        coeff.sign_to_tex_with_positive_sign()
-                                    },
-                                    coeff.abs().to_tex_ignore_one(),
-                                    j + 1
-                                ));
+/// Parse an augmented matrix whose constants may be separated by a bar, e.g. `3 2 | 1`, along
+/// with whether they are. Without a bar the last column is the constants.
+pub fn parse_augmented_matrix(s: &str) -> Result<(Matrix<BigRational>, bool), MatrixParseError> {
+    if !s.contains('|') {
+        return Ok((s.parse()?, false));
+    }
+    let matrix = s.replace('|', " ").parse::<Matrix<BigRational>>()?;
+    // rows are split as in the matrix parser, so that lines match its errors
+    let rows = if s.contains('[') {
+        s.split(']').collect_vec()
+    } else {
+        s.split('\n').collect_vec()
+    };
+    let count_entries = |s: &str| {
+        s.split(|c: char| c.is_whitespace() || c == ',' || c == '[')
+            .filter(|s| !s.is_empty())
+            .count()
+    };
+    for (i, row) in rows.into_iter().enumerate() {
+        if count_entries(&row.replace('|', " ")) == 0 {
+            continue;
+        }
+        match row.split_once('|') {
+            Some((_, constants)) if !constants.contains('|') && count_entries(constants) == 1 => {}
+            _ => return Err(MatrixParseError::MisplacedBar { line: i + 1 }),
+        }
+    }
+    Ok((matrix, true))
+}
+
+#[test]
+fn test_parse_augmented_matrix() {
+    let expected = "3 2 1\n1 -1 0".parse::<Matrix<BigRational>>().unwrap();
+    assert_eq!(
+        parse_augmented_matrix("3 2 1\n1 -1 0").unwrap(),
+        (expected.clone(), false)
+    );
+    assert_eq!(
+        parse_augmented_matrix("3 2 | 1\n1 -1 | 0\n").unwrap(),
+        (expected.clone(), true)
+    );
+    assert_eq!(
+        parse_augmented_matrix("[[3, 2 | 1], [1, -1 | 0]]").unwrap(),
+        (expected, true)
+    );
+    assert_eq!(
+        parse_augmented_matrix("3 2 | 1\n1 | -1 0"),
+        Err(MatrixParseError::MisplacedBar { line: 2 })
+    );
+    assert_eq!(
+        parse_augmented_matrix("3 2 | 1\n1 -1 0"),
+        Err(MatrixParseError::MisplacedBar { line: 2 })
+    );
+}
+
+/// `\left[\begin{array}{cc|c} ... \end{array}\right]` with the constants after the bar.
+fn augmented_matrix_to_tex(matrix: &Matrix<BigRational>) -> String {
+    let (_, n) = matrix.shape();
+    format!(
+        r"\left[\begin{{array}}{{{}|c}} {} \end{{array}}\right]",
+        "c".repeat(n - 1),
+        matrix.to_tex()
+    )
+}
+
+impl LinearEquations {
+    /// TeX of the system with the `j`-th unknown named `unknowns[j]`.
+    pub fn to_tex_with_unknowns(&self, unknowns: &[String]) -> String {
+        if self.is_empty() {
+            return String::new();
+        }
+        let (_, n) = self.shape();
+        format!(
+            r"\left\{{\begin{{alignat*}}{{{}}} {} \end{{alignat*}}\right.",
+            n + 1,
+            self.iter()
+                .filter(|r| !r.iter().all(|v| v.is_zero()))
+                .map(|r| {
+                    if r.iter().take(n - 1).all(|v| v.is_zero()) {
+                        format!(
+                            r"{} 0 & = \  & {} &",
+                            repeat_n("& ", (n - 1) * 2).join(""),
+                            r[n - 1].to_tex()
+                        )
+                    } else {
+                        let mut terms = vec!["".to_string()];
+                        let mut first_term = true;
+                        for (j, coeff) in r.iter().enumerate().take(n - 1) {
+                            if coeff.is_zero() {
+                                terms.push("&".to_string());
+                                continue;
                             }
-                            terms.push(format!(r" = \  & {} &", r[n - 1].to_tex()));
-                            terms.join(" & ")
+                            terms.push(format!(
+                                r" \  {} \  & {} {}",
+                                if first_term {
+                                    first_term = false;
+                                    coeff.sign_to_tex()
+                                } else {
+                                    coeff.sign_to_tex_with_positive_sign()
+                                },
+                                coeff.abs().to_tex_ignore_one(),
+                                unknowns[j]
+                            ));
                         }
-                    })
-                    .join(" \\\\[1ex]\n")
-            )?;
+                        terms.push(format!(r" = \  & {} &", r[n - 1].to_tex()));
+                        terms.join(" & ")
+                    }
+                })
+                .join(" \\\\[1ex]\n")
+        )
+    }
+}
+
+/// `x_1, x_2, ..., x_n`.
+fn default_unknowns(n: usize) -> Vec<String> {
+    (1..=n).map(|j| format!("x_{j}")).collect_vec()
+}
+
+/// Splits off the first line if it names the unknowns, e.g. `x y z`, and gives their TeX with
+/// trailing digits as subscripts.
+fn split_unknowns(input: &str) -> (Option<Vec<String>>, &str) {
+    let (first, rest) = input.trim_start().split_once('\n').unwrap_or((input, ""));
+    let is_name = |token: &str| {
+        token.starts_with(|c: char| c.is_ascii_alphabetic())
+            && token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    };
+    let tokens = first.split_whitespace().collect_vec();
+    if tokens.is_empty() || !tokens.iter().all(|token| is_name(token)) {
+        return (None, input);
+    }
+    let unknowns = tokens
+        .into_iter()
+        .map(|token| {
+            let name = token.trim_end_matches(|c: char| c.is_ascii_digit() || c == '_');
+            let subscript = token[name.len()..].trim_start_matches('_');
+            if subscript.is_empty() {
+                name.to_string()
+            } else {
+                format!("{name}_{{{subscript}}}")
+            }
+        })
+        .collect_vec();
+    (Some(unknowns), rest)
+}
+
+#[test]
+fn test_split_unknowns() {
+    assert_eq!(
+        split_unknowns("x y z2\n1 2 3 4"),
+        (
+            Some(vec!["x".to_string(), "y".to_string(), "z_{2}".to_string()]),
+            "1 2 3 4"
+        )
+    );
+    assert_eq!(split_unknowns("1 2\n3 4"), (None, "1 2\n3 4"));
+    let lineq = "1 -1 0".parse::<LinearEquations>().unwrap();
+    assert_eq!(
+        lineq.to_tex_with_unknowns(&["a".to_string(), "b".to_string()]),
+        lineq.to_string().replace("x_1", "a").replace("x_2", "b")
+    );
+}
+
+impl Display for LinearEquations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
         }
-        Ok(())
+        let (_, n) = self.shape();
+        f.write_str(&self.to_tex_with_unknowns(&default_unknowns(n - 1)))
     }
 }
 
-pub trait Row {
-    #[allow(dead_code)]
-    fn row(&self, row: usize) -> Vec<BigRational>;
+/// Column of the leading entry of each nonzero row, `reduced` may be in row echelon form only.
+pub fn pivot_columns(reduced: &Matrix<BigRational>) -> Vec<usize> {
+    reduced
+        .iter()
+        .filter_map(|r| r.iter().position(|x| !x.is_zero()))
+        .collect_vec()
 }
 
-impl Row for Matrix<BigRational> {
-    fn row(&self, row: usize) -> Vec<BigRational> {
-        self[row].clone()
+/// A basic solution set of the homogeneous equations whose coefficient matrix is `reduced`,
+/// which must be in reduced row echelon form.
+pub fn basic_solutions(reduced: &Matrix<BigRational>) -> Vec<Vec<BigRational>> {
+    let (_, n) = reduced.shape();
+    let pivots = pivot_columns(reduced);
+    (0..n)
+        .filter(|j| !pivots.contains(j))
+        .map(|j| {
+            let mut solution = repeat_n(BigRational::zero(), n).collect_vec();
+            solution[j] = BigRational::one();
+            for (i, &pivot) in pivots.iter().enumerate() {
+                solution[pivot] = -&reduced[i][j];
+            }
+            solution
+        })
+        .collect_vec()
+}
+
+/// The solution with all free unknowns set to zero, `reduced` must be a consistent augmented
+/// matrix in reduced row echelon form.
+fn particular_solution(reduced: &Matrix<BigRational>) -> Vec<BigRational> {
+    let (_, n) = reduced.shape();
+    let mut solution = repeat_n(BigRational::zero(), n - 1).collect_vec();
+    for (i, &pivot) in pivot_columns(reduced).iter().enumerate() {
+        solution[pivot] = reduced[i][n - 1].clone();
     }
+    solution
 }
 
-pub trait Col {
-    fn col(&self, col: usize) -> Vec<BigRational>;
+/// The solution of the equations if there is exactly one.
+fn unique_solution(lineq: &LinearEquations) -> Option<Vec<BigRational>> {
+    if !lineq.has_any_solution() || lineq.has_infinite_solutions() {
+        return None;
+    }
+    Some(particular_solution(&lineq.reduced_row_echelon_form()))
 }
 
-impl Col for Matrix<BigRational> {
-    fn col(&self, col: usize) -> Vec<BigRational> {
-        self.iter().map(|r| r[col].clone()).collect_vec()
+#[test]
+fn test_unique_solution() {
+    let lineq = "2 1 -1 8\n-3 -1 2 -11\n-2 1 2 -3"
+        .parse::<LinearEquations>()
+        .unwrap();
+    let solution = unique_solution(&lineq).unwrap();
+    assert_eq!(
+        solution,
+        [2, 3, -1]
+            .map(|x| BigRational::from_integer(x.into()))
+            .to_vec()
+    );
+    // substituting back satisfies every equation
+    for row in lineq.iter() {
+        let lhs = row
+            .iter()
+            .zip(&solution)
+            .map(|(a, x)| a * x)
+            .fold(BigRational::zero(), |acc, x| acc + x);
+        assert_eq!(&lhs, row.last().unwrap());
     }
+    let lineq = "1 1 1\n2 2 2".parse::<LinearEquations>().unwrap();
+    assert_eq!(unique_solution(&lineq), None);
+}
+
+#[test]
+fn test_basic_solutions() {
+    let reduced = "1 2 0 3\n0 0 1 4\n0 0 0 0"
+        .parse::<Matrix<BigRational>>()
+        .unwrap();
+    let to_rational = |v: Vec<i32>| {
+        v.into_iter()
+            .map(|x| BigRational::from_integer(x.into()))
+            .collect_vec()
+    };
+    assert_eq!(
+        basic_solutions(&reduced),
+        vec![
+            to_rational(vec![-2, 1, 0, 0]),
+            to_rational(vec![-3, 0, -4, 1])
+        ]
+    );
+    assert_eq!(particular_solution(&reduced), to_rational(vec![3, 0, 4]));
+    let reduced = "1 0 5\n0 1 -1\n0 0 0"
+        .parse::<Matrix<BigRational>>()
+        .unwrap();
+    assert_eq!(particular_solution(&reduced), to_rational(vec![5, -1]));
+    let reduced = "0 1 0\n0 0 1".parse::<Matrix<BigRational>>().unwrap();
+    assert_eq!(basic_solutions(&reduced), vec![to_rational(vec![1, 0, 0])]);
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -142,7 +346,8 @@ impl Solver for LinearEquationsSolver {
     }
 
     fn description(&self) -> View {
-        "输入元素为整数或分数的增广矩阵.".into_view()
+        "输入元素为整数, 小数或分数的增广矩阵, 常数列前可以用 | 分隔, 元素间以空格或逗号分隔. 第一行可以给出未知量的名称, 如 x y z. 首行输入 csv 时其后 (未知量名称之后) 按 CSV 解析, 否则逗号也按矩阵格式解析."
+            .into_view()
     }
 
     fn default_input(&self) -> String {
@@ -156,58 +361,56 @@ impl Solver for LinearEquationsSolver {
     }
 
     fn solve(&self, input: String) -> View {
-        let matrix = match input.parse::<Matrix<BigRational>>() {
-            Ok(matrix) => matrix,
-            Err(_) => {
-                return view! {
-                    <p> "Failed to parse." </p>
-                }
-                .into_view()
+        let (csv, rest) = split_keyword_line(&input, "csv");
+        let (unknowns, rest) = split_unknowns(rest);
+        let line_offset = lines_before(&input, rest);
+        let input = rest;
+        let (matrix, bar) = if csv {
+            match Matrix::from_csv(input) {
+                Ok(matrix) => (matrix, false),
+                Err(e) => return self.render_error(&format!("CSV 无法解析: {e}")),
+            }
+        } else {
+            match parse_augmented_matrix(input) {
+                Ok(parsed) => parsed,
+                Err(MatrixParseError::Empty) => return self.render_error("输入为空."),
+                Err(e) => return self.render_error(&e.offset_line(line_offset).to_string()),
             }
         };
         let (_, n) = matrix.shape();
         if n < 2 {
-            return view! {
-                <p> "Augmented matrix must contain at least 2 columns." </p>
-            }
-            .into_view();
+            return self.render_error("增广矩阵至少应有 2 列.");
         }
+        let unknowns = match unknowns {
+            Some(unknowns) if unknowns.len() != n - 1 => {
+                return self.render_error(&format!(
+                    "给出了 {} 个未知量的名称, 但增广矩阵有 {} 列系数.",
+                    unknowns.len(),
+                    n - 1
+                ))
+            }
+            Some(unknowns) => unknowns,
+            None => default_unknowns(n - 1),
+        };
+        let augmented = bar.then(|| {
+            view! {
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "增广矩阵及其行最简形矩阵" </p>
+                    <KaTeX expr={
+                        format!(
+                            r"{} \sim {}",
+                            augmented_matrix_to_tex(&matrix),
+                            augmented_matrix_to_tex(&matrix.reduced_row_echelon_form())
+                        )
+                    } />
+                </div>
+            }
+        });
         let lineq = LinearEquations(matrix.clone());
-        if lineq.is_homogeneous() {
+        let answer = if lineq.is_homogeneous() {
             let reduced = LinearEquations(matrix.reduced_row_echelon_form());
             if reduced.has_infinite_solutions() {
-                let mut main_unknowns = Vec::new();
-                let mut basic_solutions = Vec::new();
-                for j in 0..(n - 1) {
-                    let mut col = reduced.col(j);
-                    let nonzero = col
-                        .iter()
-                        .enumerate()
-                        .filter(|(_, x)| !x.is_zero())
-                        .collect_vec();
-                    if nonzero.is_empty() {
-                        todo!(); // TODO: 无关未知量
-                    } else if nonzero.len() == 1 {
-                        let x = nonzero.last().unwrap().0;
-                        if main_unknowns.contains(&x) {
-                            for item in &mut col {
-                                *item = -&*item;
-                            }
-                            col.extend(repeat_n(BigRational::zero(), n - 1 - col.len()));
-                            col[j] = BigRational::one();
-                            basic_solutions.push(col);
-                        } else {
-                            main_unknowns.push(x);
-                        }
-                    } else if nonzero.len() > 1 {
-                        for item in &mut col {
-                            *item = -&*item;
-                        }
-                        col.extend(repeat_n(BigRational::zero(), n - 1 - col.len()));
-                        col[j] = BigRational::one();
-                        basic_solutions.push(col);
-                    }
-                }
+                let basic_solutions = basic_solutions(&reduced.coefficient_matrix());
                 let solution = format!(
                     r"\left\{{{} \mid {} \in \mathbb{{R}}\right\}}",
                     (0..basic_solutions.len())
@@ -231,11 +434,11 @@ impl Solver for LinearEquationsSolver {
                 view! {
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "齐次实线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "增广矩阵的行最简形矩阵对应的齐次线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "方程组解的类型" </p>
@@ -255,11 +458,11 @@ impl Solver for LinearEquationsSolver {
                 view! {
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "齐次实线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "增广矩阵的行最简形矩阵对应的齐次线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "方程组解的类型" </p>
@@ -274,11 +477,11 @@ impl Solver for LinearEquationsSolver {
                 view! {
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "非齐次实线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "增广矩阵的行最简形矩阵对应的非齐次线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "方程组解的类型" </p>
@@ -287,42 +490,8 @@ impl Solver for LinearEquationsSolver {
                 }
                 .into_view()
             } else if reduced.has_infinite_solutions() {
-                let mut main_unknowns = Vec::new();
-                let mut basic_solutions = Vec::new();
-                for j in 0..(n - 1) {
-                    let mut col = reduced.col(j);
-                    let nonzero = col
-                        .iter()
-                        .enumerate()
-                        .filter(|(_, x)| !x.is_zero())
-                        .collect_vec();
-                    if nonzero.is_empty() {
-                        todo!(); // TODO: 无关未知量
-                    } else if nonzero.len() == 1 {
-                        let x = nonzero.last().unwrap().0;
-                        if main_unknowns.contains(&x) {
-                            for item in &mut col {
-                                *item = -&*item;
-                            }
-                            col.extend(repeat_n(BigRational::zero(), n - 1 - col.len()));
-                            col[j] = BigRational::one();
-                            basic_solutions.push(col);
-                        } else {
-                            main_unknowns.push(x);
-                        }
-                    } else if nonzero.len() > 1 {
-                        for item in &mut col {
-                            *item = -&*item;
-                        }
-                        col.extend(repeat_n(BigRational::zero(), n - 1 - col.len()));
-                        col[j] = BigRational::one();
-                        basic_solutions.push(col);
-                    }
-                }
-                let mut one_solution = basic_solutions.first().unwrap().clone();
-                for i in 0..reduced.shape().0 {
-                    one_solution[i] += &reduced[i][n - 1];
-                }
+                let basic_solutions = basic_solutions(&reduced.coefficient_matrix());
+                let one_solution = particular_solution(&reduced);
                 let one_solution = format!(
                     r"\bm\eta_0 = \begin{{pmatrix}}{}\end{{pmatrix}}",
                     one_solution
@@ -353,11 +522,11 @@ impl Solver for LinearEquationsSolver {
                 view! {
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "非齐次实线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "增广矩阵的行最简形矩阵对应的非齐次线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "方程组解的类型" </p>
@@ -378,22 +547,50 @@ impl Solver for LinearEquationsSolver {
                 }
                 .into_view()
             } else {
+                let solution = unique_solution(&lineq).unwrap();
+                let decimal = create_rw_signal(false);
+                let digits = create_rw_signal(DEFAULT_DECIMAL_DIGITS);
+                let unknowns_tex = unknowns.clone();
+                let solution = move || {
+                    let solution = format!(
+                        r"\begin{{aligned}} {} \end{{aligned}}",
+                        solution
+                            .iter()
+                            .enumerate()
+                            .map(|(j, x)| if decimal() {
+                                format!(
+                                    r"{} &\approx {}",
+                                    unknowns_tex[j],
+                                    x.to_decimal_string(digits())
+                                )
+                            } else {
+                                format!(r"{} &= {}", unknowns_tex[j], x.to_tex())
+                            })
+                            .join(r" \\[1ex] ")
+                    );
+                    view! { <KaTeX display_mode=true fleqn=true expr={ solution } /> }
+                };
                 view! {
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "非齐次实线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ lineq.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "增广矩阵的行最简形矩阵对应的非齐次线性方程组" </p>
-                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_string() } />
+                        <KaTeX display_mode=true fleqn=true expr={ reduced.to_tex_with_unknowns(&unknowns) } />
                     </div>
                     <div class="mb-10">
                         <p class="font-bold mb-2"> "方程组解的类型" </p>
                         <p> "有唯一解." </p>
                     </div>
+                    <div class="mb-10">
+                        <p class="font-bold mb-2"> "方程组的解" <DecimalToggle decimal digits /> </p>
+                        { solution }
+                    </div>
                 }
                 .into_view()
             }
-        }
+        };
+        view! { {augmented} {answer} }.into_view()
     }
 }
@@ -7,11 +7,11 @@ use std::{
 use indoc::*;
 use itertools::{repeat_n, Itertools};
 use leptos::*;
-use num::{BigRational, Zero};
+use num::{BigRational, Signed, Zero};
 use shiyanyi::*;
 
 use crate::common::*;
-use crate::linalg::LinearEquations;
+use crate::linalg::{basic_solutions, Determinant, LinearEquations};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Vector(pub Vec<BigRational>);
@@ -43,23 +43,47 @@ impl Display for Vector {
     }
 }
 
+impl Vector {
+    /// Same as [`Display`] but laid out as a row vector.
+    pub fn to_row_tex(&self) -> String {
+        if self.is_empty() {
+            return "".to_string();
+        }
+        format!(
+            r"\begin{{pmatrix}}{}\end{{pmatrix}}",
+            self.iter().map(|x| x.to_string()).join(" & ")
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct VectorSet(pub Vec<Vector>);
 
 impl VectorSet {
+    pub fn from_columns(matrix: &Matrix<BigRational>) -> Self {
+        let (_, n) = matrix.shape();
+        Self(
+            (0..n)
+                .map(|j| Vector(matrix.iter().map(|r| r[j].clone()).collect_vec()))
+                .collect_vec(),
+        )
+    }
+
+    pub fn from_rows(matrix: &Matrix<BigRational>) -> Self {
+        Self::from_columns(&matrix.transpose())
+    }
+
     pub fn to_matrix(&self) -> Matrix<BigRational> {
-        let (m, n) = self.shape();
+        let (m, _) = self.shape();
         let mut matrix = Matrix::<BigRational>(repeat_n(Vec::new(), m).collect_vec());
-        for j in 0..n {
-            for i in 0..m {
-                matrix[i].push(self[j][i].clone());
-            }
+        for vector in self.iter() {
+            matrix.append_column(vector.to_vec());
         }
         matrix
     }
 
     pub fn shape(&self) -> (usize, usize) {
-        (self[0].len(), self.len())
+        (self.first().map_or(0, |v| v.len()), self.len())
     }
 
     pub fn is_in_span(&self, vector: &Vector) -> bool {
@@ -68,11 +92,122 @@ impl VectorSet {
             panic!("Vector is not the same size as VectorSet.");
         }
         let mut matrix = self.to_matrix();
-        for i in 0..m {
-            matrix[i].push(vector[i].clone());
-        }
+        matrix.append_column(vector.to_vec());
         LinearEquations(matrix).has_any_solution()
     }
+
+    /// Coefficients expressing `vector` as a linear combination of the vectors in this set,
+    /// which must be linearly independent. Returns `None` if `vector` is not in the span.
+    pub fn coordinates(&self, vector: &Vector) -> Option<Vec<BigRational>> {
+        if self.is_empty() {
+            return if vector.iter().all(|x| x.is_zero()) {
+                Some(Vec::new())
+            } else {
+                None
+            };
+        }
+        if !self.is_in_span(vector) {
+            return None;
+        }
+        let (_, n) = self.shape();
+        let mut matrix = self.to_matrix();
+        matrix.append_column(vector.to_vec());
+        let reduced = matrix.reduced_row_echelon_form();
+        Some((0..n).map(|i| reduced[i][n].clone()).collect_vec())
+    }
+}
+
+#[test]
+fn test_coordinates() {
+    let vector_set = "1 0\n0 1\n1 1".parse::<VectorSet>().unwrap();
+    let vector = Vector(
+        vec![2.into(), (-1).into(), 1.into()]
+            .into_iter()
+            .map(BigRational::from_integer)
+            .collect_vec(),
+    );
+    assert_eq!(
+        vector_set.coordinates(&vector),
+        Some(vec![
+            BigRational::from_integer(2.into()),
+            BigRational::from_integer((-1).into())
+        ])
+    );
+    let vector = Vector(
+        vec![1.into(), 0.into(), 0.into()]
+            .into_iter()
+            .map(BigRational::from_integer)
+            .collect_vec(),
+    );
+    assert_eq!(vector_set.coordinates(&vector), None);
+}
+
+/// Splits off the last line if it is the target vector of a span query, e.g. `in 1 2 3`.
+fn split_target(input: &str) -> (&str, Option<&str>) {
+    let trimmed = input.trim_end();
+    let (rest, last) = trimmed.rsplit_once('\n').unwrap_or(("", trimmed));
+    match last.trim().strip_prefix("in ") {
+        Some(target) => (rest, Some(target)),
+        None => (input, None),
+    }
+}
+
+#[test]
+fn test_split_target() {
+    assert_eq!(
+        split_target("1 0\n0 1\nin 2 3\n"),
+        ("1 0\n0 1", Some("2 3"))
+    );
+    assert_eq!(split_target("1 0\n0 1"), ("1 0\n0 1", None));
+}
+
+#[test]
+fn test_rows_and_columns() {
+    use crate::linalg::Rank;
+
+    let rank = |input: &str| {
+        let (rows, input) = split_keyword_line(input, "rows");
+        let matrix = input.parse::<Matrix<BigRational>>().unwrap();
+        let vector_set = if rows {
+            VectorSet::from_rows(&matrix)
+        } else {
+            VectorSet::from_columns(&matrix)
+        };
+        (vector_set.len(), vector_set.to_matrix().rank())
+    };
+    let matrix = "1 2 3 4\n2 4 6 8\n0 1 1 0";
+    assert_eq!(rank(matrix), (4, 2));
+    assert_eq!(rank(&format!("rows\n{matrix}")), (3, 2));
+}
+
+fn vector_name(index: usize) -> String {
+    format!(r"\bm\alpha_{{{}}}", index + 1)
+}
+
+fn linear_combination_to_tex(coefficients: &[BigRational], indices: &[usize]) -> String {
+    let terms = coefficients
+        .iter()
+        .zip_eq(indices)
+        .filter(|(coeff, _)| !coeff.is_zero())
+        .enumerate()
+        .map(|(k, (coeff, &index))| {
+            format!(
+                r"{} {} {}",
+                if k == 0 {
+                    coeff.sign_to_tex()
+                } else {
+                    coeff.sign_to_tex_with_positive_sign()
+                },
+                coeff.abs().to_tex_ignore_one(),
+                vector_name(index)
+            )
+        })
+        .join(" ");
+    if terms.is_empty() {
+        r"\bm 0".to_string()
+    } else {
+        terms
+    }
 }
 
 impl Deref for VectorSet {
@@ -90,16 +225,10 @@ impl DerefMut for VectorSet {
 }
 
 impl FromStr for VectorSet {
-    type Err = eyre::Report;
+    type Err = MatrixParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let matrix = s.parse::<Matrix<BigRational>>()?;
-        let (_, n) = matrix.shape();
-        Ok(Self(
-            (0..n)
-                .map(|j| Vector(matrix.iter().map(|r| r[j].clone()).collect_vec()))
-                .collect_vec(),
-        ))
+        Ok(Self::from_columns(&s.parse::<Matrix<BigRational>>()?))
     }
 }
 
@@ -129,7 +258,8 @@ impl Solver for MaximalLinearlyIndependentSolver {
     }
 
     fn description(&self) -> View {
-        "输入元素为整数或分数的矩阵, 研究其列向量组.".into_view()
+        "输入元素为整数, 小数或分数的矩阵, 研究其列向量组. 首行输入 rows 则研究其行向量组. 末行输入 in 和目标向量的各分量则判断目标向量能否由向量组线性表示."
+            .into_view()
     }
 
     fn default_input(&self) -> String {
@@ -143,57 +273,189 @@ impl Solver for MaximalLinearlyIndependentSolver {
     }
 
     fn solve(&self, input: String) -> View {
-        let vector_set = match input.parse::<VectorSet>() {
-            Ok(vector_set) => vector_set,
-            Err(_) => {
-                return view! {
-                    <p> "Failed to parse." </p>
-                }
-                .into_view()
+        let (rows, rest) = split_keyword_line(&input, "rows");
+        let line_offset = lines_before(&input, rest);
+        let (input, target) = split_target(rest);
+        let vector_set = match input.parse::<Matrix<BigRational>>() {
+            Ok(matrix) if rows => VectorSet::from_rows(&matrix),
+            Ok(matrix) => VectorSet::from_columns(&matrix),
+            Err(MatrixParseError::Empty) => return self.render_error("输入为空."),
+            Err(e) => return self.render_error(&e.offset_line(line_offset).to_string()),
+        };
+        let (m, n) = vector_set.shape();
+        let target = match target.map(|target| target.parse::<Matrix<BigRational>>()) {
+            None => None,
+            Some(Ok(target)) if target.len() == 1 && target[0].len() == m => {
+                Some(Vector(target.0.into_iter().next().unwrap()))
             }
+            Some(Ok(target)) => {
+                return self.render_error(&format!(
+                    "目标向量应为一行 {m} 个元素, 实有 {} 个.",
+                    target.iter().map(Vec::len).sum::<usize>()
+                ))
+            }
+            Some(Err(e)) => return self.render_error(&format!("目标向量无法解析: {e}")),
         };
-        let (_, n) = vector_set.shape();
-        let mut unique_vector_set = VectorSet(Vec::new());
-        for i in 0..n {
-            if !unique_vector_set.contains(&vector_set[i]) {
-                unique_vector_set.push(vector_set[i].clone());
+        let mut maximal_indices = Vec::new();
+        let mut maximal_linearly_independent = VectorSet(Vec::new());
+        let mut dependent = Vec::new();
+        for j in 0..n {
+            let vector = &vector_set[j];
+            match maximal_linearly_independent.coordinates(vector) {
+                Some(coefficients) => {
+                    let note = if vector.iter().all(|x| x.is_zero()) {
+                        r"\quad \text{(零向量)}".to_string()
+                    } else if let Some(i) = (0..j).find(|&i| vector_set[i] == *vector) {
+                        format!(r"\quad \text{{(与 }} {} \text{{ 相同)}}", vector_name(i))
+                    } else {
+                        "".to_string()
+                    };
+                    dependent.push(format!(
+                        r"{} &= {}{}",
+                        vector_name(j),
+                        linear_combination_to_tex(&coefficients, &maximal_indices),
+                        note
+                    ));
+                }
+                None => {
+                    maximal_indices.push(j);
+                    maximal_linearly_independent.push(vector.clone());
+                }
             }
         }
-        let unique_nonzero_vector_set = VectorSet(
-            unique_vector_set
+        let vector_tex = |vector: &Vector| {
+            if rows {
+                vector.to_row_tex()
+            } else {
+                vector.to_string()
+            }
+        };
+        let named = |indices: &[usize]| {
+            indices
                 .iter()
-                .filter(|vector| !vector.iter().all(|x| x.is_zero()))
-                .cloned()
-                .collect_vec(),
+                .map(|&j| format!("{} = {}", vector_name(j), vector_tex(&vector_set[j])))
+                .join(r",\  ")
+        };
+        let reduced = vector_set.to_matrix().reduced_row_echelon_form();
+        let null_space = basic_solutions(&reduced);
+        let nullity = format!(
+            r"\dim N(\bm A) = {n} - \mathrm{{r}}(\bm A) = {}",
+            null_space.len()
         );
-        let mut mutable_unique_nonzero_vector_set = unique_nonzero_vector_set.clone();
-        let mut maximal_linearly_independent =
-            VectorSet(vec![mutable_unique_nonzero_vector_set.remove(0)]);
-        while !mutable_unique_nonzero_vector_set.is_empty() {
-            let vector = mutable_unique_nonzero_vector_set.remove(0);
-            if !maximal_linearly_independent.is_in_span(&vector) {
-                maximal_linearly_independent.push(vector);
+        let null_space = null_space
+            .iter()
+            .enumerate()
+            .map(|(i, v)| {
+                format!(
+                    r"\bm\xi_{} = \begin{{pmatrix}}{}\end{{pmatrix}}",
+                    i + 1,
+                    v.iter().map(BigRational::to_tex).join(r" \\[1ex] ")
+                )
+            })
+            .join(r",\ ");
+        let vectors = named(&(0..n).collect_vec());
+        let maximal = named(&maximal_indices);
+        let all_vectors = vector_set.iter().map(vector_tex).join(r",\  ");
+        let determinant = if m == n {
+            let determinant = vector_set.to_matrix().determinant();
+            let tex = if determinant.is_zero() {
+                r"\det \bm A = 0 \implies \text{线性相关}".to_string()
+            } else {
+                format!(
+                    r"\det \bm A = {} \neq 0 \implies \text{{线性无关}}",
+                    determinant.to_tex()
+                )
+            };
+            view! { <KaTeX expr={ tex } /> }.into_view()
+        } else {
+            view! { <p> "向量的个数与维数不同, 不适用行列式判别, 由秩判断线性相关性." </p> }
+                .into_view()
+        };
+        let membership = target.map(|target| {
+            let coordinates = maximal_linearly_independent.coordinates(&target);
+            let verdict = match &coordinates {
+                Some(_) => "目标向量可以由向量组线性表示, 用极大线性无关组表示为",
+                None => "目标向量不能由向量组线性表示.",
+            };
+            view! {
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "目标向量是否属于向量组生成的空间" </p>
+                    <KaTeX expr={ format!(r"\bm\beta = {}", vector_tex(&target)) } />
+                    <p class="mt-2 mb-2"> { verdict } </p>
+                    {
+                        coordinates.map(|coefficients| view! {
+                            <KaTeX expr={
+                                format!(
+                                    r"\bm\beta = {}",
+                                    linear_combination_to_tex(&coefficients, &maximal_indices)
+                                )
+                            } />
+                        })
+                    }
+                </div>
             }
-        }
+        });
         view! {
             <div class="mb-10">
                 <p class="font-bold mb-2"> "向量组" </p>
-                <KaTeX expr={ vector_set.to_string() } />
+                <p class="mb-2"> { if rows { "以矩阵的各行为向量." } else { "以矩阵的各列为向量." } } </p>
+                <KaTeX expr={ vectors } />
             </div>
             <div class="mb-10">
                 <p class="font-bold mb-2"> "一个极大线性无关组" </p>
-                <KaTeX expr={ maximal_linearly_independent.to_string() } />
+                {
+                    if maximal_indices.is_empty() {
+                        view! { <p> "向量组只含零向量, 不存在极大线性无关组." </p> }.into_view()
+                    } else {
+                        view! { <KaTeX expr={ maximal } /> }.into_view()
+                    }
+                }
             </div>
+            {
+                if dependent.is_empty() {
+                    ().into_view()
+                } else {
+                    view! {
+                        <div class="mb-10">
+                            <p class="font-bold mb-2"> "其余向量由极大线性无关组线性表示" </p>
+                            <KaTeX display_mode=true fleqn=true expr={
+                                format!(r"\begin{{align*}} {} \end{{align*}}", dependent.join(r" \\[1ex] "))
+                            } />
+                        </div>
+                    }
+                    .into_view()
+                }
+            }
             <div class="mb-10">
                 <p class="font-bold mb-2"> "向量组的秩" </p>
                 <KaTeX expr={
                     format!(
                         r"\mathrm{{r}}\left({}\right) = {}",
-                        vector_set,
+                        all_vectors,
                         maximal_linearly_independent.len()
                     )
                 } />
             </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "以向量组为列的矩阵 A 的行列式" </p>
+                { determinant }
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "以向量组为列的矩阵 A 的零空间" </p>
+                <KaTeX expr={ nullity } />
+                {
+                    if null_space.is_empty() {
+                        view! { <p class="mt-2"> "齐次线性方程组 Ax = 0 仅有零解." </p> }.into_view()
+                    } else {
+                        view! {
+                            <p class="mt-2 mb-2"> "齐次线性方程组 Ax = 0 的一个基础解系" </p>
+                            <KaTeX expr={ null_space } />
+                        }
+                        .into_view()
+                    }
+                }
+            </div>
+            { membership }
         }
         .into_view()
     }
@@ -2,7 +2,17 @@ mod inv;
 pub use inv::*;
 mod rref;
 pub use rref::*;
+mod echelon;
+pub use echelon::*;
 mod lineq;
 pub use lineq::*;
 mod maxlinind;
 pub use maxlinind::*;
+mod charpoly;
+pub use charpoly::*;
+mod horner;
+pub use horner::*;
+mod cramer;
+pub use cramer::*;
+mod rank;
+pub use rank::*;
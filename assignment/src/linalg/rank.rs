@@ -0,0 +1,52 @@
+use indoc::*;
+use leptos::*;
+use num::BigRational;
+use shiyanyi::*;
+
+use crate::common::*;
+use crate::linalg::Rank;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RankSolver;
+
+impl Solver for RankSolver {
+    fn id(&self) -> String {
+        "rank".to_string()
+    }
+
+    fn title(&self) -> String {
+        "矩阵的秩".to_string()
+    }
+
+    fn description(&self) -> View {
+        "输入元素为整数, 小数或分数的矩阵.".into_view()
+    }
+
+    fn default_input(&self) -> String {
+        indoc! {"
+            1 2 3
+            4 5 6
+            7 8 9
+        "}
+        .to_string()
+    }
+
+    fn solve(&self, input: String) -> View {
+        let matrix = match input.parse::<Matrix<BigRational>>() {
+            Ok(matrix) => matrix,
+            Err(MatrixParseError::Empty) => return self.render_error("输入为空."),
+            Err(e) => return self.render_error(&e.to_string()),
+        };
+        view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "矩阵" </p>
+                <KaTeX expr={ format!(r"\bm A = \begin{{pmatrix}}{}\end{{pmatrix}}", matrix.to_tex()) } />
+            </div>
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "矩阵的秩" </p>
+                <KaTeX expr={ format!(r"\mathrm{{r}}(\bm A) = {}", matrix.rank()) } />
+            </div>
+        }
+        .into_view()
+    }
+}
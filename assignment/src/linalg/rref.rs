@@ -3,7 +3,7 @@ use std::ops::{AddAssign, Mul, MulAssign};
 use indoc::*;
 use itertools::Itertools;
 use leptos::*;
-use num::{BigRational, One, Zero};
+use num::{traits::NumAssignRef, BigRational, One, ToPrimitive, Zero};
 use shiyanyi::*;
 
 use crate::common::*;
@@ -60,68 +60,234 @@ impl ScaleAddRow for Matrix<BigRational> {
     }
 }
 
-fn reduced_row_echelon_form_with_steps(
+/// Elementary row operation, rows are 0-based.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowOp<T = BigRational> {
+    /// row1 <-> row2
+    Swap(usize, usize),
+    /// row *= factor
+    Scale(usize, T),
+    /// dst += src * factor
+    ScaleAdd { src: usize, factor: T, dst: usize },
+}
+
+impl<T: NumAssignRef + Clone> RowOp<T> {
+    fn apply(&self, matrix: &mut Matrix<T>) {
+        match self {
+            RowOp::Swap(row1, row2) => matrix.swap(*row1, *row2),
+            RowOp::Scale(row, factor) => matrix[*row].iter_mut().for_each(|x| *x *= factor),
+            RowOp::ScaleAdd { src, factor, dst } => {
+                for j in 0..matrix.shape().1 {
+                    let mut x = matrix[*src][j].clone();
+                    x *= factor;
+                    matrix[*dst][j] += &x;
+                }
+            }
+        }
+    }
+}
+
+/// Entries that row reduction works with.
+trait Pivot: Sized {
+    /// Whether the entry is treated as zero.
+    fn is_negligible(&self) -> bool;
+
+    /// Index of the entry to pivot on among `column`, `None` if all are negligible.
+    fn choose_pivot(column: &[&Self]) -> Option<usize>;
+}
+
+impl Pivot for BigRational {
+    fn is_negligible(&self) -> bool {
+        self.is_zero()
+    }
+
+    fn choose_pivot(column: &[&Self]) -> Option<usize> {
+        column.iter().position(|x| !x.is_zero())
+    }
+}
+
+impl Pivot for f64 {
+    fn is_negligible(&self) -> bool {
+        self.abs() < APPROXIMATE_TOLERANCE
+    }
+
+    /// Partial pivoting, the entry of the largest absolute value.
+    fn choose_pivot(column: &[&Self]) -> Option<usize> {
+        column
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
+            .filter(|(_, x)| !x.is_negligible())
+            .map(|(i, _)| i)
+    }
+}
+
+impl RowOp {
+    pub fn to_tex(&self) -> String {
+        match self {
+            RowOp::Swap(row1, row2) => {
+                format!(r"r_{{{}}} \leftrightarrow r_{{{}}}", row1 + 1, row2 + 1)
+            }
+            RowOp::Scale(row, factor) => {
+                format!(r"r_{{{}}} \times {}", row + 1, factor.to_tex_with_paren())
+            }
+            RowOp::ScaleAdd { src, factor, dst } => format!(
+                r"r_{{{}}} {} r_{{{}}}",
+                dst + 1,
+                factor.to_tex_with_sign_ignore_one(),
+                src + 1
+            ),
+        }
+    }
+
+    /// The `n` by `n` elementary matrix that performs this operation by left multiplication.
+    pub fn elementary_matrix(&self, n: usize) -> Matrix<BigRational> {
+        let mut matrix = Matrix(
+            (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| {
+                            if i == j {
+                                BigRational::one()
+                            } else {
+                                BigRational::zero()
+                            }
+                        })
+                        .collect_vec()
+                })
+                .collect_vec(),
+        );
+        match self {
+            RowOp::Swap(row1, row2) => matrix.swap_row(*row1, *row2),
+            RowOp::Scale(row, factor) => matrix.scale_row(*row, factor),
+            RowOp::ScaleAdd { src, factor, dst } => matrix.scale_add_row(*src, factor, *dst),
+        }
+        matrix
+    }
+}
+
+/// Each row operation along with the matrix after applying it.
+pub fn reduced_row_echelon_form_with_steps(
+    matrix: &Matrix<BigRational>,
+) -> Vec<(RowOp, Matrix<BigRational>)> {
+    row_reduction_with_steps(matrix, false, true)
+}
+
+/// Gaussian elimination with steps, which only eliminates below the pivots if
+/// `stop_at_echelon`, and scales the pivots to one if `normalize_pivots`.
+pub fn row_reduction_with_steps(
     matrix: &Matrix<BigRational>,
-) -> Vec<(String, Matrix<BigRational>)> {
+    stop_at_echelon: bool,
+    normalize_pivots: bool,
+) -> Vec<(RowOp, Matrix<BigRational>)> {
     let mut matrix = matrix.clone();
     let mut steps = Vec::new();
+    row_reduction(
+        &mut matrix,
+        stop_at_echelon,
+        normalize_pivots,
+        |op, result| steps.push((op, result.clone())),
+    );
+    steps
+}
+
+/// Gaussian elimination in place, calling `on_step` with each row operation and its result.
+fn row_reduction<T: NumAssignRef + Clone + Pivot>(
+    matrix: &mut Matrix<T>,
+    stop_at_echelon: bool,
+    normalize_pivots: bool,
+    mut on_step: impl FnMut(RowOp<T>, &Matrix<T>),
+) {
+    let (m, n) = matrix.shape();
+    let mut apply = |matrix: &mut Matrix<T>, op: RowOp<T>| {
+        op.apply(matrix);
+        on_step(op, matrix);
+    };
     let mut target_row = 0;
-    for j in 0..matrix.shape().1 {
-        let mut first_non_zero_row = None;
-        for i in target_row..matrix.shape().0 {
-            if !matrix[i][j].is_zero() {
-                first_non_zero_row = Some(i);
-                break;
-            }
-        }
-        let first_non_zero_row = match first_non_zero_row {
-            Some(i) => i,
+    for j in 0..n {
+        let column = (target_row..m).map(|i| &matrix[i][j]).collect_vec();
+        let pivot_row = match T::choose_pivot(&column) {
+            Some(i) => target_row + i,
             None => continue,
         };
-        if target_row != first_non_zero_row {
-            matrix.swap_row(target_row, first_non_zero_row);
-            steps.push((
-                format!(
-                    r"r_{{{}}} \leftrightarrow r_{{{first_non_zero_row}}}",
-                    target_row + 1
-                ),
-                matrix.clone(),
-            ));
+        if target_row != pivot_row {
+            apply(matrix, RowOp::Swap(target_row, pivot_row));
         }
-        if !matrix[target_row][j].is_one() {
-            let mul_inv = BigRational::one() / &matrix[target_row][j];
-            matrix.scale_row(target_row, &mul_inv);
-            steps.push((
-                format!(
-                    r"r_{{{}}} \times {}",
-                    target_row + 1,
-                    mul_inv.to_tex_with_paren()
-                ),
-                matrix.clone(),
-            ));
+        if normalize_pivots && !matrix[target_row][j].is_one() {
+            let mut mul_inv = T::one();
+            mul_inv /= &matrix[target_row][j];
+            apply(matrix, RowOp::Scale(target_row, mul_inv));
         }
-        for i in 0..matrix.shape().0 {
-            if i != target_row && !matrix[i][j].is_zero() {
-                let factor = -matrix[i][j].clone();
-                matrix.scale_add_row(target_row, &factor, i);
-                steps.push((
-                    format!(
-                        r"r_{{{}}} {} r_{{{}}}",
-                        i + 1,
-                        factor.to_tex_with_sign_ignore_one(),
-                        target_row + 1
-                    ),
-                    matrix.clone(),
-                ));
+        let first_row = if stop_at_echelon { target_row + 1 } else { 0 };
+        for i in first_row..m {
+            if i != target_row && !matrix[i][j].is_negligible() {
+                let mut factor = T::zero();
+                factor -= &matrix[i][j];
+                factor /= &matrix[target_row][j];
+                apply(
+                    matrix,
+                    RowOp::ScaleAdd {
+                        src: target_row,
+                        factor,
+                        dst: i,
+                    },
+                );
             }
         }
         target_row += 1;
     }
-    steps
+}
+
+#[test]
+fn test_row_op_to_tex() {
+    assert_eq!(RowOp::Swap(0, 2).to_tex(), r"r_{1} \leftrightarrow r_{3}");
+    let factor = BigRational::new((-1).into(), 2.into());
+    assert_eq!(
+        RowOp::Scale(1, factor.clone()).to_tex(),
+        format!(r"r_{{2}} \times {}", factor.to_tex_with_paren())
+    );
+    assert_eq!(
+        RowOp::ScaleAdd {
+            src: 0,
+            factor,
+            dst: 1
+        }
+        .to_tex(),
+        r"r_{2} -\frac{1}{2} r_{1}"
+    );
+}
+
+#[test]
+fn test_elementary_matrices() {
+    let matrix = "0 2 4\n1 1 1".parse::<Matrix<BigRational>>().unwrap();
+    let steps = reduced_row_echelon_form_with_steps(&matrix);
+    let product = steps.iter().fold(matrix.clone(), |acc, (op, result)| {
+        let e = op.elementary_matrix(2);
+        let acc = Matrix(
+            (0..2)
+                .map(|i| {
+                    (0..3)
+                        .map(|j| {
+                            (0..2)
+                                .map(|k| &e[i][k] * &acc[k][j])
+                                .fold(BigRational::zero(), |x, y| x + y)
+                        })
+                        .collect_vec()
+                })
+                .collect_vec(),
+        );
+        assert_eq!(&acc, result);
+        acc
+    });
+    assert_eq!(product, matrix.reduced_row_echelon_form());
 }
 
 pub trait ReducedRowEchelonForm {
     fn reduced_row_echelon_form(&self) -> Self;
+
+    /// Whether the nonzero rows come first with leading ones in strictly increasing columns,
+    /// each being the only nonzero entry of its column.
+    fn is_rref(&self) -> bool;
 }
 
 impl ReducedRowEchelonForm for Matrix<BigRational> {
@@ -131,6 +297,39 @@ impl ReducedRowEchelonForm for Matrix<BigRational> {
             None => self.clone(),
         }
     }
+
+    fn is_rref(&self) -> bool {
+        let leading = self
+            .iter()
+            .map(|r| r.iter().position(|x| !x.is_zero()))
+            .collect_vec();
+        let pivots = leading.iter().map_while(|&j| j).collect_vec();
+        leading[pivots.len()..].iter().all(Option::is_none)
+            && pivots.iter().tuple_windows().all(|(a, b)| a < b)
+            && pivots.iter().enumerate().all(|(i, &j)| {
+                self[i][j].is_one()
+                    && self
+                        .iter()
+                        .enumerate()
+                        .all(|(k, r)| k == i || r[j].is_zero())
+            })
+    }
+}
+
+#[test]
+fn test_is_rref() {
+    let is_rref = |s: &str| s.parse::<Matrix<BigRational>>().unwrap().is_rref();
+    assert!(is_rref("1 0 2\n0 1 3\n0 0 0"));
+    assert!(is_rref("0 1 5 0\n0 0 0 1"));
+    assert!(is_rref("0 0\n0 0"));
+    assert!(!is_rref("1 2\n0 2"));
+    assert!(!is_rref("1 2\n0 1"));
+    assert!(!is_rref("0 0\n1 0"));
+    assert!(!is_rref("0 1\n1 0"));
+    let matrix = "1 2\n3 4".parse::<Matrix<BigRational>>().unwrap();
+    assert!(!matrix.is_rref());
+    assert!(!reduced_row_echelon_form_with_steps(&matrix).is_empty());
+    assert!(matrix.reduced_row_echelon_form().is_rref());
 }
 
 pub trait Rank {
@@ -146,8 +345,316 @@ impl Rank for Matrix<BigRational> {
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq)]
-pub struct ReducedRowEchelonFormSolver;
+pub trait Determinant {
+    fn determinant(&self) -> BigRational;
+}
+
+impl Determinant for Matrix<BigRational> {
+    fn determinant(&self) -> BigRational {
+        let mut matrix = self.clone();
+        let (n, _) = matrix.shape();
+        let mut determinant = BigRational::one();
+        for j in 0..n {
+            let pivot_row = match (j..n).find(|&i| !matrix[i][j].is_zero()) {
+                Some(i) => i,
+                None => return BigRational::zero(),
+            };
+            if pivot_row != j {
+                matrix.swap_row(pivot_row, j);
+                determinant = -determinant;
+            }
+            let pivot = matrix[j][j].clone();
+            for i in (j + 1)..n {
+                let factor = -&matrix[i][j] / &pivot;
+                matrix.scale_add_row(j, &factor, i);
+            }
+            determinant *= pivot;
+        }
+        determinant
+    }
+}
+
+#[test]
+fn test_determinant() {
+    let matrix = "2 1 -5 1\n1 -3 0 -6\n0 2 -1 2\n1 4 -7 6"
+        .parse::<Matrix<BigRational>>()
+        .unwrap();
+    assert_eq!(matrix.determinant(), BigRational::from_integer(27.into()));
+    let matrix = "1 2\n2 4".parse::<Matrix<BigRational>>().unwrap();
+    assert_eq!(matrix.determinant(), BigRational::zero());
+    let matrix = "0 1\n1 0".parse::<Matrix<BigRational>>().unwrap();
+    assert_eq!(matrix.determinant(), -BigRational::one());
+}
+
+/// Entries of absolute value below this are zero in the approximate reduction.
+const APPROXIMATE_TOLERANCE: f64 = 1e-9;
+
+/// Exact reduction of matrices with more entries is slow, so they are reduced approximately
+/// unless the first line is `exact`.
+const APPROXIMATE_DEFAULT_SIZE: usize = 100;
+
+/// Reduced row echelon form in `f64` with partial pivoting, entries below
+/// [`APPROXIMATE_TOLERANCE`] in absolute value are treated as zero.
+fn approximate_reduced_row_echelon_form(matrix: &Matrix<f64>) -> Matrix<f64> {
+    let mut matrix = matrix.clone();
+    row_reduction(&mut matrix, false, true, |_, _| {});
+    matrix.map(|x| if x.is_negligible() { 0.0 } else { *x })
+}
+
+/// At most 6 decimal places without trailing zeros.
+fn f64_to_tex(x: f64) -> String {
+    let s = format!("{x:.6}");
+    let s = s.trim_end_matches('0').trim_end_matches('.');
+    if s == "-0" {
+        "0".to_string()
+    } else {
+        s.to_string()
+    }
+}
+
+#[test]
+fn test_approximate_reduced_row_echelon_form() {
+    let matrix = Matrix(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+    let rref = approximate_reduced_row_echelon_form(&matrix);
+    assert_eq!(
+        rref.map(|x| f64_to_tex(*x)).to_string(),
+        r"1 & 0 \\[1ex] 0 & 1"
+    );
+    let matrix = Matrix(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 6.1]]);
+    let rref = approximate_reduced_row_echelon_form(&matrix);
+    assert_eq!(
+        rref.map(|x| f64_to_tex(*x)).to_string(),
+        r"1 & 2 & 0 \\[1ex] 0 & 0 & 1"
+    );
+    let matrix = Matrix(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+    let rref = approximate_reduced_row_echelon_form(&matrix);
+    assert_eq!(
+        rref.map(|x| f64_to_tex(*x)).to_string(),
+        r"1 & 2 \\[1ex] 0 & 0"
+    );
+    assert_eq!(f64_to_tex(1.0 / 3.0), "0.333333");
+    assert_eq!(f64_to_tex(-1e-12), "0");
+}
+
+/// Answer of [`ReducedRowEchelonFormSolver`] in TeX.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReducedRowEchelonFormTex {
+    pub matrix: String,
+    pub rref: String,
+    pub csv: String,
+    pub rank: usize,
+    /// Exact reduced row echelon form, `None` if reduced approximately.
+    pub exact_rref: Option<Matrix<BigRational>>,
+    /// `None` if no row operation was performed.
+    pub steps: Option<String>,
+    /// Whether the input matrix is already in reduced row echelon form.
+    pub already_reduced: bool,
+    /// Each row operation along with its result, empty if reduced approximately.
+    pub row_ops: Vec<(RowOp, Matrix<BigRational>)>,
+    /// `E_k \cdots E_1 A = R` followed by each elementary matrix, `None` along with `steps`.
+    pub elementary_matrices: Option<String>,
+    /// Reduced in `f64` without steps instead of exactly, requested by a first line `approx` or
+    /// by default for large matrices.
+    pub approximate: bool,
+    /// Whether the matrix is large enough to be reduced approximately by default.
+    pub large: bool,
+}
+
+/// Parse `input` and reduce it, the error is a message to show.
+pub fn reduced_row_echelon_form_tex(input: &str) -> Result<ReducedRowEchelonFormTex, String> {
+    let (approximate, rest) = split_keyword_line(input, "approx");
+    let (exact, rest) = if approximate {
+        (false, rest)
+    } else {
+        split_keyword_line(rest, "exact")
+    };
+    let (csv, rest) = split_keyword_line(rest, "csv");
+    let line_offset = lines_before(input, rest);
+    let input = rest;
+    let matrix = if csv {
+        Matrix::from_csv(input).map_err(|e| format!("CSV 无法解析: {e}"))?
+    } else {
+        match input.parse::<Matrix<BigRational>>() {
+            Ok(matrix) => matrix,
+            Err(MatrixParseError::Empty) => return Err("输入为空.".to_string()),
+            Err(e) => return Err(e.offset_line(line_offset).to_string()),
+        }
+    };
+    let (m, n) = matrix.shape();
+    let large = m * n > APPROXIMATE_DEFAULT_SIZE;
+    let approximate = approximate || large && !exact;
+    if approximate {
+        let rref =
+            approximate_reduced_row_echelon_form(&matrix.map(|x| x.to_f64().unwrap_or(f64::NAN)));
+        return Ok(ReducedRowEchelonFormTex {
+            matrix: matrix.to_tex(),
+            rref: rref.map(|x| f64_to_tex(*x)).to_string(),
+            csv: rref
+                .iter()
+                .map(|row| row.iter().map(|x| f64_to_tex(*x)).join(","))
+                .join("\n"),
+            rank: rref.iter().filter(|r| r.iter().any(|x| *x != 0.0)).count(),
+            exact_rref: None,
+            steps: None,
+            already_reduced: false,
+            row_ops: Vec::new(),
+            elementary_matrices: None,
+            approximate,
+            large,
+        });
+    }
+    let steps = reduced_row_echelon_form_with_steps(&matrix);
+    let row_ops = steps.clone();
+    let rref = steps
+        .last()
+        .map_or_else(|| matrix.clone(), |(_, result)| result.clone());
+    let elementary_matrices = if steps.is_empty() {
+        None
+    } else {
+        let m = matrix.shape().0;
+        Some(format!(
+            r"\begin{{align*}} & {} \bm A = \bm R \\[3em] {} \end{{align*}}",
+            (1..=steps.len())
+                .rev()
+                .map(|k| format!("E_{{{k}}}"))
+                .join(" "),
+            steps
+                .iter()
+                .enumerate()
+                .map(|(k, (op, _))| {
+                    format!(
+                        r"& E_{{{}}} = \begin{{pmatrix}}{}\end{{pmatrix}} \quad ({})",
+                        k + 1,
+                        op.elementary_matrix(m).to_tex(),
+                        op.to_tex()
+                    )
+                })
+                .join(r" \\[3em] ")
+        ))
+    };
+    let steps = if steps.is_empty() {
+        None
+    } else {
+        Some(steps_to_tex(&matrix.to_tex(), &steps))
+    };
+    Ok(ReducedRowEchelonFormTex {
+        matrix: matrix.to_tex(),
+        rref: rref.to_tex(),
+        csv: rref.to_csv(),
+        rank: rref.rank(),
+        exact_rref: Some(rref),
+        steps,
+        already_reduced: matrix.is_rref(),
+        row_ops,
+        elementary_matrices,
+        approximate,
+        large,
+    })
+}
+
+/// Total number of row operations broken down by kind, e.g. `共 3 步 (交换 1 次, 倍乘 1 次, 倍加 1 次)`.
+pub fn row_ops_summary(steps: &[(RowOp, Matrix<BigRational>)]) -> String {
+    let count = |f: fn(&RowOp) -> bool| steps.iter().filter(|(op, _)| f(op)).count();
+    format!(
+        "共 {} 步 (交换 {} 次, 倍乘 {} 次, 倍加 {} 次)",
+        steps.len(),
+        count(|op| matches!(op, RowOp::Swap(..))),
+        count(|op| matches!(op, RowOp::Scale(..))),
+        count(|op| matches!(op, RowOp::ScaleAdd { .. })),
+    )
+}
+
+#[test]
+fn test_row_ops_summary() {
+    let matrix = "0 2\n1 1".parse::<Matrix<BigRational>>().unwrap();
+    assert_eq!(
+        row_ops_summary(&reduced_row_echelon_form_with_steps(&matrix)),
+        "共 3 步 (交换 1 次, 倍乘 1 次, 倍加 1 次)"
+    );
+}
+
+/// The matrix followed by each row operation and its result, one per line.
+pub fn steps_to_tex(matrix: &str, steps: &[(RowOp, Matrix<BigRational>)]) -> String {
+    format!(
+        r"\begin{{align*}} \begin{{pmatrix}}{}\end{{pmatrix}} {} \end{{align*}}",
+        matrix,
+        steps
+            .iter()
+            .map(|(op, result)| {
+                let step = op.to_tex();
+                format!(
+                    r"{}{step}{}{}{}",
+                    r"& \begin{CD}\\@>{",
+                    r"}>>\\\end{CD} \begin{pmatrix}",
+                    result.map(BigRational::to_tex),
+                    r"\end{pmatrix}"
+                )
+            })
+            .join(r" \\[3em] ")
+    )
+}
+
+#[test]
+fn test_reduced_row_echelon_form_tex() {
+    let answer = reduced_row_echelon_form_tex("1 2\n2 4").unwrap();
+    assert_eq!(answer.rref, r"1 & 2 \\[1ex] 0 & 0");
+    assert_eq!(answer.rank, 1);
+    assert!(answer.steps.unwrap().starts_with(r"\begin{align*}"));
+    assert!(!answer.already_reduced);
+    let answer = reduced_row_echelon_form_tex("1 0\n0 1").unwrap();
+    assert_eq!(answer.steps, None);
+    assert!(answer.already_reduced);
+    assert_eq!(answer.elementary_matrices, None);
+    assert_eq!(
+        reduced_row_echelon_form_tex(""),
+        Err("输入为空.".to_string())
+    );
+    assert_eq!(
+        reduced_row_echelon_form_tex("approx"),
+        Err("输入为空.".to_string())
+    );
+    assert_eq!(
+        reduced_row_echelon_form_tex("\napprox\n1 2\n3"),
+        Err("矩阵第 4 行有 1 个元素, 应为 2 个".to_string())
+    );
+    assert_eq!(
+        reduced_row_echelon_form_tex("approx\ncsv\n1,2\n3"),
+        Err("CSV 无法解析: 第 2 行有 1 列, 应为 2 列".to_string())
+    );
+    assert_eq!(
+        reduced_row_echelon_form_tex("1,2\n3"),
+        Err("矩阵第 2 行有 1 个元素, 应为 2 个".to_string())
+    );
+    let answer = reduced_row_echelon_form_tex("approx\n1 2\n3 4").unwrap();
+    assert!(answer.approximate);
+    assert_eq!(answer.rref, r"1 & 0 \\[1ex] 0 & 1");
+    assert_eq!(answer.rank, 2);
+    assert_eq!(answer.steps, None);
+    let answer = reduced_row_echelon_form_tex("approx\n3 0.3").unwrap();
+    // 0.3 / 3 is 0.09999999999999999 in f64
+    assert_eq!(answer.csv, "1,0.1");
+    let large = (0..11)
+        .map(|i| (0..10).map(|j| if i == j { "1" } else { "0" }).join(" "))
+        .join("\n");
+    let answer = reduced_row_echelon_form_tex(&large).unwrap();
+    assert!(answer.large && answer.approximate);
+    let answer = reduced_row_echelon_form_tex(&format!("exact\n{large}")).unwrap();
+    assert!(answer.large && !answer.approximate);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReducedRowEchelonFormSolver {
+    /// Also show the elementary matrix of each step.
+    pub elementary_matrices: bool,
+}
+
+impl Default for ReducedRowEchelonFormSolver {
+    fn default() -> Self {
+        Self {
+            elementary_matrices: true,
+        }
+    }
+}
 
 impl Solver for ReducedRowEchelonFormSolver {
     fn id(&self) -> String {
@@ -159,7 +666,7 @@ impl Solver for ReducedRowEchelonFormSolver {
     }
 
     fn description(&self) -> View {
-        "输入元素为整数或分数的矩阵.".into_view()
+        format!("输入元素为整数, 小数或分数的矩阵, 元素间以空格或逗号分隔. 超过 {APPROXIMATE_DEFAULT_SIZE} 个元素的矩阵默认使用浮点数近似计算, 首行输入 approx 或 exact 时分别强制近似或精确计算. 首行 (或 approx, exact 的下一行) 输入 csv 时按 CSV 解析, 否则逗号也按矩阵格式解析.").into_view()
     }
 
     fn default_input(&self) -> String {
@@ -171,57 +678,148 @@ impl Solver for ReducedRowEchelonFormSolver {
         .to_string()
     }
 
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![
+            ("默认".to_string(), self.default_input()),
+            (
+                "奇异矩阵".to_string(),
+                indoc! {"
+                    1 2 3
+                    4 5 6
+                    7 8 9
+                "}
+                .to_string(),
+            ),
+            (
+                "单位矩阵".to_string(),
+                indoc! {"
+                    1 0 0
+                    0 1 0
+                    0 0 1
+                "}
+                .to_string(),
+            ),
+            (
+                "分数矩阵".to_string(),
+                indoc! {"
+                    1/2 1/3 1/4
+                    1/3 1/4 1/5
+                "}
+                .to_string(),
+            ),
+            (
+                "CSV".to_string(),
+                indoc! {"
+                    csv
+                    1,2,3
+                    4,5,6
+                "}
+                .to_string(),
+            ),
+        ]
+    }
+
     fn solve(&self, input: String) -> View {
-        let matrix = match input.parse::<Matrix<BigRational>>() {
-            Ok(matrix) => matrix,
-            Err(_) => {
-                return view! {
-                    <p> "Failed to parse." </p>
-                }
-                .into_view()
-            }
+        let answer = match reduced_row_echelon_form_tex(&input) {
+            Ok(answer) => answer,
+            Err(e) => return self.render_error(&e),
         };
-        let steps = reduced_row_echelon_form_with_steps(&matrix);
-        if steps.is_empty() {
-            view! {
-                <KaTeX expr={ format!(r"\begin{{pmatrix}}{}\end{{pmatrix}} \text{{已是行最简形矩阵.}}", matrix.map(BigRational::to_tex)) } />
-            }.into_view()
+        let backend = if answer.approximate && answer.large {
+            format!("近似计算: 矩阵超过 {APPROXIMATE_DEFAULT_SIZE} 个元素, 默认使用 f64 和部分主元消元, 绝对值小于 {APPROXIMATE_TOLERANCE:e} 的元素视为零, 结果可能有舍入误差. 可以在首行输入 exact 改用有理数精确计算.")
+        } else if answer.approximate {
+            format!("近似计算: 使用 f64 和部分主元消元, 绝对值小于 {APPROXIMATE_TOLERANCE:e} 的元素视为零, 结果可能有舍入误差.")
         } else {
-            let rref = steps.last().unwrap().1.clone();
-            let rank = rref.rank();
-            let rref = rref.to_tex();
-            let matrix = matrix.to_tex();
-            let steps = format!(
-                r"\begin{{align*}} \begin{{pmatrix}}{}\end{{pmatrix}} {} \end{{align*}}",
-                matrix,
-                steps
-                    .into_iter()
-                    .map(|(step, result)| {
-                        format!(
-                            r"{}{step}{}{}{}",
-                            r"& \begin{CD}\\@>{",
-                            r"}>>\\\end{CD} \begin{pmatrix}",
-                            result.map(BigRational::to_tex),
-                            r"\end{pmatrix}"
-                        )
-                    })
-                    .join(r" \\[3em] ")
-            );
-            view! {
+            "精确计算: 使用有理数.".to_string()
+        };
+        let backend = view! {
+            <div class="mb-10">
+                <p class="font-bold mb-2"> "计算方式" </p>
+                <p> { backend } </p>
+            </div>
+        };
+        if answer.approximate {
+            return view! {
+                { backend }
                 <div class="mb-10">
-                    <p class="font-bold mb-2"> "行最简形矩阵" </p>
-                    <KaTeX expr={ format!(r"\begin{{pmatrix}}{}\end{{pmatrix}}", rref) } />
+                    <p class="font-bold mb-2"> "行最简形矩阵 (近似)" </p>
+                    <KaTeX expr={ format!(r"\begin{{pmatrix}}{}\end{{pmatrix}}", answer.rref) } />
                 </div>
                 <div class="mb-10">
-                    <p class="font-bold mb-2"> "矩阵的秩" </p>
-                    <KaTeX expr={ format!(r"\mathrm{{r}}\begin{{pmatrix}}{}\end{{pmatrix}} = {}", matrix, rank) } />
+                    <p class="font-bold mb-2"> "行最简形矩阵 (CSV)" </p>
+                    <pre> { answer.csv } </pre>
                 </div>
                 <div class="mb-10">
-                    <p class="font-bold mb-2"> "初等行变换过程" </p>
-                    <KaTeX display_mode=true fleqn=true expr={ steps } />
+                    <p class="font-bold mb-2"> "矩阵的秩" </p>
+                    <KaTeX expr={ format!(r"\mathrm{{r}}\begin{{pmatrix}}{}\end{{pmatrix}} = {}", answer.matrix, answer.rank) } />
                 </div>
             }
-            .into_view()
+            .into_view();
         }
+        let decimal = create_rw_signal(false);
+        let digits = create_rw_signal(DEFAULT_DECIMAL_DIGITS);
+        let shown_steps = create_rw_signal(None);
+        let steps = match answer.steps {
+            _ if answer.already_reduced => view! {
+                <KaTeX expr={ format!(r"\begin{{pmatrix}}{}\end{{pmatrix}} \text{{已是行最简形矩阵.}}", answer.matrix) } />
+            }
+            .into_view(),
+            None => view! {
+                <KaTeX expr={ format!(r"\begin{{pmatrix}}{}\end{{pmatrix}} \text{{无需初等行变换.}}", answer.matrix) } />
+            }
+            .into_view(),
+            Some(steps) => view! {
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "行最简形矩阵" <DecimalToggle decimal digits /> </p>
+                    {
+                        let rref = answer.rref.clone();
+                        let exact_rref = answer.exact_rref.clone().unwrap();
+                        move || {
+                            let rref = if decimal() {
+                                exact_rref.to_decimal_string(digits())
+                            } else {
+                                rref.clone()
+                            };
+                            view! { <KaTeX expr={ format!(r"\begin{{pmatrix}}{}\end{{pmatrix}}", rref) } /> }
+                        }
+                    }
+                </div>
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "行最简形矩阵 (CSV)" </p>
+                    <pre> { answer.csv } </pre>
+                </div>
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "矩阵的秩" </p>
+                    <KaTeX expr={ format!(r"\mathrm{{r}}\begin{{pmatrix}}{}\end{{pmatrix}} = {}", answer.matrix, answer.rank) } />
+                </div>
+                <div class="mb-10">
+                    <p class="font-bold mb-2"> "初等行变换过程" <StepPlayback count={ answer.row_ops.len() } shown=shown_steps /> </p>
+                    {
+                        let matrix = answer.matrix.clone();
+                        let row_ops = answer.row_ops.clone();
+                        move || match shown_steps() {
+                            Some(shown) => view! {
+                                <KaTeX display_mode=true fleqn=true expr={ steps_to_tex(&matrix, &row_ops[..shown]) } />
+                            },
+                            None => view! { <KaTeX display_mode=true fleqn=true expr={ steps.clone() } /> },
+                        }
+                    }
+                    <p class="mt-2"> { row_ops_summary(&answer.row_ops) } </p>
+                </div>
+                {
+                    match answer.elementary_matrices {
+                        Some(elementary_matrices) if self.elementary_matrices => view! {
+                            <div class="mb-10">
+                                <p class="font-bold mb-2"> "初等矩阵" </p>
+                                <KaTeX display_mode=true fleqn=true expr={ elementary_matrices } />
+                            </div>
+                        }
+                        .into_view(),
+                        _ => ().into_view(),
+                    }
+                }
+            }
+            .into_view(),
+        };
+        view! { {backend} {steps} }.into_view()
     }
 }
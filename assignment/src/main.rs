@@ -16,7 +16,8 @@ fn main() {
             "编译原理",
             Shiyanyi::builder()
                 .solver_default::<comp::LexerSolver>()
-                .solver_default::<comp::ParserSolver>(),
+                .solver_default::<comp::ParserSolver>()
+                .solver_default::<comp::SlrParserSolver>(),
         )
         .section(
             "linalg",
@@ -24,8 +25,13 @@ fn main() {
             Shiyanyi::builder()
                 .solver_default::<linalg::InversionNumberSolver>()
                 .solver_default::<linalg::ReducedRowEchelonFormSolver>()
+                .solver_default::<linalg::RowEchelonSolver>()
+                .solver_default::<linalg::RankSolver>()
                 .solver_default::<linalg::LinearEquationsSolver>()
-                .solver_default::<linalg::MaximalLinearlyIndependentSolver>(),
+                .solver_default::<linalg::CramerSolver>()
+                .solver_default::<linalg::MaximalLinearlyIndependentSolver>()
+                .solver_default::<linalg::CharacteristicPolynomialSolver>()
+                .solver_default::<linalg::HornerSolver>(),
         )
         .section(
             "discrete",
@@ -34,7 +40,16 @@ fn main() {
                 .solver_default::<discrete::Exp1>()
                 .solver_default::<discrete::Exp2>()
                 .solver_default::<discrete::Exp3>()
-                .solver_default::<discrete::Exp4>(),
+                .solver_default::<discrete::Exp4>()
+                .solver_default::<discrete::SpanningTreeCountSolver>()
+                .solver_default::<discrete::EquivalenceSolver>()
+                .solver_default::<discrete::QuineMcCluskeySolver>()
+                .solver_default::<discrete::BooleanSimplificationSolver>()
+                .solver_default::<discrete::SetOperationSolver>()
+                .solver_default::<discrete::CombinatoricsSolver>()
+                .solver_default::<discrete::GcdSolver>()
+                .solver_default::<discrete::PrimeFactorizationSolver>()
+                .solver_default::<discrete::ModularExponentiationSolver>(),
         )
         .build()
         .boot("shiyanyi");
@@ -3,6 +3,7 @@ use std::{
     fmt,
     io::Read,
     rc::Rc,
+    time::Duration,
 };
 
 use base64::prelude::*;
@@ -16,15 +17,44 @@ use leptos::*;
 use leptos_dom::helpers::*;
 use leptos_meta::*;
 use leptos_router::*;
+use leptos_use::use_media_query;
 use stylers::style_str;
 use wasm_bindgen::prelude::*;
 use web_sys::HtmlScriptElement;
 
+mod qr;
+pub use qr::QrCode;
+
 #[macro_export]
 macro_rules! println {
     ($($t:tt)*) => (leptos::logging::log!($($t)*))
 }
 
+/// Strip leading and trailing slashes, panics if any segment is not url safe.
+fn normalize_base_path(base_path: &str) -> String {
+    let base_path = base_path.trim_matches('/');
+    if !base_path.is_empty()
+        && base_path.split('/').any(|segment| {
+            segment.is_empty()
+                || segment.contains(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        })
+    {
+        panic!("base path is not url safe: {}", base_path);
+    }
+    base_path.to_string()
+}
+
+#[test]
+fn test_normalize_base_path() {
+    assert_eq!(normalize_base_path("g/assignments"), "g/assignments");
+    assert_eq!(normalize_base_path("/g/assignments/"), "g/assignments");
+    assert_eq!(normalize_base_path("assignments/"), "assignments");
+    assert_eq!(normalize_base_path("/"), "");
+    assert_eq!(normalize_base_path(""), "");
+    assert!(std::panic::catch_unwind(|| normalize_base_path("g//assignments")).is_err());
+    assert!(std::panic::catch_unwind(|| normalize_base_path("g/a b")).is_err());
+}
+
 #[must_use]
 #[derive(Debug)]
 pub struct EmptyShiyanyiBuilder {
@@ -32,9 +62,10 @@ pub struct EmptyShiyanyiBuilder {
 }
 
 impl EmptyShiyanyiBuilder {
+    /// Sets the path all solvers are routed under, replacing any previously set one.
     pub fn base_path(self, base_path: impl ToString) -> Self {
         Self {
-            base_path: base_path.to_string(),
+            base_path: normalize_base_path(&base_path.to_string()),
         }
     }
 
@@ -75,9 +106,12 @@ pub struct ShiyanyiBuilder {
 }
 
 impl ShiyanyiBuilder {
+    /// Sets the path all solvers are routed under, replacing any previously set one. As with
+    /// [`EmptyShiyanyiBuilder::base_path`], the last call wins regardless of the children added
+    /// before or after it. Only the outermost builder may have a base path.
     pub fn base_path(self, base_path: impl ToString) -> Self {
         Self {
-            base_path: base_path.to_string(),
+            base_path: normalize_base_path(&base_path.to_string()),
             ..self
         }
     }
@@ -87,6 +121,9 @@ impl ShiyanyiBuilder {
         if id.contains(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_')) {
             panic!("id is not url safe: {}", id);
         }
+        if !children.base_path.is_empty() {
+            panic!("base path of section {} would be ignored", id);
+        }
         self.children.push(SectionOrSolver::Section {
             id,
             title: title.to_string(),
@@ -103,6 +140,7 @@ impl ShiyanyiBuilder {
         self.children.push(SectionOrSolver::Solver {
             id,
             toc_title: solver.toc_title(),
+            tags: solver.tags(),
             solver: Rc::new(solver),
         });
         self
@@ -138,6 +176,16 @@ impl Shiyanyi {
         }
     }
 
+    /// Shorthand for `Shiyanyi::builder().base_path(base_path)`.
+    pub fn builder_with_base(base_path: impl ToString) -> EmptyShiyanyiBuilder {
+        Self::builder().base_path(base_path)
+    }
+
+    /// Full paths of all solvers in order, including the base path.
+    pub fn solver_paths(&self) -> Vec<String> {
+        solver_paths(&self.base_path, &self.children)
+    }
+
     pub fn boot(self, mount_point_element_id: &str) {
         let mount_point: web_sys::HtmlElement = document()
             .get_element_by_id(mount_point_element_id)
@@ -158,6 +206,93 @@ impl Shiyanyi {
     }
 }
 
+fn solver_paths(base_path: &str, solver_tree: &[SectionOrSolver]) -> Vec<String> {
+    solver_tree
+        .iter()
+        .flat_map(|child| {
+            let path = match child {
+                SectionOrSolver::Section { id, .. } | SectionOrSolver::Solver { id, .. } => {
+                    if base_path.is_empty() {
+                        id.clone()
+                    } else {
+                        format!("{}/{}", base_path, id)
+                    }
+                }
+            };
+            match child {
+                SectionOrSolver::Section { children, .. } => solver_paths(&path, children),
+                SectionOrSolver::Solver { .. } => vec![path],
+            }
+        })
+        .collect_vec()
+}
+
+#[test]
+fn test_solver_paths() {
+    #[derive(Default)]
+    struct TestSolver;
+    impl Solver for TestSolver {
+        fn id(&self) -> String {
+            "test".to_string()
+        }
+        fn title(&self) -> String {
+            "Test".to_string()
+        }
+        fn description(&self) -> View {
+            ().into_view()
+        }
+        fn default_input(&self) -> String {
+            String::new()
+        }
+        fn solve(&self, _input: String) -> View {
+            ().into_view()
+        }
+    }
+    let shiyanyi = Shiyanyi::builder()
+        .base_path("/g/assignments/")
+        .solver_default::<TestSolver>()
+        .section(
+            "a",
+            "A",
+            Shiyanyi::builder().solver_default::<TestSolver>().section(
+                "b",
+                "B",
+                Shiyanyi::builder().solver_default::<TestSolver>(),
+            ),
+        )
+        .build();
+    assert_eq!(
+        shiyanyi.solver_paths(),
+        [
+            "g/assignments/test",
+            "g/assignments/a/test",
+            "g/assignments/a/b/test"
+        ]
+    );
+    let shiyanyi = Shiyanyi::builder().solver_default::<TestSolver>().build();
+    assert_eq!(shiyanyi.solver_paths(), ["test"]);
+    // the last base path wins regardless of where it is set
+    let shiyanyi = Shiyanyi::builder_with_base("a")
+        .base_path("b")
+        .solver_default::<TestSolver>()
+        .build();
+    assert_eq!(shiyanyi.base_path, "b");
+    let shiyanyi = Shiyanyi::builder_with_base("a")
+        .solver_default::<TestSolver>()
+        .base_path("c/")
+        .build();
+    assert_eq!(shiyanyi.base_path, "c");
+    assert_eq!(shiyanyi.solver_paths(), ["c/test"]);
+    assert!(std::panic::catch_unwind(|| {
+        Shiyanyi::builder().section(
+            "a",
+            "A",
+            Shiyanyi::builder_with_base("b").solver_default::<TestSolver>(),
+        )
+    })
+    .is_err());
+}
+
 #[derive(Clone)]
 enum SectionOrSolver {
     Section {
@@ -168,6 +303,7 @@ enum SectionOrSolver {
     Solver {
         id: String,
         toc_title: String,
+        tags: Vec<String>,
         solver: SolverObject,
     },
 }
@@ -188,11 +324,13 @@ impl fmt::Debug for SectionOrSolver {
             Self::Solver {
                 id,
                 toc_title,
+                tags,
                 solver,
             } => f
                 .debug_struct("Solver")
                 .field("id", id)
                 .field("toc_title", toc_title)
+                .field("tags", tags)
                 .field("solver", &solver.title())
                 .finish(),
         }
@@ -210,9 +348,112 @@ pub trait Solver {
     }
     /// Title shown in the main section.
     fn title(&self) -> String;
+    /// Tags shown under the title in table of contents, which can be used to filter solvers.
+    fn tags(&self) -> Vec<String> {
+        vec![]
+    }
     fn description(&self) -> View;
     fn default_input(&self) -> String;
+    /// Labeled example inputs, the first one is used when submitting an empty input.
+    fn examples(&self) -> Vec<(String, String)> {
+        vec![("Default".to_string(), self.default_input())]
+    }
+    /// Checked before solving, the error view is shown as the answer instead.
+    #[allow(clippy::result_large_err)]
+    fn validate_input(&self, _input: &str) -> Result<(), View> {
+        Ok(())
+    }
+    /// Whether the input is shown in the colors of [`Solver::highlight_input`].
+    fn highlights_input(&self) -> bool {
+        false
+    }
+    /// Segments `(text, color)` of `input` in order, `None` being the default color.
+    fn highlight_input(&self, input: &str) -> Vec<(String, Option<String>)> {
+        vec![(input.to_string(), None)]
+    }
     fn solve(&self, input: String) -> View;
+    /// Whether inputs separated by lines of [`CASE_DELIMITER`] are solved one by one.
+    fn supports_batch(&self) -> bool {
+        false
+    }
+    /// Pixels of answer height above which the answer is collapsed, `None` to never collapse.
+    fn answer_collapse_height(&self) -> Option<u32> {
+        Some(ANSWER_COLLAPSE_HEIGHT)
+    }
+    /// Whether the input is solved automatically once typing pauses, only for cheap solvers.
+    fn live(&self) -> bool {
+        false
+    }
+    /// Error message shown as the answer, solvers may call it for their own errors.
+    fn render_error(&self, err: &str) -> View {
+        let (class_name, style_val) = style_str! {
+            .error {
+                color: rgb(239, 68, 68);
+                white-space: pre-wrap;
+            }
+        };
+        view! {
+            class = class_name,
+            <Style> { style_val } </Style>
+            <pre class="error"> { err.to_string() } </pre>
+        }
+        .into_view()
+    }
+}
+
+/// Default of [`Solver::answer_collapse_height`].
+pub const ANSWER_COLLAPSE_HEIGHT: u32 = 2000;
+
+/// Line separating the cases of a batch input.
+pub const CASE_DELIMITER: &str = "---";
+
+/// Nonempty cases of a batch input, `None` if there is no delimiter.
+fn split_cases(input: &str) -> Option<Vec<String>> {
+    if !input.lines().any(|line| line.trim() == CASE_DELIMITER) {
+        return None;
+    }
+    Some(
+        input
+            .split('\n')
+            .collect_vec()
+            .split(|line| line.trim() == CASE_DELIMITER)
+            .map(|lines| lines.join("\n"))
+            .filter(|case| !case.trim().is_empty())
+            .collect_vec(),
+    )
+}
+
+#[test]
+fn test_split_cases() {
+    assert_eq!(split_cases("1 2\n3 4"), None);
+    assert_eq!(
+        split_cases("P\n---\nQ ∧ R\n ---\n\n---"),
+        Some(vec!["P".to_string(), "Q ∧ R".to_string()])
+    );
+}
+
+/// Edit distance between two strings in chars.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect_vec();
+    let mut row = (0..=b.len()).collect_vec();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[test]
+fn test_levenshtein() {
+    assert_eq!(levenshtein("", "abc"), 3);
+    assert_eq!(levenshtein("kitten", "sitting"), 3);
+    assert_eq!(levenshtein("linalg/ref", "linalg/rref"), 1);
+    assert_eq!(levenshtein("same", "same"), 0);
 }
 
 pub fn escape_uri_component(s: &str) -> String {
@@ -226,34 +467,124 @@ pub fn unescape_uri_component(s: &str) -> String {
         .unwrap()
 }
 
-fn set_location_hash_encoded(s: &str) {
+/// Appended to the location hash of a submitted input, so that the link opens the answer.
+const SUBMITTED_SUFFIX: &str = "!submitted";
+
+/// Encoded input in the location hash and whether it has been submitted.
+fn split_submitted(hash: &str) -> (&str, bool) {
+    let hash = hash.splitn(2, '#').last().unwrap();
+    match hash.strip_suffix(SUBMITTED_SUFFIX) {
+        Some(hash) => (hash, true),
+        None => (hash, false),
+    }
+}
+
+#[test]
+fn test_split_submitted() {
+    assert_eq!(split_submitted("#abc"), ("abc", false));
+    assert_eq!(split_submitted("abc!submitted"), ("abc", true));
+    assert_eq!(split_submitted("#!submitted"), ("", true));
+}
+
+/// Version of the encoded input, stored as the first byte `HASH_VERSION_MARKER + version`
+/// before deflating. Such bytes never start UTF-8, so hashes without one are version 0.
+const HASH_VERSION: u8 = 1;
+const HASH_VERSION_MARKER: u8 = 0xf8;
+
+/// Deflated and base64 encoded input with the current version.
+pub fn encode_input_to_hash(s: &str) -> String {
+    let payload = [HASH_VERSION_MARKER + HASH_VERSION]
+        .into_iter()
+        .chain(s.bytes())
+        .collect_vec();
     let mut deflate = Vec::new();
-    DeflateEncoder::new(s.as_bytes(), Compression::best())
+    DeflateEncoder::new(payload.as_slice(), Compression::best())
         .read_to_end(&mut deflate)
         .unwrap();
-    let base64 = BASE64_URL_SAFE_NO_PAD.encode(deflate);
+    BASE64_URL_SAFE_NO_PAD.encode(deflate)
+}
+
+/// Inverse of [`encode_input_to_hash`], also accepting version 0 which is the raw input.
+/// `None` if malformed or of an unknown version.
+pub fn decode_input_from_hash(encoded: &str) -> Option<String> {
+    let bytes = BASE64_URL_SAFE_NO_PAD.decode(encoded).ok()?;
+    let mut payload = Vec::new();
+    DeflateDecoder::new(bytes.as_slice())
+        .read_to_end(&mut payload)
+        .ok()?;
+    let payload = match payload.split_first() {
+        Some((&first, rest)) if first >= HASH_VERSION_MARKER => {
+            let version = first - HASH_VERSION_MARKER;
+            if version != HASH_VERSION {
+                leptos::logging::warn!("unknown version {} of the location hash", version);
+                return None;
+            }
+            rest.to_vec()
+        }
+        _ => payload,
+    };
+    String::from_utf8(payload).ok()
+}
+
+#[test]
+fn test_hash_versions() {
+    let input = "1 2\n3 4 ∧ ¬";
+    let encoded = encode_input_to_hash(input);
+    assert_eq!(decode_input_from_hash(&encoded).as_deref(), Some(input));
+    assert_eq!(
+        decode_input_from_hash(&encode_input_to_hash("")).as_deref(),
+        Some("")
+    );
+    let deflate = |payload: &[u8]| {
+        let mut deflate = Vec::new();
+        DeflateEncoder::new(payload, Compression::best())
+            .read_to_end(&mut deflate)
+            .unwrap();
+        BASE64_URL_SAFE_NO_PAD.encode(deflate)
+    };
+    // hashes shared before versioning
+    assert_eq!(
+        decode_input_from_hash(&deflate(input.as_bytes())).as_deref(),
+        Some(input)
+    );
+    assert_eq!(
+        decode_input_from_hash(&deflate(&[HASH_VERSION_MARKER + 7, b'a'])),
+        None
+    );
+    assert_eq!(decode_input_from_hash("not base64!"), None);
+}
+
+fn set_location_hash_encoded(s: &str, submitted: bool) {
+    let mut hash = encode_input_to_hash(s);
+    if submitted {
+        hash.push_str(SUBMITTED_SUFFIX);
+    }
     document()
         .location()
         .unwrap()
-        .set_hash(base64.as_str())
+        .set_hash(hash.as_str())
         .unwrap();
 }
 
-fn get_location_hash_decoded() -> Option<String> {
-    location_hash()
-        .and_then(|h| if h.is_empty() { None } else { Some(h) })
-        .and_then(|h| {
-            BASE64_URL_SAFE_NO_PAD
-                .decode(h.splitn(2, '#').last().unwrap())
-                .ok()
-        })
-        .and_then(|bytes| {
-            let mut s = String::new();
-            DeflateDecoder::new(bytes.as_slice())
-                .read_to_string(&mut s)
-                .ok()
-                .map(|_| s)
-        })
+fn get_location_hash_decoded() -> Option<(String, bool)> {
+    let hash = location_hash().filter(|h| !h.is_empty())?;
+    let (encoded, submitted) = split_submitted(hash.as_str());
+    Some((decode_input_from_hash(encoded)?, submitted))
+}
+
+/// Removes [`SUBMITTED_SUFFIX`] from the location hash without triggering `hashchange`.
+fn strip_submitted_location_hash() {
+    let Some(hash) = location_hash() else {
+        return;
+    };
+    let (encoded, submitted) = split_submitted(hash.as_str());
+    if submitted {
+        window()
+            .history()
+            .unwrap()
+            .replace_state_with_url(&JsValue::NULL, "", Some(format!("#{encoded}").as_str()))
+            .unwrap();
+    }
 }
 
 fn register_katex_load_callback(set_katex_loaded: WriteSignal<bool>, katex_src: &str) {
@@ -281,16 +612,67 @@ fn register_katex_load_callback(set_katex_loaded: WriteSignal<bool>, katex_src:
     closure.forget();
 }
 
+/// Replace the KaTeX script tag with a new one to load it again.
+fn reload_katex_script(
+    set_katex_loaded: WriteSignal<bool>,
+    katex_src: &str,
+    katex_integrity: &str,
+) {
+    let elements = document().get_elements_by_tag_name("script");
+    let elements = (0..elements.length())
+        .filter_map(|i| elements.get_with_index(i))
+        .collect_vec();
+    for element in elements {
+        if element.get_attribute("src").as_deref() == Some(katex_src) {
+            element.remove();
+        }
+    }
+    let element: HtmlScriptElement = document()
+        .create_element("script")
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+    element.set_src(katex_src);
+    element.set_attribute("integrity", katex_integrity).unwrap();
+    element.set_attribute("crossorigin", "anonymous").unwrap();
+    document().head().unwrap().append_child(&element).unwrap();
+    register_katex_load_callback(set_katex_loaded, katex_src);
+}
+
+/// Seconds to wait for KaTeX before showing the retry button.
+const KATEX_LOAD_TIMEOUT: u64 = 10;
+
+fn watch_katex_load(katex_loaded: ReadSignal<bool>, set_katex_failed: WriteSignal<bool>) {
+    set_timeout(
+        move || {
+            if !katex_loaded.get_untracked() {
+                set_katex_failed(true);
+            }
+        },
+        Duration::from_secs(KATEX_LOAD_TIMEOUT),
+    );
+}
+
 #[component]
 fn ShiyanyiComponent(base_path: String, solver_tree: Vec<SectionOrSolver>) -> impl IntoView {
     provide_meta_context();
     let (map_path_solver, set_map_path_solver) = create_signal(HashMap::new());
+    // unsubmitted input of each solver path
+    let (drafts, set_drafts) = create_signal(HashMap::new());
     let (katex_loaded, set_katex_loaded) = create_signal(false);
+    let (katex_failed, set_katex_failed) = create_signal(false);
     let katex_src = "https://cdn.jsdelivr.net/npm/katex@0.16.11/dist/katex.min.js";
+    let katex_integrity = "sha384-7zkQWkzuo3B5mTepMUcHkMB5jZaolc2xDwL6VFqjFALcbeS9Ggm/Yr2r3Dy4lfFg";
     let element = create_node_ref();
     element.on_load(move |_| {
         register_katex_load_callback(set_katex_loaded, katex_src);
+        watch_katex_load(katex_loaded, set_katex_failed);
     });
+    let retry = move |_| {
+        set_katex_failed(false);
+        reload_katex_script(set_katex_loaded, katex_src, katex_integrity);
+        watch_katex_load(katex_loaded, set_katex_failed);
+    };
     let (class_name, style_val) = style_str! {
         :deep(#shiyanyi) {
             flex: 1;
@@ -342,19 +724,119 @@ fn ShiyanyiComponent(base_path: String, solver_tree: Vec<SectionOrSolver>) -> im
                 padding: 0 1rem 0 1rem;
             }
         }
+        .progress {
+            position: fixed;
+            top: 0;
+            left: 0;
+            right: 0;
+            height: 3px;
+            overflow: hidden;
+            z-index: 10;
+        }
+        .progress > div {
+            width: 30%;
+            height: 100%;
+            background-color: rgb(125, 196, 255);
+            animation: progress 1.2s ease-in-out infinite;
+        }
+        @keyframes progress {
+            from {
+                transform: translateX(-100%);
+            }
+            to {
+                transform: translateX(350%);
+            }
+        }
+        .progress-failed {
+            position: fixed;
+            top: 0;
+            left: 0;
+            right: 0;
+            display: flex;
+            justify-content: center;
+            align-items: center;
+            gap: 1rem;
+            padding: 0.5rem;
+            z-index: 10;
+            color: rgb(255, 255, 255);
+            background-color: rgb(239, 68, 68);
+        }
+        .progress-failed > button {
+            padding: 0.1rem 1rem;
+            border-radius: 0.25rem;
+            font-weight: 700;
+            color: rgb(239, 68, 68);
+            background-color: rgb(255, 255, 255);
+        }
+        @media print {
+            .root {
+                padding: 0;
+                color: rgb(0, 0, 0);
+                background: rgb(255, 255, 255);
+            }
+            nav {
+                display: none;
+            }
+            main {
+                width: 100%;
+            }
+            :deep(.input > button),
+            :deep(.input > h2 > button) {
+                display: none;
+            }
+            :deep(.input > select) {
+                display: none;
+            }
+            :deep(.answer > h2 > button),
+            :deep(.answer > button.expander) {
+                display: none;
+            }
+            :deep(.answer > div.collapsed) {
+                max-height: none !important;
+                mask-image: none;
+            }
+            :deep(.section) {
+                padding: 1rem 0;
+                box-shadow: none;
+                background: rgb(255, 255, 255);
+            }
+            :deep(.answer > div) {
+                overflow: visible;
+            }
+            :deep(.katex-display) {
+                overflow: visible;
+            }
+            :deep(table) {
+                break-inside: auto;
+            }
+            :deep(tr) {
+                break-inside: avoid;
+            }
+        }
     };
     view! {
         class = class_name,
         <Style> { style_val } </Style>
         <Link rel="stylesheet" href="https://cdn.jsdelivr.net/npm/katex@0.16.11/dist/katex.min.css" integrity="sha384-nB0miv6/jRmo5UMMR1wu3Gz6NLsoTkbqJghGIsx//Rlm+ZU03BU6SQNC66uf4l5+" crossorigin="anonymous" />
-        <Script defer="" src={ katex_src } integrity="sha384-7zkQWkzuo3B5mTepMUcHkMB5jZaolc2xDwL6VFqjFALcbeS9Ggm/Yr2r3Dy4lfFg" crossorigin="anonymous" />
+        <Script defer="" src={ katex_src } integrity={ katex_integrity } crossorigin="anonymous" />
+        <Show when=move || !katex_loaded()>
+            <Show
+                when=katex_failed
+                fallback=move || view! { class = class_name, <div class="progress"> <div /> </div> }
+            >
+                <div class="progress-failed">
+                    <span> "Failed to load KaTeX." </span>
+                    <button on:click=retry> "Retry" </button>
+                </div>
+            </Show>
+        </Show>
         <Router>
             <div class="root" node_ref=element>
-                <nav> <Contents base_path={ base_path.clone() } solver_tree set_map_path_solver /> </nav>
+                <nav aria-label="Contents"> <Contents base_path={ base_path.clone() } solver_tree set_map_path_solver /> </nav>
                 <main>
-                    <Routes base={ base_path }>
+                    <Routes base={ base_path.clone() }>
                         <Route path="" view=Outlet >
-                            <Route path="*path" view=move || view! { <SolverWrapper map_path_solver katex_loaded /> } />
+                            <Route path="*path" view=move || view! { <SolverWrapper base_path={ base_path.clone() } map_path_solver katex_loaded drafts set_drafts /> } />
                         </Route>
                     </Routes>
                 </main>
@@ -372,12 +854,16 @@ fn Contents(
     let path_selected = use_location().pathname;
     let path_selected =
         Signal::derive(move || with!(|path_selected| path_selected[1..].to_string()));
+    let default_path = solver_paths(&base_path, &solver_tree)
+        .into_iter()
+        .next()
+        .expect("no solver is registered");
     // convert tree of solver into contents
     let mut stack_solver_tree = vec![VecDeque::from(solver_tree)];
     let mut stack_path = Vec::new();
     let mut stack_contents = vec![(String::new(), VecDeque::new())];
     let mut map_path_solver_value = HashMap::new();
-    let mut default_path = None;
+    let (tag_selected, set_tag_selected) = create_signal(None::<String>);
     let (class_name, style_val) = style_str! {
         details.header > summary {
             pointer-events: none;
@@ -418,6 +904,32 @@ fn Contents(
             font-weight: 700;
             background-color: rgb(205, 233, 255);
         }
+        li.hidden {
+            display: none;
+        }
+        li.tag-filter {
+            padding: 0.3rem 1rem 0.7rem 1rem;
+            font-size: 0.875rem;
+            cursor: pointer;
+        }
+        .tags {
+            display: flex;
+            flex-wrap: wrap;
+            gap: 0.25rem;
+            margin-top: 0.3rem;
+        }
+        .tag {
+            padding: 0 0.5rem;
+            border-radius: 9999px;
+            font-size: 0.75rem;
+            font-weight: 400;
+            background-color: rgb(229, 231, 235);
+            cursor: pointer;
+        }
+        .tag.tag-selected {
+            color: rgb(255, 255, 255);
+            background-color: rgb(125, 196, 255);
+        }
         @media only screen and (max-width: 1024px) {
             ol.root {
                 min-width: revert;
@@ -445,7 +957,7 @@ fn Contents(
                         stack_path.push(id);
                         stack_contents.push((title, VecDeque::new()));
                     },
-                    Some(SectionOrSolver::Solver { id, toc_title, solver }) => {
+                    Some(SectionOrSolver::Solver { id, toc_title, tags, solver }) => {
                         stack_solver_tree.push(sub_solver_tree);
                         match stack_contents.last_mut() {
                             Some(sub_contents) => {
@@ -462,15 +974,53 @@ fn Contents(
                                 } else {
                                     format!("{}/{}", base_path, path)
                                 };
-                                if default_path.is_none() {
-                                    default_path = Some(path.clone());
-                                }
+                                let chips = tags.iter().map(|tag| {
+                                    let tag = tag.clone();
+                                    let tag_view = tag.clone();
+                                    let tag_class = tag.clone();
+                                    let toggle_tag = move || set_tag_selected.update(|tag_selected| {
+                                        *tag_selected = if tag_selected.as_ref() == Some(&tag) {
+                                            None
+                                        } else {
+                                            Some(tag.clone())
+                                        };
+                                    });
+                                    let toggle_tag_by_key = toggle_tag.clone();
+                                    view! {
+                                        class = class_name,
+                                        <span class="tag" role="button" tabindex="0" aria-label={
+                                            format!("Filter by tag {tag_view}")
+                                        } class:tag-selected={
+                                            move || with!(|tag_selected| tag_selected.as_ref() == Some(&tag_class))
+                                        } on:click=move |ev| {
+                                            // select the tag instead of following the link
+                                            ev.prevent_default();
+                                            ev.stop_propagation();
+                                            toggle_tag();
+                                        } on:keydown=move |ev| {
+                                            if ev.key() == "Enter" || ev.key() == " " {
+                                                ev.prevent_default();
+                                                ev.stop_propagation();
+                                                toggle_tag_by_key();
+                                            }
+                                        }> { tag_view } </span>
+                                    }
+                                }).collect_vec();
+                                let hidden = move || with!(|tag_selected| {
+                                    tag_selected.as_ref().is_some_and(|tag| !tags.contains(tag))
+                                });
                                 sub_contents.1.push_back(view! {
                                     class = class_name,
                                     <A href={ path.clone() }>
                                         <li class="solver" class:selected={
                                             move || with!(|path_selected| path_selected == &path)
-                                        } > { toc_title } </li>
+                                        } class:hidden=hidden > { toc_title } {
+                                            if chips.is_empty() {
+                                                ().into_view()
+                                            } else {
+                                                view! { class = class_name, <div class="tags"> { chips } </div> }.into_view()
+                                            }
+                                        } </li>
                                     </A>
                                 }.into_view());
                             },
@@ -509,37 +1059,117 @@ fn Contents(
         }
     };
     set_map_path_solver(map_path_solver_value);
-    let default_path = default_path.unwrap();
     let navigate = use_navigate();
     create_effect(move |_| {
-        if with!(
-            |path_selected| path_selected.is_empty() || path_selected == &format!("{}/", base_path)
-        ) {
+        // both `/base` and `/base/`
+        if with!(|path_selected| path_selected.trim_end_matches('/') == base_path) {
             navigate(default_path.as_str(), Default::default());
         }
     });
     let header = create_node_ref();
-    let mobile = window()
-        .match_media("only screen and (max-width: 1024px)")
-        .unwrap()
-        .unwrap()
-        .matches();
+    // listens to changes of the media query and stops on cleanup
+    let mobile = use_media_query("only screen and (max-width: 1024px)");
     view! {
         class = class_name,
         <Style> { style_val } </Style>
-        <details class="header" open={ if mobile { None } else { Some("") } } _ref=header>
+        <details class="header" open=move || if mobile() { None } else { Some("") } _ref=header>
             <summary> "Contents" </summary>
             <ol class="root">
+                <Show when=move || with!(|tag_selected| tag_selected.is_some())>
+                    <li class="tag-filter" role="button" tabindex="0" aria-label="Clear the tag filter" on:click=move |_| set_tag_selected(None) on:keydown=move |ev| {
+                        if ev.key() == "Enter" || ev.key() == " " {
+                            ev.prevent_default();
+                            set_tag_selected(None);
+                        }
+                    }> {
+                        move || with!(|tag_selected| format!("Tag: {} ✕", tag_selected.as_deref().unwrap_or_default()))
+                    } </li>
+                </Show>
                 { contents }
             </ol>
         </details>
     }
 }
 
+/// Milliseconds after the last keystroke before the input is highlighted again.
+const HIGHLIGHT_DELAY: u64 = 150;
+
+/// Milliseconds after the last keystroke before the input is solved again in live mode.
+const LIVE_DELAY: u64 = 300;
+
+/// Live mode pauses once a solve takes more milliseconds than this.
+const LIVE_MAX_DURATION: u64 = 100;
+
+/// Inputs submitted to the shown solver in this session, with undo and redo.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct InputHistory {
+    inputs: Vec<String>,
+    /// Index of the current input, inputs after it can be redone
+    cursor: usize,
+}
+
+impl InputHistory {
+    /// Discards the redoable inputs, unless `input` is the current one.
+    fn push(&mut self, input: String) {
+        if self.inputs.get(self.cursor) == Some(&input) {
+            return;
+        }
+        if !self.inputs.is_empty() {
+            self.inputs.truncate(self.cursor + 1);
+        }
+        self.inputs.push(input);
+        self.cursor = self.inputs.len() - 1;
+    }
+
+    fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    fn can_redo(&self) -> bool {
+        self.cursor + 1 < self.inputs.len()
+    }
+
+    fn undo(&mut self) -> Option<&str> {
+        self.can_undo().then(|| {
+            self.cursor -= 1;
+            self.inputs[self.cursor].as_str()
+        })
+    }
+
+    fn redo(&mut self) -> Option<&str> {
+        self.can_redo().then(|| {
+            self.cursor += 1;
+            self.inputs[self.cursor].as_str()
+        })
+    }
+}
+
+#[test]
+fn test_input_history() {
+    let mut history = InputHistory::default();
+    assert!(!history.can_undo() && !history.can_redo());
+    history.push("a".to_string());
+    history.push("b".to_string());
+    history.push("b".to_string());
+    history.push("c".to_string());
+    assert_eq!(history.undo(), Some("b"));
+    assert_eq!(history.undo(), Some("a"));
+    assert_eq!(history.undo(), None);
+    assert_eq!(history.redo(), Some("b"));
+    // submitting after undoing discards the redoable inputs
+    history.push("d".to_string());
+    assert_eq!(history.redo(), None);
+    assert_eq!(history.undo(), Some("b"));
+    assert_eq!(history.undo(), Some("a"));
+}
+
 #[component]
 fn SolverWrapper(
+    base_path: String,
     map_path_solver: ReadSignal<HashMap<String, SolverObject>>,
     katex_loaded: ReadSignal<bool>,
+    drafts: ReadSignal<HashMap<String, String>>,
+    set_drafts: WriteSignal<HashMap<String, String>>,
 ) -> impl IntoView {
     let (class_name_not_found, style_val_not_found) = style_str! {
         div {
@@ -553,6 +1183,13 @@ fn SolverWrapper(
             font-size: 3rem;
             text-align: center;
         }
+        p {
+            text-align: center;
+        }
+        a {
+            font-weight: 700;
+            text-decoration: underline;
+        }
     };
     let (class_name, style_val) = style_str! {
         .solver {
@@ -592,16 +1229,51 @@ fn SolverWrapper(
             margin-right: 2rem;
             overflow: auto;
         }
-        .input > textarea {
-            padding: 0.5rem;
+        .input > .editor {
+            display: grid;
             margin-left: 2rem;
             margin-right: 2rem;
+        }
+        .editor > textarea {
+            grid-row: 1;
+            grid-column: 1;
+            padding: 0.5rem;
             border-radius: 0.25rem;
             border: 2px solid rgb(229, 231, 235);
             font-family: "DejaVu Sans Mono", ui-monospace, "Cascadia Code", Menlo,
             "Source Code Pro", Consolas, monospace;
             min-height: 12rem;
         }
+        .editor > pre {
+            grid-row: 1;
+            grid-column: 1;
+            margin: 0;
+            padding: 0.5rem;
+            border: 2px solid transparent;
+            font-family: "DejaVu Sans Mono", ui-monospace, "Cascadia Code", Menlo,
+            "Source Code Pro", Consolas, monospace;
+            font-size: 0.875rem;
+            line-height: 1.25rem;
+            white-space: pre-wrap;
+            overflow-wrap: anywhere;
+            pointer-events: none;
+        }
+        .editor.highlighted > textarea {
+            font-size: 0.875rem;
+            line-height: 1.25rem;
+            overflow-wrap: anywhere;
+            color: transparent;
+            caret-color: rgb(63, 63, 66);
+            background: transparent;
+        }
+        .input > select {
+            padding: 0.4rem;
+            margin-left: 2rem;
+            margin-right: 2rem;
+            align-self: start;
+            border-radius: 0.25rem;
+            border: 2px solid rgb(229, 231, 235);
+        }
         .input > button {
             padding: 0.6rem 2.5rem;
             margin-left: 2rem;
@@ -623,12 +1295,72 @@ fn SolverWrapper(
         .answer {
             flex: 1;
         }
+        .answer > div.collapsed {
+            overflow: hidden;
+            mask-image: linear-gradient(to bottom, black 80%, transparent);
+        }
+        .answer > button.expander {
+            align-self: center;
+            padding: 0.3rem 1.5rem;
+            border-radius: 0.25rem;
+            border: 2px solid rgb(229, 231, 235);
+        }
+        .answer > button.expander:hover {
+            background-color: rgb(229, 231, 235);
+        }
+        .case {
+            margin-bottom: 2.5rem;
+        }
+        .case > h3 {
+            margin-bottom: 1rem;
+            font-size: 1.25rem;
+            line-height: 1.75rem;
+            font-weight: 700;
+        }
+        .qr-modal {
+            position: fixed;
+            inset: 0;
+            z-index: 10;
+            display: flex;
+            justify-content: center;
+            align-items: center;
+            background-color: rgb(0 0 0 / 0.5);
+        }
+        .qr-modal > div {
+            padding: 1.5rem;
+            border-radius: 0.75rem;
+            text-align: center;
+            background-color: rgb(255, 255, 255);
+        }
+        .qr-modal .qr {
+            width: min(20rem, 80vw);
+            margin-bottom: 1rem;
+        }
         .answer > div {
             margin-left: 2rem;
             margin-right: 2rem;
             overflow: auto visible;
             min-height: 6rem;
         }
+        .input > h2 > button,
+        .answer > h2 > button {
+            margin-left: 1rem;
+            padding: 0.1rem 0.8rem;
+            border-radius: 0.25rem;
+            border: 2px solid rgb(229, 231, 235);
+            font-size: 0.875rem;
+            font-weight: 400;
+        }
+        .input > h2 > button + button {
+            margin-left: 0.5rem;
+        }
+        .input > h2 > button:disabled {
+            opacity: 0.4;
+        }
+        .input > h2 > button:hover:enabled,
+        .answer > h2 > button:hover {
+            background-color: rgb(229, 231, 235);
+        }
         @media only screen and (max-width: 1024px) {
             .solver {
                 gap: 1rem;
@@ -650,7 +1382,7 @@ fn SolverWrapper(
                 font-size: 1rem;
                 line-height: 1.5rem;
             }
-            .input > textarea {
+            .input > .editor, .input > select {
                 margin-left: 0;
                 margin-right: 0;
             }
@@ -669,20 +1401,167 @@ fn SolverWrapper(
         }
     };
     let params = use_params_map();
+    let query = use_query_map();
+    provide_context(RawTexShown(Signal::derive(move || {
+        with!(|query| query.get("debug").is_some_and(|debug| debug == "1"))
+    })));
+    // `solve` runs in event handlers, which have no owner to look up the context from
+    let owner = Owner::current().unwrap();
     let path = Signal::derive(move || {
         with!(|params| params.get("path").unwrap_or(&"".to_string()).to_string())
     });
     let s =
         Signal::derive(move || with!(|path, map_path_solver| map_path_solver.get(path).cloned()));
+    // closest existing path when `path` is mistyped
+    let suggestion = Signal::derive(move || {
+        with!(|path, map_path_solver| {
+            let max_distance = 2.max(path.chars().count() / 3);
+            map_path_solver
+                .keys()
+                .map(|candidate| (levenshtein(path, candidate), candidate))
+                .filter(|(distance, _)| *distance <= max_distance)
+                .min()
+                .map(|(_, candidate)| candidate.clone())
+        })
+    });
     let input: NodeRef<html::Textarea> = create_node_ref();
-    let default_input = Signal::derive(move || {
+    let highlights =
+        Signal::derive(move || with!(|s| s.as_ref().is_some_and(|s| s.highlights_input())));
+    let (highlighted, set_highlighted) = create_signal(Vec::new());
+    // called after each change of the input
+    let highlight = move || {
+        let Some(input) = input.get_untracked() else {
+            return;
+        };
+        if highlights.get_untracked() {
+            let value = input.value();
+            s.with_untracked(|s| {
+                if let Some(s) = s {
+                    set_highlighted(s.highlight_input(value.as_str()));
+                }
+            });
+        }
+    };
+    let highlight_generation = store_value(0u64);
+    let examples = Signal::derive(move || {
         with!(|s| s
             .as_ref()
-            .map(|solver| solver.default_input())
+            .map(|solver| solver.examples())
             .unwrap_or_default())
     });
+    let default_input = Signal::derive(move || {
+        with!(|examples| examples
+            .first()
+            .map(|(_, example)| example.clone())
+            .unwrap_or_default())
+    });
+    // path of the solver whose input is in the textarea
+    let shown_path = store_value(None::<String>);
     let (answer, set_answer) = create_signal(None);
+    let answer_element: NodeRef<html::Div> = create_node_ref();
+    let answer_heading: NodeRef<html::H2> = create_node_ref();
+    let (copied, set_copied) = create_signal(false);
     let (duration, set_duration) = create_signal(None);
+    let (input_history, set_input_history) = create_signal(InputHistory::default());
+    // whether the rendered answer is taller than the collapse height
+    let (answer_overflows, set_answer_overflows) = create_signal(false);
+    // kept while the same solver is shown
+    let (answer_expanded, set_answer_expanded) = create_signal(false);
+    let collapse_height =
+        Signal::derive(move || with!(|s| s.as_ref().and_then(|s| s.answer_collapse_height())));
+    // `Some(None)` if the link is too long for a QR code
+    let (qr_code, set_qr_code) = create_signal(None::<Option<String>>);
+    // sets the input to one from the history without submitting it
+    let restore = move |restored: Option<String>| {
+        let (Some(input), Some(restored)) = (input.get_untracked(), restored) else {
+            return;
+        };
+        input.set_value(restored.as_str());
+        set_location_hash_encoded(restored.as_str(), false);
+        highlight();
+    };
+    // live solving neither records the history nor moves the focus away from the input
+    let solve_input = move |live: bool| {
+        let input = match input.get_untracked() {
+            Some(input) => input,
+            None => return,
+        };
+        let input_string = match input.value().as_str() {
+            "" if live => return,
+            "" => {
+                let default_input = default_input.get_untracked();
+                input.set_value(default_input.as_str());
+                highlight();
+                default_input
+            }
+            s => s.to_string(),
+        };
+        set_location_hash_encoded(input_string.as_str(), true);
+        if !live {
+            set_input_history.update(|history| history.push(input_string.clone()));
+        }
+        let begin = window().performance().unwrap().now();
+        let (answer, solved) = with_owner(owner, || {
+            s.with_untracked(|s| {
+                let s = s.as_ref().unwrap();
+                let cases = if s.supports_batch() {
+                    split_cases(input_string.as_str())
+                } else {
+                    None
+                };
+                match cases {
+                    Some(cases) => {
+                        let answers = cases
+                            .into_iter()
+                            .zip(1..)
+                            .map(|(case, i)| {
+                                let answer = match s.validate_input(case.as_str()) {
+                                    Ok(()) => s.solve(case),
+                                    Err(view) => view,
+                                };
+                                view! {
+                                    class = class_name,
+                                    <div class="case">
+                                        <h3> { format!("Case {i}.") } </h3>
+                                        { answer }
+                                    </div>
+                                }
+                            })
+                            .collect_vec();
+                        (answers.into_view(), true)
+                    }
+                    None => match s.validate_input(input_string.as_str()) {
+                        Ok(()) => (s.solve(input_string), true),
+                        Err(view) => (view, false),
+                    },
+                }
+            })
+        });
+        set_duration(Some(
+            1.max((window().performance().unwrap().now() - begin) as u64),
+        ));
+        set_answer(Some(answer));
+        set_answer_overflows(false);
+        // measured once the answer is mounted
+        request_animation_frame(move || {
+            if let (Some(answer_element), Some(height)) = (
+                answer_element.get_untracked(),
+                collapse_height.get_untracked(),
+            ) {
+                set_answer_overflows(answer_element.scroll_height() > height as i32);
+            }
+        });
+        if solved && !live {
+            // the heading is mounted along with the answer
+            request_animation_frame(move || {
+                if let Some(answer_heading) = answer_heading.get_untracked() {
+                    let _ = answer_heading.focus();
+                }
+            });
+        }
+    };
+    let submit = move || solve_input(false);
+    let live = Signal::derive(move || with!(|s| s.as_ref().is_some_and(|s| s.live())));
     create_effect(move |first_run| {
         if !katex_loaded() {
             return true;
@@ -693,19 +1572,40 @@ fn SolverWrapper(
                 .as_str()
         ));
         if let Some(input) = input.get_untracked() {
-            if first_run.unwrap_or(true) {
-                if let Some(input_from_hash) = get_location_hash_decoded() {
-                    input.set_value(input_from_hash.as_str());
-                } else {
-                    default_input
-                        .with_untracked(|default_input| input.set_value(default_input.as_str()));
+            let path = path.get_untracked();
+            if let Some(previous_path) = shown_path.get_value() {
+                if previous_path != path {
+                    set_drafts.update(|drafts| {
+                        drafts.insert(previous_path, input.value());
+                    });
                 }
+            }
+            if shown_path.get_value().as_ref() != Some(&path) {
+                set_input_history(InputHistory::default());
+                set_answer_expanded(false);
+            }
+            shown_path.set_value(Some(path.clone()));
+            let (input_from_hash, submitted) = if first_run.unwrap_or(true) {
+                get_location_hash_decoded().unzip()
             } else {
-                default_input
-                    .with_untracked(|default_input| input.set_value(default_input.as_str()));
+                (None, None)
+            };
+            // explicit hash, then unsubmitted input, then default input
+            match input_from_hash
+                .or_else(|| drafts.with_untracked(|drafts| drafts.get(&path).cloned()))
+            {
+                Some(value) => input.set_value(value.as_str()),
+                None => default_input
+                    .with_untracked(|default_input| input.set_value(default_input.as_str())),
             }
+            highlight();
             set_duration(None);
             set_answer(None);
+            set_answer_overflows(false);
+            if submitted == Some(true) {
+                // reads inside `solve` must not rerun this effect
+                untrack(submit);
+            }
             false
         } else {
             true
@@ -713,9 +1613,13 @@ fn SolverWrapper(
     });
     window_event_listener(ev::hashchange, move |_| {
         if let Some(input) = input() {
-            if let Some(input_from_hash) = get_location_hash_decoded() {
+            if let Some((input_from_hash, submitted)) = get_location_hash_decoded() {
                 if input.value() != input_from_hash.as_str() {
                     input.set_value(input_from_hash.as_str());
+                    highlight();
+                    if submitted {
+                        submit();
+                    }
                 }
             }
         }
@@ -728,7 +1632,22 @@ fn SolverWrapper(
             when=move || with!(move |s| s.is_some())
             fallback=move || view! {
                 class = class_name_not_found,
-                <div> <h1> "Not Found" </h1> </div>
+                <div>
+                    <h1> "Not Found" </h1>
+                    {
+                        let base_path = base_path.clone();
+                        move || suggestion().map(|suggestion| view! {
+                            class = class_name_not_found,
+                            <p> "Did you mean " <a href={
+                                if base_path.is_empty() {
+                                    format!("/{}", suggestion)
+                                } else {
+                                    format!("/{}/{}", base_path, suggestion)
+                                }
+                            }> { suggestion.clone() } </a> "?" </p>
+                        })
+                    }
+                </div>
             }
         >
             <Show
@@ -745,37 +1664,134 @@ fn SolverWrapper(
                         <div> { move || with!(move |s| s.as_ref().unwrap().description()) } </div>
                     </div>
                     <div class="section input">
-                        <h2> "Input." </h2>
-                        <textarea node_ref=input />
-                        <button on:click=move |_| {
-                            let input = match input.get_untracked() {
-                                Some(input) => input,
-                                None => return,
-                            };
-                            let input_string = match input.value().as_str() {
-                                "" => {
-                                    let default_input = default_input.get_untracked();
-                                    input.set_value(default_input.as_str());
-                                    default_input
+                        <h2> "Input." <button
+                            title="Undo"
+                            aria-label="Undo to the previous submitted input"
+                            disabled=move || with!(|input_history| !input_history.can_undo())
+                            on:click=move |_| {
+                                let mut restored = None;
+                                set_input_history.update(|history| restored = history.undo().map(str::to_string));
+                                restore(restored);
+                            }
+                        > "⟲" </button> <button
+                            title="Redo"
+                            aria-label="Redo to the next submitted input"
+                            disabled=move || with!(|input_history| !input_history.can_redo())
+                            on:click=move |_| {
+                                let mut restored = None;
+                                set_input_history.update(|history| restored = history.redo().map(str::to_string));
+                                restore(restored);
+                            }
+                        > "⟳" </button> <button
+                            title="QR code"
+                            aria-label="Show the link to the answer as a QR code"
+                            on:click=move |_| {
+                                let Some(input) = input.get_untracked() else {
+                                    return;
+                                };
+                                let href = window().location().href().unwrap_or_default();
+                                let url = format!(
+                                    "{}#{}{SUBMITTED_SUFFIX}",
+                                    href.split('#').next().unwrap_or_default(),
+                                    encode_input_to_hash(&input.value())
+                                );
+                                set_qr_code(Some(QrCode::encode(url.as_bytes()).map(|qr| qr.to_svg())));
+                            }
+                        > "QR" </button> </h2>
+                        <Show when=move || with!(|examples| examples.len() > 1)>
+                            <select aria-label="Examples" on:change=move |ev| {
+                                let Ok(i) = event_target_value(&ev).parse::<usize>() else {
+                                    return;
+                                };
+                                let Some(input) = input.get_untracked() else {
+                                    return;
+                                };
+                                examples.with_untracked(|examples| {
+                                    if let Some((_, example)) = examples.get(i) {
+                                        input.set_value(example.as_str());
+                                        set_location_hash_encoded(example.as_str(), false);
+                                        highlight();
+                                    }
+                                });
+                            }> {
+                                move || with!(|examples| examples.iter().enumerate().map(|(i, (label, _))| view! {
+                                    <option value={ i.to_string() }> { label.clone() } </option>
+                                }).collect_vec())
+                            } </select>
+                        </Show>
+                        <div class="editor" class:highlighted=highlights>
+                            <Show when=highlights>
+                                <pre aria-hidden="true"> {
+                                    move || with!(|highlighted| highlighted.iter().map(|(text, color): &(String, Option<String>)| view! {
+                                        <span style={ color.as_ref().map(|color| format!("color: {color};")) }> { text.clone() } </span>
+                                    }).collect_vec())
+                                } " " </pre>
+                            </Show>
+                            <textarea node_ref=input aria-label="Input" on:input=move |_| {
+                                // the shared answer no longer matches the input
+                                strip_submitted_location_hash();
+                                let live = live.get_untracked()
+                                    && duration.get_untracked().is_none_or(|duration| duration <= LIVE_MAX_DURATION);
+                                if !live && !highlights.get_untracked() {
+                                    return;
                                 }
-                                s => s.to_string(),
-                            };
-                            set_location_hash_encoded(input_string.as_str());
-                            let begin = window().performance().unwrap().now();
-                            let answer = s.with_untracked(|s| s.as_ref().unwrap().solve(input_string));
-                            set_duration(Some(1.max((window().performance().unwrap().now() - begin) as u64)));
-                            set_answer(Some(answer));
-                        }> "Submit" </button>
+                                // highlight or solve once typing pauses
+                                highlight_generation.update_value(|generation| *generation += 1);
+                                let generation = highlight_generation.get_value();
+                                set_timeout(move || {
+                                    if highlight_generation.get_value() == generation {
+                                        highlight();
+                                        if live {
+                                            solve_input(true);
+                                        }
+                                    }
+                                }, Duration::from_millis(if live { LIVE_DELAY } else { HIGHLIGHT_DELAY }));
+                            } />
+                        </div>
+                        <button aria-label="Submit input" on:click=move |_| submit()> "Submit" </button>
                     </div>
                     <Show when=move || with!(|answer| answer.is_some())>
-                        <div class="section answer">
-                            <h2> {
+                        <div class="section answer" aria-live="polite">
+                            <h2 node_ref=answer_heading tabindex="-1"> {
                                 move || with!(|duration| match duration {
                                     Some(duration) => format!("Answer. (took {}ms)", duration),
                                     None => "Answer.".to_string()
                                 })
-                            } </h2>
-                            <div> { answer } </div>
+                            } <button on:click=move |_| {
+                                let Some(answer_element) = answer_element.get_untracked() else {
+                                    return;
+                                };
+                                copy_to_clipboard(&collect_tex(&answer_element));
+                                set_copied(true);
+                                set_timeout(move || set_copied(false), Duration::from_secs(2));
+                            }> { move || if copied() { "Copied" } else { "Copy LaTeX" } } </button> </h2>
+                            <div
+                                node_ref=answer_element
+                                class:collapsed=move || answer_overflows() && !answer_expanded()
+                                style:max-height=move || {
+                                    (answer_overflows() && !answer_expanded())
+                                        .then(|| format!("{}px", collapse_height().unwrap_or_default()))
+                                }
+                            > { answer } </div>
+                            <Show when=answer_overflows>
+                                <button class="expander" on:click=move |_| set_answer_expanded.update(|expanded| *expanded = !*expanded)> {
+                                    move || if answer_expanded() { "收起" } else { "展开全部" }
+                                } </button>
+                            </Show>
+                        </div>
+                    </Show>
+                    <Show when=move || with!(|qr_code| qr_code.is_some())>
+                        <div class="qr-modal" role="dialog" aria-label="QR code" on:click=move |_| set_qr_code(None)>
+                            <div> {
+                                move || match qr_code() {
+                                    Some(Some(svg)) => view! {
+                                        class = class_name,
+                                        <div class="qr" inner_html=svg></div>
+                                    }
+                                    .into_view(),
+                                    _ => view! { <p class="mb-4"> "The input is too long for a QR code." </p> }.into_view(),
+                                }
+                            } <p> "Scan to open the answer, click to close." </p> </div>
                         </div>
                     </Show>
                 </div>
@@ -784,12 +1800,56 @@ fn SolverWrapper(
     }
 }
 
+/// Source TeX of all [`KaTeX`] components under `element` in document order.
+fn collect_tex(element: &web_sys::Element) -> String {
+    let elements = element.query_selector_all("[data-tex]").unwrap();
+    (0..elements.length())
+        .filter_map(|i| elements.get(i))
+        .filter_map(|node| node.dyn_into::<web_sys::Element>().ok())
+        .filter_map(|element| element.get_attribute("data-tex"))
+        .join("\n\n")
+}
+
+/// Writes `text` to the clipboard, logging failures. Does nothing where the clipboard API is
+/// unavailable, e.g. on non-secure origins.
+pub fn copy_to_clipboard(text: &str) {
+    let Ok(clipboard) = Reflect::get(&window().navigator(), &"clipboard".into()) else {
+        return;
+    };
+    if clipboard.is_undefined() {
+        leptos::logging::warn!("clipboard is unavailable");
+        return;
+    }
+    let Ok(write_text) = Reflect::get(&clipboard, &"writeText".into())
+        .and_then(|f| f.dyn_into::<js_sys::Function>())
+    else {
+        leptos::logging::warn!("clipboard is unavailable");
+        return;
+    };
+    let promise = match write_text.call1(&clipboard, &text.into()) {
+        Ok(promise) => promise.unchecked_into::<js_sys::Promise>(),
+        Err(e) => {
+            leptos::logging::warn!("failed to copy to the clipboard: {:?}", e);
+            return;
+        }
+    };
+    let on_error = Closure::once(|e: JsValue| {
+        leptos::logging::warn!("failed to copy to the clipboard: {:?}", e);
+    });
+    let _ = promise.catch(&on_error);
+    on_error.forget();
+}
+
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(js_namespace = katex, js_name = renderToString)]
-    fn katex_render_to_string(expression: &str, options: &JsValue) -> String;
+    #[wasm_bindgen(catch, js_namespace = katex, js_name = renderToString)]
+    fn katex_render_to_string(expression: &str, options: &JsValue) -> Result<String, JsValue>;
 }
 
+/// Whether [`KaTeX`] components show their source below the rendered math, set by `?debug=1`.
+#[derive(Debug, Clone, Copy)]
+struct RawTexShown(Signal<bool>);
+
 #[component]
 pub fn KaTeX(
     #[prop(into)] expr: String,
@@ -803,8 +1863,43 @@ pub fn KaTeX(
     Reflect::set(&options, &"displayMode".into(), &display_mode.into()).unwrap();
     Reflect::set(&options, &"leqno".into(), &leqno.into()).unwrap();
     Reflect::set(&options, &"fleqn".into(), &fleqn.into()).unwrap();
+    // the `debug-katex` feature catches bad TeX during development
+    let throw_on_error = throw_on_error || cfg!(feature = "debug-katex");
     Reflect::set(&options, &"throwOnError".into(), &throw_on_error.into()).unwrap();
-    view! {
-        <div inner_html={ katex_render_to_string(expr.as_str(), options.as_ref()) }></div>
-    }
+    let raw_tex_shown = use_context::<RawTexShown>();
+    let raw_tex = {
+        let expr = expr.clone();
+        view! {
+            <Show when=move || raw_tex_shown.is_some_and(|shown| shown.0.get())>
+                <details>
+                    <summary> "TeX" </summary>
+                    <pre style="white-space: pre-wrap;"> { expr.clone() } </pre>
+                </details>
+            </Show>
+        }
+    };
+    let rendered = match katex_render_to_string(expr.as_str(), options.as_ref()) {
+        Ok(html) => {
+            // KaTeX renders the error in place instead of throwing
+            let failed = html.contains("katex-error");
+            if failed {
+                leptos::logging::warn!("failed to render TeX: {}", expr);
+            }
+            view! {
+                <div inner_html={ html } title={ failed.then(|| expr.clone()) } data-katex-expr={ failed.then(|| expr.clone()) } data-tex={ expr.clone() }></div>
+            }
+        }
+        Err(e) => {
+            let message = e
+                .dyn_ref::<js_sys::Error>()
+                .map_or_else(|| format!("{:?}", e), |e| String::from(e.message()));
+            leptos::logging::error!("failed to render TeX: {}\n{}", expr, message);
+            view! {
+                <div title={ expr.clone() } data-katex-expr={ expr.clone() } data-tex={ expr.clone() }>
+                    <pre style="color: #cc0000; white-space: pre-wrap;"> { message } </pre>
+                </div>
+            }
+        }
+    };
+    view! { {rendered} {raw_tex} }
 }
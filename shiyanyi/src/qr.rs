@@ -0,0 +1,465 @@
+//! QR code encoder in byte mode with error correction level L, enough for sharing links.
+
+use itertools::Itertools;
+
+/// Error correction codewords per block of each version at level L, index 0 is unused.
+const ECC_CODEWORDS_PER_BLOCK: [usize; 41] = [
+    0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18, 20, 24, 26, 30, 22, 24, 28, 30, 28, 28, 28, 28, 30,
+    30, 26, 28, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30, 30,
+];
+
+/// Error correction blocks of each version at level L, index 0 is unused.
+const ERROR_CORRECTION_BLOCKS: [usize; 41] = [
+    0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4, 4, 4, 4, 4, 6, 6, 6, 6, 7, 8, 8, 9, 9, 10, 12, 12, 12, 13, 14,
+    15, 16, 17, 18, 19, 19, 20, 21, 22, 24, 25,
+];
+
+/// Modules of a QR code, `true` being dark, indexed by row then column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QrCode {
+    pub version: usize,
+    pub modules: Vec<Vec<bool>>,
+}
+
+/// Modules available for codewords, that is all but the function patterns.
+fn raw_data_modules(version: usize) -> usize {
+    let mut result = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        result -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            result -= 36;
+        }
+    }
+    result
+}
+
+fn data_codewords(version: usize) -> usize {
+    raw_data_modules(version) / 8
+        - ECC_CODEWORDS_PER_BLOCK[version] * ERROR_CORRECTION_BLOCKS[version]
+}
+
+/// Smallest version holding `len` bytes, `None` if none does.
+fn version_for(len: usize) -> Option<usize> {
+    (1..=40).find(|&version| {
+        let count_bits = if version <= 9 { 8 } else { 16 };
+        4 + count_bits + 8 * len <= data_codewords(version) * 8
+    })
+}
+
+#[test]
+fn test_version_for() {
+    assert_eq!(data_codewords(1), 19);
+    assert_eq!(data_codewords(10), 274);
+    assert_eq!(data_codewords(40), 2956);
+    assert_eq!(version_for(17), Some(1));
+    assert_eq!(version_for(18), Some(2));
+    assert_eq!(version_for(2953), Some(40));
+    assert_eq!(version_for(2954), None);
+}
+
+/// Product in GF(2^8) modulo `x^8 + x^4 + x^3 + x^2 + 1`.
+fn gf_mul(x: u8, y: u8) -> u8 {
+    let mut z = 0u8;
+    for i in (0..8).rev() {
+        z = (z << 1) ^ ((z >> 7) * 0x1d);
+        z ^= ((y >> i) & 1) * x;
+    }
+    z
+}
+
+/// Coefficients of the Reed–Solomon generator polynomial of `degree` from high to low,
+/// the leading one omitted.
+fn reed_solomon_divisor(degree: usize) -> Vec<u8> {
+    let mut result = vec![0; degree];
+    result[degree - 1] = 1;
+    let mut root = 1;
+    for _ in 0..degree {
+        for j in 0..degree {
+            result[j] = gf_mul(result[j], root);
+            if j + 1 < degree {
+                result[j] ^= result[j + 1];
+            }
+        }
+        root = gf_mul(root, 2);
+    }
+    result
+}
+
+fn reed_solomon_remainder(data: &[u8], divisor: &[u8]) -> Vec<u8> {
+    let mut result = vec![0; divisor.len()];
+    for &b in data {
+        let factor = b ^ result.remove(0);
+        result.push(0);
+        for (x, &y) in result.iter_mut().zip(divisor) {
+            *x ^= gf_mul(y, factor);
+        }
+    }
+    result
+}
+
+#[test]
+fn test_reed_solomon() {
+    // HELLO WORLD in version 1-M
+    let data = [
+        32, 91, 11, 120, 209, 114, 220, 77, 67, 64, 236, 17, 236, 17, 236, 17,
+    ];
+    assert_eq!(
+        reed_solomon_remainder(&data, &reed_solomon_divisor(10)),
+        [196, 35, 39, 119, 235, 215, 231, 226, 93, 23]
+    );
+}
+
+/// Data codewords of the payload with padding, then split into blocks and interleaved
+/// with the error correction codewords.
+fn codewords(data: &[u8], version: usize) -> Vec<u8> {
+    let capacity = data_codewords(version);
+    let mut bits = Vec::new();
+    let mut push_bits = |value: usize, len: usize| {
+        bits.extend((0..len).rev().map(|i| (value >> i) & 1 == 1));
+    };
+    push_bits(0b0100, 4);
+    push_bits(data.len(), if version <= 9 { 8 } else { 16 });
+    for &b in data {
+        push_bits(b as usize, 8);
+    }
+    let terminator = (capacity * 8 - bits.len()).min(4);
+    bits.extend(std::iter::repeat_n(false, terminator));
+    bits.extend(std::iter::repeat_n(false, (8 - bits.len() % 8) % 8));
+    let mut data = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |acc, &bit| (acc << 1) | bit as u8))
+        .collect_vec();
+    for pad in [0xec, 0x11].into_iter().cycle() {
+        if data.len() >= capacity {
+            break;
+        }
+        data.push(pad);
+    }
+
+    let blocks = ERROR_CORRECTION_BLOCKS[version];
+    let ecc_len = ECC_CODEWORDS_PER_BLOCK[version];
+    let raw_codewords = raw_data_modules(version) / 8;
+    let short_blocks = blocks - raw_codewords % blocks;
+    let short_block_len = raw_codewords / blocks;
+    let divisor = reed_solomon_divisor(ecc_len);
+    let mut rest = data.as_slice();
+    let blocks = (0..blocks)
+        .map(|i| {
+            let len = short_block_len - ecc_len + usize::from(i >= short_blocks);
+            let (block, remaining) = rest.split_at(len);
+            rest = remaining;
+            let ecc = reed_solomon_remainder(block, &divisor);
+            // short blocks are padded so that the codewords line up
+            let mut block = block.to_vec();
+            if i < short_blocks {
+                block.push(0);
+            }
+            block.extend(ecc);
+            block
+        })
+        .collect_vec();
+    (0..blocks[0].len())
+        .flat_map(|i| {
+            blocks
+                .iter()
+                .enumerate()
+                .filter(move |&(j, _)| i != short_block_len - ecc_len || j >= short_blocks)
+                .map(move |(_, block)| block[i])
+        })
+        .collect_vec()
+}
+
+fn alignment_positions(version: usize) -> Vec<usize> {
+    if version == 1 {
+        return vec![];
+    }
+    let alignments = version / 7 + 2;
+    let step = (version * 8 + alignments * 3 + 5) / (alignments * 4 - 4) * 2;
+    let size = version * 4 + 17;
+    let mut positions = (0..alignments - 1)
+        .map(|i| size - 7 - i * step)
+        .collect_vec();
+    positions.push(6);
+    positions.reverse();
+    positions
+}
+
+#[test]
+fn test_alignment_positions() {
+    assert_eq!(alignment_positions(2), [6, 18]);
+    assert_eq!(alignment_positions(7), [6, 22, 38]);
+    assert_eq!(alignment_positions(32), [6, 34, 60, 86, 112, 138]);
+}
+
+/// 15 format bits of level L and `mask`, BCH coded and masked.
+fn format_bits(mask: usize) -> usize {
+    let data = (0b01 << 3) | mask;
+    let mut remainder = data;
+    for _ in 0..10 {
+        remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+    }
+    ((data << 10) | remainder) ^ 0x5412
+}
+
+#[test]
+fn test_format_bits() {
+    assert_eq!(format_bits(0), 0b111011111000100);
+    assert_eq!(format_bits(4), 0b110011000101111);
+}
+
+fn mask_applies(mask: usize, row: usize, col: usize) -> bool {
+    let (x, y) = (col, row);
+    match mask {
+        0 => (x + y) % 2 == 0,
+        1 => y % 2 == 0,
+        2 => x % 3 == 0,
+        3 => (x + y) % 3 == 0,
+        4 => (x / 3 + y / 2) % 2 == 0,
+        5 => x * y % 2 + x * y % 3 == 0,
+        6 => (x * y % 2 + x * y % 3) % 2 == 0,
+        _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+    }
+}
+
+struct Canvas {
+    size: usize,
+    modules: Vec<Vec<bool>>,
+    is_function: Vec<Vec<bool>>,
+}
+
+impl Canvas {
+    fn set_function(&mut self, row: usize, col: usize, dark: bool) {
+        self.modules[row][col] = dark;
+        self.is_function[row][col] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize) {
+        let size = self.size;
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+        for (row, col) in [(3, 3), (3, size - 4), (size - 4, 3)] {
+            for dy in -4isize..=4 {
+                for dx in -4isize..=4 {
+                    let (y, x) = (row as isize + dy, col as isize + dx);
+                    if (0..size as isize).contains(&y) && (0..size as isize).contains(&x) {
+                        let distance = dx.abs().max(dy.abs());
+                        self.set_function(y as usize, x as usize, distance != 2 && distance != 4);
+                    }
+                }
+            }
+        }
+        let positions = alignment_positions(version);
+        let last = positions.len().saturating_sub(1);
+        for (i, &row) in positions.iter().enumerate() {
+            for (j, &col) in positions.iter().enumerate() {
+                // the finder patterns are at these corners
+                if (i == 0 && j == 0) || (i == 0 && j == last) || (i == last && j == 0) {
+                    continue;
+                }
+                for dy in -2isize..=2 {
+                    for dx in -2isize..=2 {
+                        let (y, x) = ((row as isize + dy) as usize, (col as isize + dx) as usize);
+                        self.set_function(y, x, dx.abs().max(dy.abs()) != 1);
+                    }
+                }
+            }
+        }
+        // reserved until the mask is chosen
+        self.draw_format_bits(0);
+        if version >= 7 {
+            let mut remainder = version;
+            for _ in 0..12 {
+                remainder = (remainder << 1) ^ ((remainder >> 11) * 0x1f25);
+            }
+            let bits = (version << 12) | remainder;
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 == 1;
+                let (a, b) = (size - 11 + i % 3, i / 3);
+                self.set_function(b, a, dark);
+                self.set_function(a, b, dark);
+            }
+        }
+    }
+
+    fn draw_format_bits(&mut self, mask: usize) {
+        let size = self.size;
+        let bits = format_bits(mask);
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+        for i in 0..=5 {
+            self.set_function(i, 8, bit(i));
+        }
+        self.set_function(7, 8, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(8, 7, bit(8));
+        for i in 9..15 {
+            self.set_function(8, 14 - i, bit(i));
+        }
+        for i in 0..8 {
+            self.set_function(8, size - 1 - i, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(size - 15 + i, 8, bit(i));
+        }
+        self.set_function(size - 8, 8, true);
+    }
+
+    /// Places the codewords in the zigzag order, two columns at a time from the right.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size;
+        let mut i = 0;
+        let mut right = size as isize - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..size {
+                for j in 0..2 {
+                    let col = (right - j) as usize;
+                    let upward = (right + 1) & 2 == 0;
+                    let row = if upward {
+                        size - 1 - vertical
+                    } else {
+                        vertical
+                    };
+                    if !self.is_function[row][col] && i < codewords.len() * 8 {
+                        self.modules[row][col] = (codewords[i / 8] >> (7 - i % 8)) & 1 == 1;
+                        i += 1;
+                    }
+                }
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: usize) {
+        for row in 0..self.size {
+            for col in 0..self.size {
+                if !self.is_function[row][col] && mask_applies(mask, row, col) {
+                    self.modules[row][col] ^= true;
+                }
+            }
+        }
+    }
+
+    /// Penalty of runs, 2×2 blocks and dark proportion, without the finder-like rule.
+    fn penalty(&self) -> usize {
+        let size = self.size;
+        let runs = |line: &mut dyn Iterator<Item = bool>| {
+            line.chunk_by(|&dark| dark)
+                .into_iter()
+                .map(|(_, run)| run.count())
+                .filter(|&len| len >= 5)
+                .map(|len| len - 2)
+                .sum::<usize>()
+        };
+        let mut penalty = 0;
+        for i in 0..size {
+            penalty += runs(&mut self.modules[i].iter().copied());
+            penalty += runs(&mut (0..size).map(|row| self.modules[row][i]));
+        }
+        for row in 0..size - 1 {
+            for col in 0..size - 1 {
+                let dark = self.modules[row][col];
+                if self.modules[row][col + 1] == dark
+                    && self.modules[row + 1][col] == dark
+                    && self.modules[row + 1][col + 1] == dark
+                {
+                    penalty += 3;
+                }
+            }
+        }
+        let dark = self.modules.iter().flatten().filter(|&&dark| dark).count();
+        let total = size * size;
+        let deviation = (dark * 20).abs_diff(total * 10);
+        penalty + deviation.div_ceil(total).saturating_sub(1) * 10
+    }
+}
+
+impl QrCode {
+    /// `None` if `data` is too long even for version 40.
+    pub fn encode(data: &[u8]) -> Option<Self> {
+        let version = version_for(data.len())?;
+        let size = version * 4 + 17;
+        let mut canvas = Canvas {
+            size,
+            modules: vec![vec![false; size]; size],
+            is_function: vec![vec![false; size]; size],
+        };
+        canvas.draw_function_patterns(version);
+        canvas.draw_codewords(&codewords(data, version));
+        let mask = (0..8)
+            .min_by_key(|&mask| {
+                canvas.apply_mask(mask);
+                canvas.draw_format_bits(mask);
+                let penalty = canvas.penalty();
+                // masking twice restores the modules
+                canvas.apply_mask(mask);
+                penalty
+            })
+            .unwrap();
+        canvas.apply_mask(mask);
+        canvas.draw_format_bits(mask);
+        Some(Self {
+            version,
+            modules: canvas.modules,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// SVG with a quiet zone of four modules, scaled to fit its container.
+    pub fn to_svg(&self) -> String {
+        let size = self.size() + 8;
+        let path = self
+            .modules
+            .iter()
+            .enumerate()
+            .flat_map(|(row, modules)| {
+                modules
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, &dark)| dark)
+                    .map(move |(col, _)| format!("M{},{}h1v1h-1z", col + 4, row + 4))
+            })
+            .join("");
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {size} {size}" shape-rendering="crispEdges"><rect width="{size}" height="{size}" fill="#fff"/><path d="{path}" fill="#000"/></svg>"##
+        )
+    }
+}
+
+#[test]
+fn test_qr_code() {
+    let qr = QrCode::encode(b"https://example.com/#abc").unwrap();
+    assert_eq!(qr.version, 2);
+    assert_eq!(qr.size(), 25);
+    // finder pattern in the top left corner and its separator
+    assert_eq!(
+        qr.modules[0][..8],
+        [true, true, true, true, true, true, true, false]
+    );
+    assert_eq!(
+        qr.modules[2][..8],
+        [true, false, true, true, true, false, true, false]
+    );
+    // dark module
+    assert!(qr.modules[qr.size() - 8][8]);
+    // the format bits are duplicated
+    let first = (0..=5)
+        .map(|i| qr.modules[i][8])
+        .chain([qr.modules[7][8], qr.modules[8][8], qr.modules[8][7]])
+        .chain((9..15).map(|i| qr.modules[8][14 - i]))
+        .collect_vec();
+    let second = (0..8)
+        .map(|i| qr.modules[8][qr.size() - 1 - i])
+        .chain((8..15).map(|i| qr.modules[qr.size() - 15 + i][8]))
+        .collect_vec();
+    assert_eq!(first, second);
+    assert!(qr.to_svg().starts_with("<svg"));
+    assert!(QrCode::encode(&[0; 3000]).is_none());
+}